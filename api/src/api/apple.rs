@@ -2,14 +2,23 @@ use actix_web::{web, HttpResponse};
 use log::info;
 use sea_orm::DbConn;
 
+use crate::api::provider;
 use swaptun_services::auth::Claims;
 use swaptun_services::error::AppError;
-use swaptun_services::{AddTokenRequest, AppleMusicService, UserService};
+use swaptun_services::{
+    AddTokenRequest, AppleMusicService, PlaylistOrigin, UpdateImportSelectionRequest, UserService,
+};
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("/token").post(set_token))
         .service(web::resource("/developer-token").get(get_developer_token))
         .service(web::resource("/synchronize").post(synchronize_playlist))
+        .route("/remote-playlists", web::get().to(get_remote_playlists))
+        .service(
+            web::resource("/import-selection")
+                .put(update_import_selection)
+                .delete(clear_import_selection),
+        )
         .route("/disconnect", web::delete().to(disconnect));
 }
 
@@ -47,27 +56,34 @@ async fn synchronize_playlist(
     db: web::Data<DbConn>,
     claims: web::ReqData<Claims>,
 ) -> Result<HttpResponse, AppError> {
-    let claims = claims.into_inner();
-    let user_service = UserService::new(db.get_ref().clone().into());
-    let user = user_service.get_user_from_claims(claims).await?;
-
-    let apple_music_service = AppleMusicService::new(db.get_ref().clone().into());
-    let _ = apple_music_service.import_playlists(&user).await?;
-    Ok(HttpResponse::Ok().finish())
+    provider::import_playlists(db, claims, PlaylistOrigin::AppleMusic).await
 }
 
-async fn disconnect(
+async fn get_remote_playlists(
     db: web::Data<DbConn>,
     claims: web::ReqData<Claims>,
 ) -> Result<HttpResponse, AppError> {
-    let apple_service = AppleMusicService::new(db.get_ref().clone().into());
-    let user_service = UserService::new(db.get_ref().clone().into());
+    provider::get_remote_playlists(db, claims, PlaylistOrigin::AppleMusic).await
+}
 
-    let user = user_service
-        .get_user_from_claims(claims.into_inner())
-        .await?;
+async fn update_import_selection(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    req: web::Json<UpdateImportSelectionRequest>,
+) -> Result<HttpResponse, AppError> {
+    provider::update_import_selection(db, claims, req, PlaylistOrigin::AppleMusic).await
+}
 
-    apple_service.disconnect(&user).await?;
+async fn clear_import_selection(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    provider::clear_import_selection(db, claims, PlaylistOrigin::AppleMusic).await
+}
 
-    Ok(HttpResponse::NoContent().finish())
+async fn disconnect(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    provider::disconnect(db, claims, PlaylistOrigin::AppleMusic).await
 }
@@ -0,0 +1,17 @@
+use actix_web::{web, HttpResponse};
+use sea_orm::DbConn;
+
+use swaptun_services::error::AppError;
+use swaptun_services::ArtistService;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/{id}").get(get_artist));
+}
+
+/// Artist along with their albums and every music they are credited on
+async fn get_artist(db: web::Data<DbConn>, path: web::Path<i32>) -> Result<HttpResponse, AppError> {
+    let artist_service = ArtistService::new(db.get_ref().clone().into());
+    let artist = artist_service.get_artist(path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(artist))
+}
@@ -1,16 +1,36 @@
 use actix_web::{web, HttpResponse};
+use log::info;
 use sea_orm::DbConn;
 
+use crate::api::provider;
 use swaptun_services::auth::Claims;
 use swaptun_services::error::AppError;
-use swaptun_services::{DeezerService, UserService};
+use swaptun_services::{
+    AddTokenRequest, DeezerService, PlaylistOrigin, UpdateImportSelectionRequest, UserService,
+};
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.route("/disconnect", web::delete().to(disconnect));
+    cfg.route("/authorization-url", web::get().to(get_authorization_url))
+        .service(web::resource("/token").post(set_token))
+        .route("/playlists", web::post().to(import_playlists))
+        .route("/remote-playlists", web::get().to(get_remote_playlists))
+        .service(
+            web::resource("/import-selection")
+                .put(update_import_selection)
+                .delete(clear_import_selection),
+        )
+        .route("/disconnect", web::delete().to(disconnect));
 }
 
-async fn disconnect(
+async fn get_authorization_url(db: web::Data<DbConn>) -> Result<HttpResponse, AppError> {
+    let deezer_service = DeezerService::new(db.get_ref().clone().into());
+    let authorization_url = deezer_service.get_authorization_url().await?;
+    Ok(HttpResponse::Ok().json(authorization_url))
+}
+
+async fn set_token(
     db: web::Data<DbConn>,
+    req: web::Json<AddTokenRequest>,
     claims: web::ReqData<Claims>,
 ) -> Result<HttpResponse, AppError> {
     let deezer_service = DeezerService::new(db.get_ref().clone().into());
@@ -19,8 +39,46 @@ async fn disconnect(
     let user = user_service
         .get_user_from_claims(claims.into_inner())
         .await?;
+    deezer_service
+        .auth_callback(&user, req.into_inner())
+        .await?;
+    info!("Deezer token added for user");
+
+    Ok(HttpResponse::Ok().json(true))
+}
+
+async fn import_playlists(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    provider::import_playlists(db, claims, PlaylistOrigin::Deezer).await
+}
+
+async fn get_remote_playlists(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    provider::get_remote_playlists(db, claims, PlaylistOrigin::Deezer).await
+}
 
-    deezer_service.disconnect(&user).await?;
+async fn update_import_selection(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    req: web::Json<UpdateImportSelectionRequest>,
+) -> Result<HttpResponse, AppError> {
+    provider::update_import_selection(db, claims, req, PlaylistOrigin::Deezer).await
+}
 
-    Ok(HttpResponse::NoContent().finish())
+async fn clear_import_selection(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    provider::clear_import_selection(db, claims, PlaylistOrigin::Deezer).await
+}
+
+async fn disconnect(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    provider::disconnect(db, claims, PlaylistOrigin::Deezer).await
 }
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use actix_web::web::Bytes;
+use actix_web::{web, HttpResponse};
+use log::warn;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use swaptun_services::auth::{generate_stream_token, Claims};
+use swaptun_services::error::AppError;
+use swaptun_services::{EventHub, UserEvent};
+
+/// Comment sent on idle streams so proxies do not close them
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").get(stream_events))
+        .service(web::resource("/token").post(create_stream_token));
+}
+
+/// Receiver of a stream, handed back to the hub when the client disconnects
+struct Subscription {
+    user_id: i32,
+    receiver: Option<broadcast::Receiver<UserEvent>>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(receiver) = self.receiver.take() {
+            EventHub::global().unsubscribe(self.user_id, receiver);
+        }
+    }
+}
+
+/// Short-lived token to open the stream with `?token=`, EventSource can not send headers
+async fn create_stream_token(claims: web::ReqData<Claims>) -> Result<HttpResponse, AppError> {
+    let token = generate_stream_token(&claims)?;
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "token": token })))
+}
+
+/// Server-Sent Events stream of the authenticated user's live updates
+async fn stream_events(claims: web::ReqData<Claims>) -> HttpResponse {
+    let user_id = claims.user_id;
+    let subscription = Subscription {
+        user_id,
+        receiver: Some(EventHub::global().subscribe(user_id)),
+    };
+
+    let stream = futures::stream::unfold(subscription, move |mut subscription| async move {
+        let receiver = subscription.receiver.as_mut()?;
+        let frame = match tokio::time::timeout(KEEP_ALIVE_INTERVAL, receiver.recv()).await {
+            Ok(Ok(event)) => event.to_sse_frame(),
+            Ok(Err(RecvError::Lagged(skipped))) => {
+                warn!(
+                    "Event stream of user {} skipped {} events",
+                    user_id, skipped
+                );
+                format!(": skipped {} events\n\n", skipped)
+            }
+            Ok(Err(RecvError::Closed)) => return None,
+            Err(_) => ": keep-alive\n\n".to_string(),
+        };
+        Some((Ok::<_, actix_web::Error>(Bytes::from(frame)), subscription))
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(stream)
+}
@@ -0,0 +1,44 @@
+use actix_web::{web, HttpResponse};
+use sea_orm::DbConn;
+
+use swaptun_services::auth::Claims;
+use swaptun_services::error::AppError;
+use swaptun_services::{GetJobsParams, JobService};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").get(get_jobs))
+        .service(web::resource("/{id}").get(get_job));
+}
+
+/// Imports and exports of the authenticated user, most recent first, paged with `limit`
+/// and `offset`
+async fn get_jobs(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    query: web::Query<GetJobsParams>,
+) -> Result<HttpResponse, AppError> {
+    let job_service = JobService::new(db.get_ref().clone().into());
+    let jobs = job_service
+        .get_user_jobs(claims.user_id, query.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(jobs))
+}
+
+/// Status and progress of one of the user's jobs
+async fn get_job(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let job_service = JobService::new(db.get_ref().clone().into());
+    let job = job_service.get_job(path.into_inner()).await?;
+
+    if job.user_id != claims.user_id {
+        return Err(AppError::Unauthorized(
+            "You do not have permission to view this job".to_string(),
+        ));
+    }
+
+    Ok(HttpResponse::Ok().json(job))
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use actix_web::{web, HttpResponse};
+use sea_orm::{DatabaseConnection, DbConn};
+
+use swaptun_services::auth::Claims;
+use swaptun_services::error::AppError;
+use swaptun_services::validators::user_validators::process_json_validation;
+use swaptun_services::{
+    CreatePlaylistMirrorRequest, PlaylistMirrorService, ProviderRegistry, UserService,
+};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").get(get_mirrors).post(create_mirror))
+        .service(web::resource("/{id}").delete(delete_mirror));
+}
+
+/// Playlists of the authenticated user kept in sync on another platform
+async fn get_mirrors(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    let playlist_mirror_service = PlaylistMirrorService::new(db.get_ref().clone().into());
+    let mirrors = playlist_mirror_service
+        .get_user_mirrors(claims.user_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(mirrors))
+}
+
+/// Starts mirroring a playlist, its first push runs as a background job
+async fn create_mirror(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    req: web::Json<CreatePlaylistMirrorRequest>,
+) -> Result<HttpResponse, AppError> {
+    process_json_validation(&req)?;
+
+    let db: Arc<DatabaseConnection> = db.get_ref().clone().into();
+    let user_service = UserService::new(db.clone());
+    let user = user_service
+        .get_user_from_claims(claims.into_inner())
+        .await?;
+
+    // Fail fast on platforms we cannot export to, every push would fail later
+    ProviderRegistry::new(db.clone()).get(&req.destination)?;
+
+    let playlist_mirror_service = PlaylistMirrorService::new(db);
+    let mirror = playlist_mirror_service
+        .create(&user, req.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Created().json(mirror))
+}
+
+async fn delete_mirror(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let playlist_mirror_service = PlaylistMirrorService::new(db.get_ref().clone().into());
+    playlist_mirror_service
+        .delete(claims.user_id, path.into_inner())
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
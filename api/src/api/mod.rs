@@ -3,12 +3,18 @@ use actix_web::{web, HttpResponse};
 use sea_orm::DbConn;
 use swaptun_services::auth::jwt::{JwtMiddleware, RoleGuard};
 mod apple;
+mod artist;
 mod auth;
 mod deezer;
+mod event;
+mod job;
+mod mirror;
 mod musicbrainz;
 mod notification;
 mod playlist;
+mod provider;
 mod spotify;
+mod track_match;
 mod user_info;
 mod users;
 mod youtube;
@@ -35,7 +41,14 @@ pub fn configure_routes(cfg: &mut ServiceConfig, db: DbConn) {
                         .service(web::scope("/apple").configure(|c| apple::configure(c)))
                         .service(web::scope("/deezer").configure(|c| deezer::configure(c)))
                         .service(web::scope("/playlists").configure(|c| playlist::configure(c)))
+                        .service(web::scope("/artists").configure(|c| artist::configure(c)))
+                        .service(web::scope("/jobs").configure(|c| job::configure(c)))
+                        .service(web::scope("/mirrors").configure(|c| mirror::configure(c)))
+                        .service(web::scope("/events").configure(|c| event::configure(c)))
                         .service(web::scope("/youtube").configure(|c| youtube::configure(c)))
+                        .service(
+                            web::scope("/track-matches").configure(|c| track_match::configure(c)),
+                        )
                         .service(web::scope("/musicbrainz").configure(musicbrainz::configure))
                         .service(web::scope("/user_info").configure(|c| user_info::configure(c)))
                         .service(
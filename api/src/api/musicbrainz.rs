@@ -1,20 +1,49 @@
-use actix_web::{HttpResponse, web};
+use actix_web::{web, HttpResponse};
+use sea_orm::DbConn;
 use swaptun_services::error::AppError;
 use swaptun_services::musicbrainz::MusicBrainzService;
 
 use std::collections::HashMap;
 
-
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.route("/track_metadata", web::get().to(get_track_metadata));
+    cfg.route("/track_metadata", web::get().to(get_track_metadata))
+        .route("/recordings", web::get().to(search_recordings));
+}
+
+fn title_and_artist(query: &HashMap<String, String>) -> Result<(&str, &str), AppError> {
+    let title = query
+        .get("title")
+        .ok_or_else(|| AppError::Validation("title is required".to_string()))?;
+    let artist = query
+        .get("artist")
+        .ok_or_else(|| AppError::Validation("artist is required".to_string()))?;
+    Ok((title, artist))
 }
 
-pub async fn get_track_metadata(query: web::Query<HashMap<String, String>>) -> Result<HttpResponse, AppError> {
-    let title = query.get("title").ok_or_else(|| AppError::InternalServerError)?;
-    let artist = query.get("artist").ok_or_else(|| AppError::InternalServerError)?;
+pub async fn get_track_metadata(
+    db: web::Data<DbConn>,
+    query: web::Query<HashMap<String, String>>,
+) -> Result<HttpResponse, AppError> {
+    let (title, artist) = title_and_artist(&query)?;
 
-    let service = MusicBrainzService::new();
-    let track_info = service.search_track(title, artist)?;
+    let service = MusicBrainzService::new(db.get_ref().clone().into());
+    let track_info = service
+        .search_track(title, artist)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("No recording found for {artist} - {title}")))?;
 
     Ok(HttpResponse::Ok().json(track_info))
-}
\ No newline at end of file
+}
+
+/// Every candidate recording, best score first
+pub async fn search_recordings(
+    db: web::Data<DbConn>,
+    query: web::Query<HashMap<String, String>>,
+) -> Result<HttpResponse, AppError> {
+    let (title, artist) = title_and_artist(&query)?;
+
+    let service = MusicBrainzService::new(db.get_ref().clone().into());
+    let recordings = service.search_recordings(title, artist).await?;
+
+    Ok(HttpResponse::Ok().json(recordings))
+}
@@ -6,11 +6,13 @@ use sea_orm::{DatabaseConnection, DbConn};
 
 use swaptun_services::auth::Claims;
 use swaptun_services::error::AppError;
+use swaptun_services::validators::user_validators::process_json_validation;
 use swaptun_services::{
-    AppleMusicService, CreateMusicRequest, CreatePlaylistRequest, DeezerService,
-    DeletePlaylistRequest, GetPlaylistsParams, GetSharedPlaylistsParams, NotificationService,
-    PlaylistOrigin, PlaylistService, SendPlaylistRequest, SendPlaylistResponse,
-    SharePlaylistRequest, SpotifyService, UpdatePlaylistRequest, UserService, YoutubeMusicService,
+    CreateMusicRequest, CreatePlaylistRequest, DeletePlaylistRequest, EventHub, GetPlaylistsParams,
+    GetSharedPlaylistsParams, JobPayload, JobProgress, JobService, MoveMusicRequest, MusicProvider,
+    NotificationService, PlaylistService, ProviderRegistry, RemoveMusicParams,
+    SendPlaylistPreviewResponse, SendPlaylistRequest, SharePlaylistRequest, SnapshotDiffParams,
+    UpdatePlaylistRequest, UserEvent, UserService,
 };
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -28,13 +30,17 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     )
     .service(web::resource("/{id}/details").get(get_playlist_details))
     .service(web::resource("/{id}/musics").get(get_playlist_musics))
-    .service(
-        web::resource("/{id}/music")
-            .post(add_music_to_playlist)
-            .delete(remove_music_from_playlist),
-    )
+    .service(web::resource("/{id}/music").post(add_music_to_playlist))
+    .service(web::resource("/{id}/music/{music_id}").delete(remove_music_from_playlist))
+    .service(web::resource("/{id}/music/{music_id}/position").put(move_music_in_playlist))
     .service(web::resource("/{id}/send").post(send_playlist_to_origin))
-    .service(web::resource("/{id}/share").post(share_playlist));
+    .service(web::resource("/{id}/send/preview").post(preview_send_playlist))
+    .service(web::resource("/{id}/share").post(share_playlist))
+    .service(web::resource("/{id}/snapshots").get(get_playlist_snapshots))
+    .service(web::resource("/{id}/snapshots/diff").get(diff_playlist_snapshots))
+    .service(
+        web::resource("/{id}/snapshots/{snapshot_id}/restore").post(restore_playlist_snapshot),
+    );
 }
 
 async fn get_user_playlists(
@@ -213,7 +219,8 @@ async fn add_music_to_playlist(
 async fn remove_music_from_playlist(
     db: web::Data<DbConn>,
     req: HttpRequest,
-    path: web::Path<(i32, String, String, String)>,
+    path: web::Path<(i32, i32)>,
+    query: web::Query<RemoveMusicParams>,
 ) -> Result<HttpResponse, AppError> {
     let claims = req
         .extensions()
@@ -221,7 +228,7 @@ async fn remove_music_from_playlist(
         .cloned()
         .ok_or_else(|| AppError::Unauthorized("No authentication token found".to_string()))?;
 
-    let (playlist_id, title, artist, album) = path.into_inner();
+    let (playlist_id, music_id) = path.into_inner();
     let playlist_service = PlaylistService::new(db.get_ref().clone().into());
     let playlist = playlist_service.get_playlist(playlist_id).await?;
 
@@ -233,11 +240,49 @@ async fn remove_music_from_playlist(
 
     let music_service = swaptun_services::MusicService::new(db.get_ref().clone().into());
     let music = music_service
-        .find_by_id(title, artist, album)
+        .find_by_id(music_id)
         .await?
         .ok_or_else(|| AppError::NotFound("Music not found".to_string()))?;
 
-    playlist_service.remove_music(&playlist, &music).await?;
+    playlist_service
+        .remove_music(&playlist, &music, query.occurrence)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+async fn move_music_in_playlist(
+    db: web::Data<DbConn>,
+    req: HttpRequest,
+    path: web::Path<(i32, i32)>,
+    request: web::Json<MoveMusicRequest>,
+) -> Result<HttpResponse, AppError> {
+    process_json_validation(&request)?;
+
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or_else(|| AppError::Unauthorized("No authentication token found".to_string()))?;
+
+    let (playlist_id, music_id) = path.into_inner();
+    let playlist_service = PlaylistService::new(db.get_ref().clone().into());
+    let playlist = playlist_service.get_playlist(playlist_id).await?;
+
+    if playlist.user_id != claims.user_id {
+        return Err(AppError::Unauthorized(
+            "You do not have permission to modify this playlist".to_string(),
+        ));
+    }
+
+    playlist_service
+        .move_music(
+            &playlist,
+            music_id,
+            request.occurrence as usize,
+            request.position as usize,
+        )
+        .await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
@@ -255,40 +300,59 @@ async fn send_playlist_to_origin(
         .await?;
     let req = req.into_inner();
     let playlist_id = path.into_inner();
-    let destination = req.destination.clone();
-
-    // Send playlist based on its destination
-    let platform_playlist_id = match destination {
-        PlaylistOrigin::Spotify => {
-            let spotify_service = SpotifyService::new(db.clone());
-            spotify_service
-                .create_spotify_playlist_from_db(playlist_id, &user)
-                .await?
-        }
-        PlaylistOrigin::YoutubeMusic => {
-            let youtube_service = YoutubeMusicService::new(db.clone());
-            youtube_service
-                .import_playlist_in_yt(&user, playlist_id)
-                .await?
-        }
-        PlaylistOrigin::AppleMusic => {
-            let apple_service = AppleMusicService::new(db.clone());
-            apple_service
-                .export_playlist_to_apple(playlist_id, &user)
-                .await?
-        }
-        PlaylistOrigin::Deezer => {
-            return Err(AppError::InternalServerError);
-        }
-    };
 
-    let response = SendPlaylistResponse {
+    // Fail fast on platforms we cannot export to, the worker would only fail later
+    ProviderRegistry::new(db.clone()).get(&req.destination)?;
+    if let Some(matches) = req.matches.as_ref() {
+        PlaylistService::new(db.clone())
+            .check_matches(playlist_id, matches)
+            .await?;
+    }
+
+    let job_service = JobService::new(db);
+    let job = job_service
+        .enqueue(
+            user.id,
+            JobPayload::ExportPlaylist {
+                playlist_id,
+                destination: req.destination,
+                matches: req.matches,
+                create_new: req.create_new,
+            },
+        )
+        .await?;
+
+    Ok(HttpResponse::Accepted().json(job))
+}
+
+async fn preview_send_playlist(
+    db: web::Data<DbConn>,
+    req: web::Json<SendPlaylistRequest>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let db: Arc<DatabaseConnection> = db.get_ref().clone().into();
+    let user_service = UserService::new(db.clone());
+    let user = user_service
+        .get_user_from_claims(claims.into_inner())
+        .await?;
+    let req = req.into_inner();
+    let playlist_id = path.into_inner();
+
+    let registry = ProviderRegistry::new(db.clone());
+    let report = registry
+        .get(&req.destination)?
+        .preview_playlist(&user, playlist_id, &JobProgress::none())
+        .await?;
+
+    let response = SendPlaylistPreviewResponse {
         platform: req.destination,
-        playlist_id: platform_playlist_id,
+        report,
     };
 
     Ok(HttpResponse::Ok().json(response))
 }
+
 async fn share_playlist(
     db: web::Data<DbConn>,
     req: web::Json<SharePlaylistRequest>,
@@ -321,6 +385,15 @@ async fn share_playlist(
             .await
             .ok();
 
+        EventHub::global().publish(
+            shared_with_user.id,
+            UserEvent::PlaylistShared {
+                playlist_id: playlist_model.id,
+                shared_by_id: current_user.id,
+                shared_by_username: current_user.username.clone(),
+            },
+        );
+
         // Envoyer une notification à l'utilisateur
         match NotificationService::new(db.clone()).await {
             Ok(notification_service) => {
@@ -392,3 +465,47 @@ async fn share_playlist(
 
     Ok(HttpResponse::NoContent().finish())
 }
+
+/// Past versions of the playlist, most recent first
+async fn get_playlist_snapshots(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let playlist_service = PlaylistService::new(db.get_ref().clone().into());
+    let snapshots = playlist_service
+        .get_snapshots(path.into_inner(), claims.user_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(snapshots))
+}
+
+/// Musics added, removed and moved between the snapshots `from` and `to`
+async fn diff_playlist_snapshots(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<i32>,
+    query: web::Query<SnapshotDiffParams>,
+) -> Result<HttpResponse, AppError> {
+    let playlist_service = PlaylistService::new(db.get_ref().clone().into());
+    let diff = playlist_service
+        .diff_snapshots(path.into_inner(), claims.user_id, query.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(diff))
+}
+
+/// Replaces the musics of the playlist with those of the snapshot
+async fn restore_playlist_snapshot(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, AppError> {
+    let (playlist_id, snapshot_id) = path.into_inner();
+    let playlist_service = PlaylistService::new(db.get_ref().clone().into());
+    playlist_service
+        .restore_snapshot(playlist_id, snapshot_id, claims.user_id)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,108 @@
+use actix_web::{web, HttpResponse};
+use sea_orm::DbConn;
+
+use swaptun_services::auth::Claims;
+use swaptun_services::error::AppError;
+use swaptun_services::{
+    ImportSelectionService, JobPayload, JobService, MusicProvider, PlaylistOrigin,
+    ProviderRegistry, UpdateImportSelectionRequest, UserService,
+};
+
+/// Disconnects the authenticated user from the given platform
+pub async fn disconnect(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    origin: PlaylistOrigin,
+) -> Result<HttpResponse, AppError> {
+    let registry = ProviderRegistry::new(db.get_ref().clone().into());
+    let user_service = UserService::new(db.get_ref().clone().into());
+
+    let user = user_service
+        .get_user_from_claims(claims.into_inner())
+        .await?;
+
+    registry.get(&origin)?.disconnect(&user).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Queues the import of the authenticated user's playlists from the given platform
+pub async fn import_playlists(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    origin: PlaylistOrigin,
+) -> Result<HttpResponse, AppError> {
+    let job_service = JobService::new(db.get_ref().clone().into());
+    let user_service = UserService::new(db.get_ref().clone().into());
+
+    let user = user_service
+        .get_user_from_claims(claims.into_inner())
+        .await?;
+
+    let job = job_service
+        .enqueue(user.id, JobPayload::ImportPlaylists { origin })
+        .await?;
+
+    Ok(HttpResponse::Accepted().json(job))
+}
+
+/// Lists the authenticated user's playlists on the given platform without importing them
+pub async fn get_remote_playlists(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    origin: PlaylistOrigin,
+) -> Result<HttpResponse, AppError> {
+    let registry = ProviderRegistry::new(db.get_ref().clone().into());
+    let import_selection_service = ImportSelectionService::new(db.get_ref().clone().into());
+    let user_service = UserService::new(db.get_ref().clone().into());
+
+    let user = user_service
+        .get_user_from_claims(claims.into_inner())
+        .await?;
+
+    let response = import_selection_service
+        .get_remote_playlists(registry.get(&origin)?.as_ref(), &user)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Limits the imports from the given platform to the selected playlists, then queues an import
+pub async fn update_import_selection(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<UpdateImportSelectionRequest>,
+    origin: PlaylistOrigin,
+) -> Result<HttpResponse, AppError> {
+    let import_selection_service = ImportSelectionService::new(db.get_ref().clone().into());
+    let job_service = JobService::new(db.get_ref().clone().into());
+    let user_service = UserService::new(db.get_ref().clone().into());
+
+    let user = user_service
+        .get_user_from_claims(claims.into_inner())
+        .await?;
+
+    import_selection_service
+        .save(user.id, origin.clone(), request.into_inner())
+        .await?;
+    let job = job_service
+        .enqueue(user.id, JobPayload::ImportPlaylists { origin })
+        .await?;
+
+    Ok(HttpResponse::Accepted().json(job))
+}
+
+/// Goes back to importing every playlist from the given platform
+pub async fn clear_import_selection(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    origin: PlaylistOrigin,
+) -> Result<HttpResponse, AppError> {
+    let import_selection_service = ImportSelectionService::new(db.get_ref().clone().into());
+
+    import_selection_service
+        .clear(claims.user_id, origin)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -1,14 +1,23 @@
-use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use actix_web::{web, HttpResponse};
 use log::info;
 use sea_orm::DbConn;
 
+use crate::api::provider;
 use swaptun_services::auth::Claims;
 use swaptun_services::error::AppError;
-use swaptun_services::{AddTokenRequest, SpotifyService, UserService};
+use swaptun_services::{
+    AddTokenRequest, PlaylistOrigin, SpotifyService, UpdateImportSelectionRequest, UserService,
+};
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.route("/authorization-url", web::get().to(get_authorization_url))
         .service(web::resource("/token").post(set_token))
         .route("/playlist", web::post().to(post_user_playlists))
+        .route("/remote-playlists", web::get().to(get_remote_playlists))
+        .service(
+            web::resource("/import-selection")
+                .put(update_import_selection)
+                .delete(clear_import_selection),
+        )
         .route("/disconnect", web::delete().to(disconnect));
 }
 
@@ -40,35 +49,36 @@ async fn set_token(
 
 async fn post_user_playlists(
     db: web::Data<DbConn>,
-    http_req: HttpRequest,
+    claims: web::ReqData<Claims>,
 ) -> Result<HttpResponse, AppError> {
-    let spotify_service = SpotifyService::new(db.get_ref().clone().into());
-    let user_service = UserService::new(db.get_ref().clone().into());
-    let claims = http_req.extensions().get::<Claims>().cloned();
-
-    if let Some(claims) = claims {
-        let user = user_service.get_user_from_claims(claims).await?;
-        let playlists = spotify_service.get_user_playlists(user).await?;
-        info!("playlists {:?}", playlists);
-        info!("fin de playlists spotify");
-        Ok(HttpResponse::Ok().finish())
-    } else {
-        Err(AppError::Unauthorized("Unauthorized".to_string()))
-    }
+    provider::import_playlists(db, claims, PlaylistOrigin::Spotify).await
 }
 
-async fn disconnect(
+async fn get_remote_playlists(
     db: web::Data<DbConn>,
     claims: web::ReqData<Claims>,
 ) -> Result<HttpResponse, AppError> {
-    let spotify_service = SpotifyService::new(db.get_ref().clone().into());
-    let user_service = UserService::new(db.get_ref().clone().into());
+    provider::get_remote_playlists(db, claims, PlaylistOrigin::Spotify).await
+}
 
-    let user = user_service
-        .get_user_from_claims(claims.into_inner())
-        .await?;
+async fn update_import_selection(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    req: web::Json<UpdateImportSelectionRequest>,
+) -> Result<HttpResponse, AppError> {
+    provider::update_import_selection(db, claims, req, PlaylistOrigin::Spotify).await
+}
 
-    spotify_service.disconnect(&user).await?;
+async fn clear_import_selection(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    provider::clear_import_selection(db, claims, PlaylistOrigin::Spotify).await
+}
 
-    Ok(HttpResponse::NoContent().finish())
+async fn disconnect(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    provider::disconnect(db, claims, PlaylistOrigin::Spotify).await
 }
@@ -0,0 +1,57 @@
+use actix_web::{web, HttpResponse};
+use sea_orm::DbConn;
+
+use swaptun_services::auth::Claims;
+use swaptun_services::error::AppError;
+use swaptun_services::validators::user_validators::process_json_validation;
+use swaptun_services::{
+    ClearTrackMatchOverrideRequest, SetTrackMatchOverrideRequest, TrackMatchOverrideService,
+    UserService,
+};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.route("/overrides", web::put().to(set_override))
+        .route("/overrides", web::delete().to(clear_override));
+}
+
+/// Pins the platform track to use for one of our musics on later exports
+async fn set_override(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    req: web::Json<SetTrackMatchOverrideRequest>,
+) -> Result<HttpResponse, AppError> {
+    process_json_validation(&req)?;
+
+    let user_service = UserService::new(db.get_ref().clone().into());
+    let track_match_override_service = TrackMatchOverrideService::new(db.get_ref().clone().into());
+
+    let user = user_service
+        .get_user_from_claims(claims.into_inner())
+        .await?;
+
+    track_match_override_service
+        .set_override(&user, req.into_inner())
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Removes a pinned track so the matcher searches again on the next export
+async fn clear_override(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    req: web::Json<ClearTrackMatchOverrideRequest>,
+) -> Result<HttpResponse, AppError> {
+    let user_service = UserService::new(db.get_ref().clone().into());
+    let track_match_override_service = TrackMatchOverrideService::new(db.get_ref().clone().into());
+
+    let user = user_service
+        .get_user_from_claims(claims.into_inner())
+        .await?;
+
+    track_match_override_service
+        .clear_override(&user, req.into_inner())
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
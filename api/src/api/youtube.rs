@@ -1,14 +1,23 @@
 use actix_web::{web, HttpResponse};
 use sea_orm::DbConn;
 
+use crate::api::provider;
 use log::info;
 use swaptun_services::auth::Claims;
 use swaptun_services::error::AppError;
-use swaptun_services::{UserService, YoutubeMusicService};
+use swaptun_services::{
+    PlaylistOrigin, UpdateImportSelectionRequest, UserService, YoutubeMusicService,
+};
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.route("/authorization-url", web::get().to(get_authorization_url))
         .service(web::resource("/token").post(set_token))
         .route("/playlists", web::get().to(get_playlists))
+        .route("/remote-playlists", web::get().to(get_remote_playlists))
+        .service(
+            web::resource("/import-selection")
+                .put(update_import_selection)
+                .delete(clear_import_selection),
+        )
         .route("/disconnect", web::delete().to(disconnect));
 }
 
@@ -49,27 +58,34 @@ async fn get_playlists(
     db: web::Data<DbConn>,
     claims: web::ReqData<Claims>,
 ) -> Result<HttpResponse, AppError> {
-    let youtube_service = YoutubeMusicService::new(db.get_ref().clone().into());
-    let user_service = UserService::new(db.get_ref().clone().into());
-    let claims = claims.into_inner();
-
-    let user = user_service.get_user_from_claims(claims).await?;
-    let playlists = youtube_service.get_user_playlists(&user).await?;
-    Ok(HttpResponse::Ok().json(playlists))
+    provider::import_playlists(db, claims, PlaylistOrigin::YoutubeMusic).await
 }
 
-async fn disconnect(
+async fn get_remote_playlists(
     db: web::Data<DbConn>,
     claims: web::ReqData<Claims>,
 ) -> Result<HttpResponse, AppError> {
-    let youtube_service = YoutubeMusicService::new(db.get_ref().clone().into());
-    let user_service = UserService::new(db.get_ref().clone().into());
+    provider::get_remote_playlists(db, claims, PlaylistOrigin::YoutubeMusic).await
+}
 
-    let user = user_service
-        .get_user_from_claims(claims.into_inner())
-        .await?;
+async fn update_import_selection(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+    req: web::Json<UpdateImportSelectionRequest>,
+) -> Result<HttpResponse, AppError> {
+    provider::update_import_selection(db, claims, req, PlaylistOrigin::YoutubeMusic).await
+}
 
-    youtube_service.disconnect(&user).await?;
+async fn clear_import_selection(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    provider::clear_import_selection(db, claims, PlaylistOrigin::YoutubeMusic).await
+}
 
-    Ok(HttpResponse::NoContent().finish())
+async fn disconnect(
+    db: web::Data<DbConn>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse, AppError> {
+    provider::disconnect(db, claims, PlaylistOrigin::YoutubeMusic).await
 }
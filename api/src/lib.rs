@@ -52,6 +52,15 @@ pub async fn main() -> io::Result<()> {
         log::warn!("Mail service connection test skipped in debug mode");
     }
 
+    log::info!("Starting music enrichment worker...");
+    swaptun_services::EnrichmentService::new(db.clone().into()).start();
+
+    log::info!("Starting job workers...");
+    swaptun_services::JobWorker::new(db.clone().into()).start();
+
+    log::info!("Starting playlist sync scheduler...");
+    swaptun_services::SyncScheduler::new(db.clone().into()).start();
+
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(db.clone()))
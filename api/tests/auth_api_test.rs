@@ -221,4 +221,42 @@ mod tests {
 
         test_db.drop().await;
     }
+
+    #[actix_web::test]
+    async fn test_stream_token_only_opens_the_event_stream() {
+        let test_db = TestDatabase::new().await;
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(test_db.get_db()))
+                .configure(|config| api::configure_routes(config, test_db.get_db_raw())),
+        )
+        .await;
+        let token = authenticate_user(&app).await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/events/token")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        let json: serde_json::Value = test::read_body_json(resp).await;
+        let stream_token = json["token"].as_str().unwrap().to_string();
+
+        // The stream token is refused as a regular bearer token
+        let req = test::TestRequest::get()
+            .uri("/api/jobs")
+            .insert_header(("Authorization", format!("Bearer {}", stream_token)))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+
+        // And a session token is refused in the event stream URL
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/events?token={}", token))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+
+        test_db.drop().await;
+    }
 }
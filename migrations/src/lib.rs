@@ -20,6 +20,23 @@ mod m2025_10_05_add_shared_by_to_shared_playlist;
 #[path = "m2025_10_05_remove_unique_constraint_friendships.rs"]
 mod m2025_10_05_remove_unique_constraint_friendships;
 mod m2025_10_18_add_image_url_to_playlist;
+mod m2025_10_20_create_track_match_override_table;
+mod m2025_10_21_create_music_external_id_table;
+mod m2025_10_22_add_isrc_to_music;
+mod m2025_10_23_add_surrogate_id_to_music;
+mod m2025_10_24_add_position_to_music_playlist;
+mod m2025_10_25_add_metadata_to_music;
+mod m2025_10_26_create_artist_and_album_tables;
+mod m2025_10_27_create_musicbrainz_cache_table;
+mod m2025_10_28_add_musicbrainz_enrichment_to_music;
+mod m2025_10_29_create_job_table;
+mod m2025_10_30_add_progress_to_job;
+mod m2025_10_31_create_playlist_export_table;
+mod m2025_11_01_add_last_synced_on_to_playlist;
+mod m2025_11_02_create_playlist_mirror_table;
+mod m2025_11_03_create_playlist_snapshot_table;
+mod m2025_11_04_create_import_selection_table;
+mod m2025_11_05_add_enrichment_failures_to_music;
 pub struct Migrator;
 
 #[async_trait::async_trait]
@@ -45,6 +62,23 @@ impl MigratorTrait for Migrator {
             Box::new(m2025_10_05_add_shared_by_to_shared_playlist::Migration),
             Box::new(m2025_10_05_remove_unique_constraint_friendships::Migration),
             Box::new(m2025_10_18_add_image_url_to_playlist::Migration),
+            Box::new(m2025_10_20_create_track_match_override_table::Migration),
+            Box::new(m2025_10_21_create_music_external_id_table::Migration),
+            Box::new(m2025_10_22_add_isrc_to_music::Migration),
+            Box::new(m2025_10_23_add_surrogate_id_to_music::Migration),
+            Box::new(m2025_10_24_add_position_to_music_playlist::Migration),
+            Box::new(m2025_10_25_add_metadata_to_music::Migration),
+            Box::new(m2025_10_26_create_artist_and_album_tables::Migration),
+            Box::new(m2025_10_27_create_musicbrainz_cache_table::Migration),
+            Box::new(m2025_10_28_add_musicbrainz_enrichment_to_music::Migration),
+            Box::new(m2025_10_29_create_job_table::Migration),
+            Box::new(m2025_10_30_add_progress_to_job::Migration),
+            Box::new(m2025_10_31_create_playlist_export_table::Migration),
+            Box::new(m2025_11_01_add_last_synced_on_to_playlist::Migration),
+            Box::new(m2025_11_02_create_playlist_mirror_table::Migration),
+            Box::new(m2025_11_03_create_playlist_snapshot_table::Migration),
+            Box::new(m2025_11_04_create_import_selection_table::Migration),
+            Box::new(m2025_11_05_add_enrichment_failures_to_music::Migration),
         ]
     }
 }
@@ -0,0 +1,101 @@
+use sea_orm::ActiveEnum;
+use sea_orm_migration::prelude::*;
+use swaptun_models::playlist::PlaylistOrigin;
+
+use crate::m2025_03_19_create_tbl_users::TblUsers;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TrackMatchOverride::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TrackMatchOverride::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TrackMatchOverride::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TrackMatchOverride::MusicTitle)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TrackMatchOverride::MusicArtist)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TrackMatchOverride::MusicAlbum)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TrackMatchOverride::Origin)
+                            .custom(PlaylistOrigin::name())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TrackMatchOverride::PlatformTrackId)
+                            .string()
+                            .not_null()
+                            .comment("Track id chosen by the user on the platform"),
+                    )
+                    .col(
+                        ColumnDef::new(TrackMatchOverride::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TrackMatchOverride::Table, TrackMatchOverride::UserId)
+                            .to(TblUsers::Table, TblUsers::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .name("idx_track_match_override_unique")
+                            .col(TrackMatchOverride::UserId)
+                            .col(TrackMatchOverride::MusicTitle)
+                            .col(TrackMatchOverride::MusicArtist)
+                            .col(TrackMatchOverride::MusicAlbum)
+                            .col(TrackMatchOverride::Origin),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TrackMatchOverride::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TrackMatchOverride {
+    Table,
+    Id,
+    UserId,
+    MusicTitle,
+    MusicArtist,
+    MusicAlbum,
+    Origin,
+    PlatformTrackId,
+    CreatedOn,
+}
@@ -0,0 +1,86 @@
+use sea_orm::ActiveEnum;
+use sea_orm_migration::prelude::*;
+use swaptun_models::playlist::PlaylistOrigin;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MusicExternalId::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MusicExternalId::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(MusicExternalId::MusicTitle)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MusicExternalId::MusicArtist)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MusicExternalId::MusicAlbum)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MusicExternalId::Origin)
+                            .custom(PlaylistOrigin::name())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MusicExternalId::ExternalId)
+                            .string()
+                            .not_null()
+                            .comment("Track id of the music on the platform"),
+                    )
+                    .col(
+                        ColumnDef::new(MusicExternalId::UpdatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .name("idx_music_external_id_unique")
+                            .col(MusicExternalId::MusicTitle)
+                            .col(MusicExternalId::MusicArtist)
+                            .col(MusicExternalId::MusicAlbum)
+                            .col(MusicExternalId::Origin),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MusicExternalId::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MusicExternalId {
+    Table,
+    Id,
+    MusicTitle,
+    MusicArtist,
+    MusicAlbum,
+    Origin,
+    ExternalId,
+    UpdatedOn,
+}
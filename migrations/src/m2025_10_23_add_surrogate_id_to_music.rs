@@ -0,0 +1,142 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tables that pointed to a music through its (title, artist, album) strings
+const MUSIC_REFERENCES: [&str; 3] = [
+    "music_playlist",
+    "music_external_id",
+    "track_match_override",
+];
+
+async fn execute(manager: &SchemaManager<'_>, sql: &str) -> Result<(), DbErr> {
+    manager
+        .get_connection()
+        .execute_unprepared(sql)
+        .await
+        .map(|_| ())
+        .map_err(|e| DbErr::Custom(format!("Failed to execute `{}`: {}", sql, e)))
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SERIAL numbers the existing rows while adding the column
+        execute(
+            manager,
+            "ALTER TABLE music ADD COLUMN id SERIAL PRIMARY KEY",
+        )
+        .await?;
+        execute(
+            manager,
+            "CREATE INDEX IF NOT EXISTS idx_music_title_artist_album ON music (title, artist, album)",
+        )
+        .await?;
+
+        for table in MUSIC_REFERENCES {
+            execute(
+                manager,
+                &format!("ALTER TABLE {} ADD COLUMN music_id INTEGER", table),
+            )
+            .await?;
+            execute(
+                manager,
+                &format!(
+                    "UPDATE {table} SET music_id = music.id FROM music \
+                     WHERE music.title = {table}.music_title \
+                     AND music.artist = {table}.music_artist \
+                     AND music.album IS NOT DISTINCT FROM {table}.music_album"
+                ),
+            )
+            .await?;
+            // Rows pointing to a music that no longer exists can't be kept
+            execute(
+                manager,
+                &format!("DELETE FROM {} WHERE music_id IS NULL", table),
+            )
+            .await?;
+            execute(
+                manager,
+                &format!(
+                    "ALTER TABLE {table} \
+                     ALTER COLUMN music_id SET NOT NULL, \
+                     ADD CONSTRAINT fk_{table}_music FOREIGN KEY (music_id) \
+                     REFERENCES music (id) ON DELETE CASCADE, \
+                     DROP COLUMN music_title, \
+                     DROP COLUMN music_artist, \
+                     DROP COLUMN music_album"
+                ),
+            )
+            .await?;
+        }
+
+        // The unique indexes were dropped along with the string columns
+        execute(
+            manager,
+            "CREATE UNIQUE INDEX idx_music_external_id_unique ON music_external_id (music_id, origin)",
+        )
+        .await?;
+        execute(
+            manager,
+            "CREATE UNIQUE INDEX idx_track_match_override_unique \
+             ON track_match_override (user_id, music_id, origin)",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in MUSIC_REFERENCES {
+            execute(
+                manager,
+                &format!(
+                    "ALTER TABLE {table} \
+                     ADD COLUMN music_title VARCHAR, \
+                     ADD COLUMN music_artist VARCHAR, \
+                     ADD COLUMN music_album VARCHAR"
+                ),
+            )
+            .await?;
+            execute(
+                manager,
+                &format!(
+                    "UPDATE {table} SET music_title = music.title, \
+                     music_artist = music.artist, music_album = music.album \
+                     FROM music WHERE music.id = {table}.music_id"
+                ),
+            )
+            .await?;
+            execute(
+                manager,
+                &format!(
+                    "ALTER TABLE {table} \
+                     ALTER COLUMN music_title SET NOT NULL, \
+                     ALTER COLUMN music_artist SET NOT NULL, \
+                     ALTER COLUMN music_album SET NOT NULL, \
+                     DROP COLUMN music_id"
+                ),
+            )
+            .await?;
+        }
+
+        execute(
+            manager,
+            "CREATE UNIQUE INDEX idx_music_external_id_unique \
+             ON music_external_id (music_title, music_artist, music_album, origin)",
+        )
+        .await?;
+        execute(
+            manager,
+            "CREATE UNIQUE INDEX idx_track_match_override_unique \
+             ON track_match_override (user_id, music_title, music_artist, music_album, origin)",
+        )
+        .await?;
+
+        execute(manager, "DROP INDEX IF EXISTS idx_music_title_artist_album").await?;
+        execute(manager, "ALTER TABLE music DROP COLUMN id").await?;
+
+        Ok(())
+    }
+}
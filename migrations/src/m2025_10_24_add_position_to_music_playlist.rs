@@ -0,0 +1,67 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MusicPlaylist::Table)
+                    .add_column(integer(MusicPlaylist::Position).default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Existing entries keep the order in which they were added
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE music_playlist SET position = ordered.position FROM ( \
+                 SELECT id, ROW_NUMBER() OVER (PARTITION BY playlist_id ORDER BY id) - 1 AS position \
+                 FROM music_playlist) AS ordered \
+                 WHERE music_playlist.id = ordered.id",
+            )
+            .await
+            .map_err(|e| DbErr::Custom(format!("Failed to backfill playlist positions: {}", e)))?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_music_playlist_position")
+                    .table(MusicPlaylist::Table)
+                    .col(MusicPlaylist::PlaylistId)
+                    .col(MusicPlaylist::Position)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_music_playlist_position")
+                    .table(MusicPlaylist::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MusicPlaylist::Table)
+                    .drop_column(MusicPlaylist::Position)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MusicPlaylist {
+    Table,
+    PlaylistId,
+    Position,
+}
@@ -0,0 +1,50 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Music::Table)
+                    .add_column(integer_null(Music::DurationMs))
+                    .add_column(boolean_null(Music::Explicit))
+                    .add_column(string_null(Music::ArtworkUrl))
+                    .add_column(integer_null(Music::DiscNumber))
+                    .add_column(integer_null(Music::TrackNumber))
+                    .add_column(string_null(Music::PreviewUrl))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Music::Table)
+                    .drop_column(Music::DurationMs)
+                    .drop_column(Music::Explicit)
+                    .drop_column(Music::ArtworkUrl)
+                    .drop_column(Music::DiscNumber)
+                    .drop_column(Music::TrackNumber)
+                    .drop_column(Music::PreviewUrl)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Music {
+    Table,
+    DurationMs,
+    Explicit,
+    ArtworkUrl,
+    DiscNumber,
+    TrackNumber,
+    PreviewUrl,
+}
@@ -0,0 +1,189 @@
+use sea_orm::{sea_query::extension::postgres::Type, ActiveEnum, DbBackend, Schema};
+use sea_orm_migration::{prelude::*, schema::*};
+use swaptun_models::music_artist::ArtistRole;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Every existing music gets its artist string as primary artist, and an album by that artist
+const BACKFILL: [&str; 4] = [
+    "INSERT INTO artist (name) SELECT DISTINCT artist FROM music WHERE artist <> '' \
+     ON CONFLICT (name) DO NOTHING",
+    "INSERT INTO music_artist (music_id, artist_id, role, position) \
+     SELECT music.id, artist.id, 'Primary'::artist_role, 0 \
+     FROM music JOIN artist ON artist.name = music.artist",
+    "INSERT INTO album (title, artist_id) SELECT DISTINCT music.album, artist.id \
+     FROM music JOIN artist ON artist.name = music.artist WHERE music.album <> ''",
+    "UPDATE music SET album_id = album.id FROM album JOIN artist ON artist.id = album.artist_id \
+     WHERE album.title = music.album AND artist.name = music.artist",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(DbBackend::Postgres);
+        manager
+            .create_type(schema.create_enum_from_active_enum::<ArtistRole>())
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Artist::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Artist::Id))
+                    .col(string_uniq(Artist::Name))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Album::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Album::Id))
+                    .col(string(Album::Title))
+                    .col(integer_null(Album::ArtistId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_album_artist")
+                            .from(Album::Table, Album::ArtistId)
+                            .to(Artist::Table, Artist::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .name("idx_album_title_artist")
+                            .col(Album::Title)
+                            .col(Album::ArtistId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Music::Table)
+                    .add_column(integer_null(Music::AlbumId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_music_album")
+                            .from_tbl(Music::Table)
+                            .from_col(Music::AlbumId)
+                            .to_tbl(Album::Table)
+                            .to_col(Album::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(MusicArtist::Table)
+                    .if_not_exists()
+                    .col(pk_auto(MusicArtist::Id))
+                    .col(integer(MusicArtist::MusicId))
+                    .col(integer(MusicArtist::ArtistId))
+                    .col(
+                        ColumnDef::new(MusicArtist::Role)
+                            .custom(ArtistRole::name())
+                            .not_null(),
+                    )
+                    .col(integer(MusicArtist::Position).default(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_music_artist_music")
+                            .from(MusicArtist::Table, MusicArtist::MusicId)
+                            .to(Music::Table, Music::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_music_artist_artist")
+                            .from(MusicArtist::Table, MusicArtist::ArtistId)
+                            .to(Artist::Table, Artist::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .name("idx_music_artist_unique")
+                            .col(MusicArtist::MusicId)
+                            .col(MusicArtist::ArtistId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        for sql in BACKFILL {
+            manager
+                .get_connection()
+                .execute_unprepared(sql)
+                .await
+                .map_err(|e| DbErr::Custom(format!("Failed to backfill artists: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MusicArtist::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Music::Table)
+                    .drop_foreign_key(Alias::new("fk_music_album"))
+                    .drop_column(Music::AlbumId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Album::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Artist::Table).to_owned())
+            .await?;
+        manager
+            .drop_type(Type::drop().name(ArtistRole::name()).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Artist {
+    Table,
+    Id,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum Album {
+    Table,
+    Id,
+    Title,
+    ArtistId,
+}
+
+#[derive(DeriveIden)]
+enum Music {
+    Table,
+    Id,
+    AlbumId,
+}
+
+#[derive(DeriveIden)]
+enum MusicArtist {
+    Table,
+    Id,
+    MusicId,
+    ArtistId,
+    Role,
+    Position,
+}
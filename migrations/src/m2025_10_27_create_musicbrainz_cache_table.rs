@@ -0,0 +1,56 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MusicbrainzCache::Table)
+                    .if_not_exists()
+                    .col(pk_auto(MusicbrainzCache::Id))
+                    .col(string(MusicbrainzCache::Title))
+                    .col(string(MusicbrainzCache::Artist))
+                    .col(
+                        ColumnDef::new(MusicbrainzCache::Candidates)
+                            .json_binary()
+                            .not_null()
+                            .comment("Ranked recordings returned by MusicBrainz"),
+                    )
+                    .col(
+                        ColumnDef::new(MusicbrainzCache::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .name("idx_musicbrainz_cache_unique")
+                            .col(MusicbrainzCache::Title)
+                            .col(MusicbrainzCache::Artist),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MusicbrainzCache::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MusicbrainzCache {
+    Table,
+    Id,
+    Title,
+    Artist,
+    Candidates,
+    CreatedOn,
+}
@@ -0,0 +1,110 @@
+use sea_orm::{sea_query::extension::postgres::Type, ActiveEnum, DbBackend, Schema};
+use sea_orm_migration::{prelude::*, schema::*};
+use swaptun_models::job::{JobKind, JobStatus};
+
+use crate::m2025_03_19_create_tbl_users::TblUsers;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(DbBackend::Postgres);
+        manager
+            .create_type(schema.create_enum_from_active_enum::<JobKind>())
+            .await?;
+        manager
+            .create_type(schema.create_enum_from_active_enum::<JobStatus>())
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Job::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Job::Id))
+                    .col(integer(Job::UserId))
+                    .col(ColumnDef::new(Job::Kind).custom(JobKind::name()).not_null())
+                    .col(
+                        ColumnDef::new(Job::Status)
+                            .custom(JobStatus::name())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Job::Payload)
+                            .json_binary()
+                            .not_null()
+                            .comment("Parameters of the job, depends on its kind"),
+                    )
+                    .col(ColumnDef::new(Job::Result).json_binary().null())
+                    .col(text_null(Job::Error))
+                    .col(integer(Job::Attempts).default(0))
+                    .col(integer(Job::MaxAttempts))
+                    .col(
+                        ColumnDef::new(Job::RunAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(timestamp_with_time_zone_null(Job::StartedOn))
+                    .col(timestamp_with_time_zone_null(Job::FinishedOn))
+                    .col(
+                        ColumnDef::new(Job::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Job::Table, Job::UserId)
+                            .to(TblUsers::Table, TblUsers::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Workers look for the next queued job on every poll
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_job_status_run_at")
+                    .table(Job::Table)
+                    .col(Job::Status)
+                    .col(Job::RunAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Job::Table).to_owned())
+            .await?;
+        manager
+            .drop_type(Type::drop().name(JobStatus::name()).to_owned())
+            .await?;
+        manager
+            .drop_type(Type::drop().name(JobKind::name()).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Job {
+    Table,
+    Id,
+    UserId,
+    Kind,
+    Status,
+    Payload,
+    Result,
+    Error,
+    Attempts,
+    MaxAttempts,
+    RunAt,
+    StartedOn,
+    FinishedOn,
+    CreatedOn,
+}
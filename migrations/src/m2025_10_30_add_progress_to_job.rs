@@ -0,0 +1,50 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Job::Table)
+                    .add_column(integer(Job::TotalTracks).default(0))
+                    .add_column(integer(Job::ProcessedTracks).default(0))
+                    .add_column(string_null(Job::CurrentPlaylist))
+                    .add_column(
+                        ColumnDef::new(Job::PlaylistErrors)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb"))
+                            .comment("Playlists that could not be transferred and why"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Job::Table)
+                    .drop_column(Job::TotalTracks)
+                    .drop_column(Job::ProcessedTracks)
+                    .drop_column(Job::CurrentPlaylist)
+                    .drop_column(Job::PlaylistErrors)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Job {
+    Table,
+    TotalTracks,
+    ProcessedTracks,
+    CurrentPlaylist,
+    PlaylistErrors,
+}
@@ -0,0 +1,96 @@
+use sea_orm::ActiveEnum;
+use sea_orm_migration::prelude::*;
+use swaptun_models::playlist::PlaylistOrigin;
+
+use crate::{m2025_03_19_create_tbl_users::TblUsers, m2025_04_29_create_playlist_table::Playlist};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PlaylistExport::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PlaylistExport::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PlaylistExport::PlaylistId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PlaylistExport::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(PlaylistExport::Destination)
+                            .custom(PlaylistOrigin::name())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PlaylistExport::PlatformPlaylistId)
+                            .string()
+                            .not_null()
+                            .comment("Id of the playlist created on the destination platform"),
+                    )
+                    .col(
+                        ColumnDef::new(PlaylistExport::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(PlaylistExport::UpdatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PlaylistExport::Table, PlaylistExport::PlaylistId)
+                            .to(Playlist::Table, Playlist::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PlaylistExport::Table, PlaylistExport::UserId)
+                            .to(TblUsers::Table, TblUsers::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .name("idx_playlist_export_unique")
+                            .col(PlaylistExport::PlaylistId)
+                            .col(PlaylistExport::UserId)
+                            .col(PlaylistExport::Destination),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PlaylistExport::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PlaylistExport {
+    Table,
+    Id,
+    PlaylistId,
+    UserId,
+    Destination,
+    PlatformPlaylistId,
+    CreatedOn,
+    UpdatedOn,
+}
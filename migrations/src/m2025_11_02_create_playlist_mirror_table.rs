@@ -0,0 +1,82 @@
+use sea_orm::ActiveEnum;
+use sea_orm_migration::prelude::*;
+use swaptun_models::playlist::PlaylistOrigin;
+
+use crate::{m2025_03_19_create_tbl_users::TblUsers, m2025_04_29_create_playlist_table::Playlist};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PlaylistMirror::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PlaylistMirror::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PlaylistMirror::PlaylistId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PlaylistMirror::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(PlaylistMirror::Destination)
+                            .custom(PlaylistOrigin::name())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PlaylistMirror::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PlaylistMirror::Table, PlaylistMirror::PlaylistId)
+                            .to(Playlist::Table, Playlist::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PlaylistMirror::Table, PlaylistMirror::UserId)
+                            .to(TblUsers::Table, TblUsers::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .name("idx_playlist_mirror_unique")
+                            .col(PlaylistMirror::PlaylistId)
+                            .col(PlaylistMirror::UserId)
+                            .col(PlaylistMirror::Destination),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PlaylistMirror::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PlaylistMirror {
+    Table,
+    Id,
+    PlaylistId,
+    UserId,
+    Destination,
+    CreatedOn,
+}
@@ -0,0 +1,103 @@
+use sea_orm::{sea_query::extension::postgres::Type, ActiveEnum, DbBackend, Schema};
+use sea_orm_migration::{prelude::*, schema::*};
+use swaptun_models::playlist_snapshot::SnapshotReason;
+
+use crate::m2025_04_29_create_playlist_table::Playlist;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(DbBackend::Postgres);
+        manager
+            .create_type(schema.create_enum_from_active_enum::<SnapshotReason>())
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PlaylistSnapshot::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PlaylistSnapshot::Id))
+                    .col(integer(PlaylistSnapshot::PlaylistId))
+                    .col(
+                        ColumnDef::new(PlaylistSnapshot::Reason)
+                            .custom(SnapshotReason::name())
+                            .not_null(),
+                    )
+                    .col(string(PlaylistSnapshot::Name))
+                    .col(text_null(PlaylistSnapshot::Description))
+                    .col(string_null(PlaylistSnapshot::ImageUrl))
+                    .col(
+                        ColumnDef::new(PlaylistSnapshot::MusicIds)
+                            .json_binary()
+                            .not_null()
+                            .comment("Ids of the musics of the playlist, in order"),
+                    )
+                    .col(
+                        ColumnDef::new(PlaylistSnapshot::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PlaylistSnapshot::Table, PlaylistSnapshot::PlaylistId)
+                            .to(Playlist::Table, Playlist::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_playlist_snapshot_playlist")
+                    .table(PlaylistSnapshot::Table)
+                    .col(PlaylistSnapshot::PlaylistId)
+                    .col(PlaylistSnapshot::CreatedOn)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Existing playlists start their history with their current contents
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO playlist_snapshot (playlist_id, reason, name, description, image_url, music_ids) \
+                 SELECT playlist.id, 'Initial', playlist.name, playlist.description, playlist.image_url, \
+                 COALESCE((SELECT jsonb_agg(music_playlist.music_id ORDER BY music_playlist.position, music_playlist.id) \
+                 FROM music_playlist WHERE music_playlist.playlist_id = playlist.id), '[]'::jsonb) \
+                 FROM playlist",
+            )
+            .await
+            .map_err(|e| DbErr::Custom(format!("Failed to backfill playlist snapshots: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PlaylistSnapshot::Table).to_owned())
+            .await?;
+        manager
+            .drop_type(Type::drop().name(SnapshotReason::name()).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PlaylistSnapshot {
+    Table,
+    Id,
+    PlaylistId,
+    Reason,
+    Name,
+    Description,
+    ImageUrl,
+    MusicIds,
+    CreatedOn,
+}
@@ -0,0 +1,75 @@
+use sea_orm::ActiveEnum;
+use sea_orm_migration::prelude::*;
+use swaptun_models::playlist::PlaylistOrigin;
+
+use crate::m2025_03_19_create_tbl_users::TblUsers;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImportSelection::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ImportSelection::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ImportSelection::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(ImportSelection::Origin)
+                            .custom(PlaylistOrigin::name())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportSelection::PlaylistIds)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportSelection::UpdatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ImportSelection::Table, ImportSelection::UserId)
+                            .to(TblUsers::Table, TblUsers::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .name("idx_import_selection_unique")
+                            .col(ImportSelection::UserId)
+                            .col(ImportSelection::Origin),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ImportSelection::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ImportSelection {
+    Table,
+    Id,
+    UserId,
+    Origin,
+    PlaylistIds,
+    UpdatedOn,
+}
@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "artist")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub name: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl Related<super::music::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::music_artist::Relation::Music.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::music_artist::Relation::Artist.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
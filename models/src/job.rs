@@ -0,0 +1,72 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(EnumIter, DeriveActiveEnum, Debug, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "job_kind")]
+pub enum JobKind {
+    #[sea_orm(string_value = "ImportPlaylists")]
+    ImportPlaylists,
+    #[sea_orm(string_value = "ExportPlaylist")]
+    ExportPlaylist,
+}
+
+#[derive(EnumIter, DeriveActiveEnum, Debug, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "job_status")]
+pub enum JobStatus {
+    #[sea_orm(string_value = "Queued")]
+    Queued,
+    #[sea_orm(string_value = "Running")]
+    Running,
+    #[sea_orm(string_value = "Succeeded")]
+    Succeeded,
+    #[sea_orm(string_value = "Failed")]
+    Failed,
+}
+
+/// Import or export run by the background workers
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "job")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    #[sea_orm(comment = "Parameters of the job, depends on its kind")]
+    pub payload: Json,
+    pub result: Option<Json>,
+    /// Error of the last failed attempt
+    pub error: Option<String>,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    /// The job is not picked up before this time, pushed back after each failed attempt
+    pub run_at: DateTimeWithTimeZone,
+    pub started_on: Option<DateTimeWithTimeZone>,
+    pub finished_on: Option<DateTimeWithTimeZone>,
+    pub created_on: DateTimeWithTimeZone,
+    /// Tracks of the playlists reached so far, grows as the playlists are fetched
+    pub total_tracks: i32,
+    pub processed_tracks: i32,
+    pub current_playlist: Option<String>,
+    #[sea_orm(comment = "Playlists that could not be transferred and why")]
+    pub playlist_errors: Json,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
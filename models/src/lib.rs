@@ -1,13 +1,24 @@
+pub mod album;
 pub mod apple_token;
+pub mod artist;
 pub mod deezer_token;
 pub mod fcm_token;
 pub mod friendship;
+pub mod import_selection;
+pub mod job;
 pub mod music;
+pub mod music_artist;
+pub mod music_external_id;
 pub mod music_playlist;
+pub mod musicbrainz_cache;
 pub mod playlist;
+pub mod playlist_export;
+pub mod playlist_mirror;
+pub mod playlist_snapshot;
 pub mod shared_playlist;
 pub mod spotify_code;
 pub mod spotify_token;
+pub mod track_match_override;
 pub mod user;
 pub mod user_info;
 pub mod youtube_token;
@@ -15,18 +26,42 @@ pub use deezer_token::{
     ActiveModel as DeezerTokenActiveModel, Column as DeezerTokenColumn,
     Entity as DeezerTokenEntity, Model as DeezerTokenModel,
 };
+pub use album::{
+    ActiveModel as AlbumActiveModel, Column as AlbumColumn, Entity as AlbumEntity,
+    Model as AlbumModel,
+};
+pub use artist::{
+    ActiveModel as ArtistActiveModel, Column as ArtistColumn, Entity as ArtistEntity,
+    Model as ArtistModel,
+};
 pub use fcm_token::{
     ActiveModel as FcmTokenActiveModel, Column as FcmTokenColumn, Entity as FcmTokenEntity,
     Model as FcmTokenModel,
 };
+pub use job::{
+    ActiveModel as JobActiveModel, Column as JobColumn, Entity as JobEntity, JobKind, JobStatus,
+    Model as JobModel,
+};
 pub use music::{
     ActiveModel as MusicActiveModel, Column as MusicColumn, Entity as MusicEntity,
     Model as MusicModel,
 };
+pub use music_artist::{
+    ActiveModel as MusicArtistActiveModel, ArtistRole, Column as MusicArtistColumn,
+    Entity as MusicArtistEntity, Model as MusicArtistModel,
+};
+pub use music_external_id::{
+    ActiveModel as MusicExternalIdActiveModel, Column as MusicExternalIdColumn,
+    Entity as MusicExternalIdEntity, Model as MusicExternalIdModel,
+};
 pub use music_playlist::{
     ActiveModel as MusicPlaylistActiveModel, Column as MusicPlaylistColumn,
     Entity as MusicPlaylistEntity, Model as MusicPlaylistModel,
 };
+pub use musicbrainz_cache::{
+    ActiveModel as MusicbrainzCacheActiveModel, Column as MusicbrainzCacheColumn,
+    Entity as MusicbrainzCacheEntity, Model as MusicbrainzCacheModel,
+};
 pub use playlist::{
     ActiveModel as PlaylistActiveModel, Column as PlaylistColumn, Entity as PlaylistEntity,
     Model as PlaylistModel, PlaylistOrigin,
@@ -68,3 +103,28 @@ pub use friendship::{
     ActiveModel as FriendshipActiveModel, Column as FriendshipColumn, Entity as FriendshipEntity,
     Model as FriendshipModel,
 };
+
+pub use import_selection::{
+    ActiveModel as ImportSelectionActiveModel, Column as ImportSelectionColumn,
+    Entity as ImportSelectionEntity, Model as ImportSelectionModel,
+};
+
+pub use playlist_export::{
+    ActiveModel as PlaylistExportActiveModel, Column as PlaylistExportColumn,
+    Entity as PlaylistExportEntity, Model as PlaylistExportModel,
+};
+
+pub use playlist_mirror::{
+    ActiveModel as PlaylistMirrorActiveModel, Column as PlaylistMirrorColumn,
+    Entity as PlaylistMirrorEntity, Model as PlaylistMirrorModel,
+};
+
+pub use playlist_snapshot::{
+    ActiveModel as PlaylistSnapshotActiveModel, Column as PlaylistSnapshotColumn,
+    Entity as PlaylistSnapshotEntity, Model as PlaylistSnapshotModel, SnapshotReason,
+};
+
+pub use track_match_override::{
+    ActiveModel as TrackMatchOverrideActiveModel, Column as TrackMatchOverrideColumn,
+    Entity as TrackMatchOverrideEntity, Model as TrackMatchOverrideModel,
+};
@@ -5,17 +5,58 @@ use serde::{Deserialize, Serialize};
 #[sea_orm(table_name = "music")]
 pub struct Model {
     #[sea_orm(primary_key)]
+    pub id: i32,
     pub title: String,
-    #[sea_orm(primary_key)]
     pub artist: String,
-    #[sea_orm(primary_key)]
     pub album: String,
+    pub album_id: Option<i32>,
     pub release_date: Date,
     pub genre: Option<String>,
+    /// International Standard Recording Code, shared by every platform for the same recording
+    pub isrc: Option<String>,
+    pub duration_ms: Option<i32>,
+    pub explicit: Option<bool>,
+    pub artwork_url: Option<String>,
+    pub disc_number: Option<i32>,
+    pub track_number: Option<i32>,
+    /// Short audio extract hosted by the platform the music was imported from
+    pub preview_url: Option<String>,
+    /// Recording id on MusicBrainz, found by the enrichment job
+    pub musicbrainz_id: Option<String>,
+    /// Last time the enrichment job looked the music up, `None` until it has
+    pub enriched_on: Option<DateTimeWithTimeZone>,
+    /// Failed enrichment lookups in a row, the music is given up on after a few
+    pub enrichment_attempts: i32,
+    /// Last failed enrichment lookup, the music waits before being looked up again
+    pub enrichment_failed_on: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::album::Entity",
+        from = "Column::AlbumId",
+        to = "super::album::Column::Id",
+        on_delete = "SetNull"
+    )]
+    Album,
+}
+
+impl Related<super::album::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Album.def()
+    }
+}
+
+impl Related<super::artist::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::music_artist::Relation::Artist.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::music_artist::Relation::Music.def().rev())
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
 // Implémentation correcte de Related pour la relation many-to-many avec Playlist
@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(EnumIter, DeriveActiveEnum, Debug, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "artist_role")]
+pub enum ArtistRole {
+    #[sea_orm(string_value = "Primary")]
+    Primary,
+    #[sea_orm(string_value = "Featured")]
+    Featured,
+}
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "music_artist")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub music_id: i32,
+    pub artist_id: i32,
+    pub role: ArtistRole,
+    /// Zero-based rank of the artist in the credits
+    pub position: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::music::Entity",
+        from = "Column::MusicId",
+        to = "super::music::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Music,
+    #[sea_orm(
+        belongs_to = "super::artist::Entity",
+        from = "Column::ArtistId",
+        to = "super::artist::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Artist,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::playlist::PlaylistOrigin;
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "music_external_id")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub music_id: i32,
+    pub origin: PlaylistOrigin,
+    #[sea_orm(comment = "Track id of the music on the platform")]
+    pub external_id: String,
+    pub updated_on: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::music::Entity",
+        from = "Column::MusicId",
+        to = "super::music::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Music,
+}
+
+impl Related<super::music::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Music.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -7,9 +7,9 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub playlist_id: i32,
-    pub music_title: String,
-    pub music_artist: String,
-    pub music_album: String,
+    pub music_id: i32,
+    /// Zero-based rank of the music in the playlist
+    pub position: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -22,8 +22,9 @@ pub enum Relation {
     Playlist,
     #[sea_orm(
         belongs_to = "super::music::Entity",
-        from = "(Column::MusicTitle, Column::MusicArtist, Column::MusicAlbum)",
-        to = "(super::music::Column::Title, super::music::Column::Artist, super::music::Column::Album)"
+        from = "Column::MusicId",
+        to = "super::music::Column::Id",
+        on_delete = "Cascade"
     )]
     Music,
 }
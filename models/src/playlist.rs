@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use sea_orm::entity::prelude::*;
 
-#[derive(EnumIter, DeriveActiveEnum, Debug, Clone, PartialEq, Serialize, Deserialize, Eq)]
+#[derive(EnumIter, DeriveActiveEnum, Debug, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "playlist_origin")]
 pub enum PlaylistOrigin {
     #[sea_orm(string_value = "Spotify")]
@@ -27,6 +27,8 @@ pub struct Model {
     pub updated_on: DateTimeWithTimeZone,
     pub origin_id: String,
     pub image_url: Option<String>,
+    /// Last time the tracks were re-imported from the origin platform
+    pub last_synced_on: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,51 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(EnumIter, DeriveActiveEnum, Debug, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "snapshot_reason")]
+pub enum SnapshotReason {
+    /// Contents the playlist had when snapshots were introduced
+    #[sea_orm(string_value = "Initial")]
+    Initial,
+    #[sea_orm(string_value = "Import")]
+    Import,
+    #[sea_orm(string_value = "Edit")]
+    Edit,
+    #[sea_orm(string_value = "Restore")]
+    Restore,
+}
+
+/// Contents of a playlist right after an import or an edit changed it
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "playlist_snapshot")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub playlist_id: i32,
+    pub reason: SnapshotReason,
+    pub name: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    /// Ids of the musics of the playlist, in order
+    pub music_ids: Json,
+    pub created_on: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::playlist::Entity",
+        from = "Column::PlaylistId",
+        to = "super::playlist::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Playlist,
+}
+
+impl Related<super::playlist::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Playlist.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::playlist::PlaylistOrigin;
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "track_match_override")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    pub music_id: i32,
+    pub origin: PlaylistOrigin,
+    #[sea_orm(comment = "Track id chosen by the user on the platform")]
+    pub platform_track_id: String,
+    pub created_on: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::music::Entity",
+        from = "Column::MusicId",
+        to = "super::music::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Music,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::music::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Music.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,61 @@
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use std::sync::Arc;
+use swaptun_models::{AlbumActiveModel, AlbumColumn, AlbumEntity, AlbumModel};
+
+#[derive(Clone)]
+pub struct AlbumRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl AlbumRepository {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, album: AlbumActiveModel) -> Result<AlbumModel, DbErr> {
+        album.insert(self.db.as_ref()).await
+    }
+
+    /// Inserts the album unless the artist already has one with the same title, another
+    /// import may be creating it at the same time
+    pub async fn create_if_missing(&self, album: AlbumActiveModel) -> Result<(), DbErr> {
+        AlbumEntity::insert(album)
+            .on_conflict(
+                OnConflict::columns([AlbumColumn::Title, AlbumColumn::ArtistId])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> Result<Option<AlbumModel>, DbErr> {
+        AlbumEntity::find_by_id(id).one(self.db.as_ref()).await
+    }
+
+    pub async fn find_by_title_and_artist(
+        &self,
+        title: &str,
+        artist_id: Option<i32>,
+    ) -> Result<Option<AlbumModel>, DbErr> {
+        let artist_filter = match artist_id {
+            Some(artist_id) => AlbumColumn::ArtistId.eq(artist_id),
+            None => AlbumColumn::ArtistId.is_null(),
+        };
+        AlbumEntity::find()
+            .filter(AlbumColumn::Title.eq(title))
+            .filter(artist_filter)
+            .one(self.db.as_ref())
+            .await
+    }
+
+    pub async fn find_by_artist(&self, artist_id: i32) -> Result<Vec<AlbumModel>, DbErr> {
+        AlbumEntity::find()
+            .filter(AlbumColumn::ArtistId.eq(artist_id))
+            .all(self.db.as_ref())
+            .await
+    }
+}
@@ -0,0 +1,64 @@
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, ModelTrait, QueryFilter,
+    QueryOrder,
+};
+use std::sync::Arc;
+use swaptun_models::{
+    ArtistActiveModel, ArtistColumn, ArtistEntity, ArtistModel, MusicArtistColumn, MusicEntity,
+    MusicModel,
+};
+
+#[derive(Clone)]
+pub struct ArtistRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl ArtistRepository {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, artist: ArtistActiveModel) -> Result<ArtistModel, DbErr> {
+        artist.insert(self.db.as_ref()).await
+    }
+
+    /// Inserts the artist unless one with the same name exists, another import may be
+    /// creating it at the same time
+    pub async fn create_if_missing(&self, artist: ArtistActiveModel) -> Result<(), DbErr> {
+        ArtistEntity::insert(artist)
+            .on_conflict(
+                OnConflict::column(ArtistColumn::Name)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> Result<Option<ArtistModel>, DbErr> {
+        ArtistEntity::find_by_id(id).one(self.db.as_ref()).await
+    }
+
+    pub async fn find_by_name(&self, name: &str) -> Result<Option<ArtistModel>, DbErr> {
+        ArtistEntity::find()
+            .filter(ArtistColumn::Name.eq(name))
+            .one(self.db.as_ref())
+            .await
+    }
+
+    /// Artists credited on the music, in credit order
+    pub async fn find_by_music(&self, music: &MusicModel) -> Result<Vec<ArtistModel>, DbErr> {
+        music
+            .find_related(ArtistEntity)
+            .order_by_asc(MusicArtistColumn::Position)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    pub async fn find_musics(&self, artist: &ArtistModel) -> Result<Vec<MusicModel>, DbErr> {
+        artist.find_related(MusicEntity).all(self.db.as_ref()).await
+    }
+}
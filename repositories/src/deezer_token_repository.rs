@@ -6,6 +6,7 @@ use swaptun_models::{
     DeezerTokenActiveModel, DeezerTokenColumn, DeezerTokenEntity, DeezerTokenModel, UserModel,
 };
 
+#[derive(Clone)]
 pub struct DeezerTokenRepository {
     db: Arc<DatabaseConnection>,
 }
@@ -14,6 +15,11 @@ impl DeezerTokenRepository {
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
         Self { db }
     }
+
+    pub async fn find_all(&self) -> Result<Vec<DeezerTokenModel>, DbErr> {
+        DeezerTokenEntity::find().all(self.db.as_ref()).await
+    }
+
     pub async fn save(
         &self,
         token: DeezerTokenActiveModel,
@@ -0,0 +1,58 @@
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait,
+    QueryFilter,
+};
+use std::sync::Arc;
+use swaptun_models::{
+    ImportSelectionActiveModel, ImportSelectionColumn, ImportSelectionEntity, ImportSelectionModel,
+    PlaylistOrigin,
+};
+
+#[derive(Clone)]
+pub struct ImportSelectionRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl ImportSelectionRepository {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    pub async fn find(
+        &self,
+        user_id: i32,
+        origin: PlaylistOrigin,
+    ) -> Result<Option<ImportSelectionModel>, DbErr> {
+        ImportSelectionEntity::find()
+            .filter(ImportSelectionColumn::UserId.eq(user_id))
+            .filter(ImportSelectionColumn::Origin.eq(origin))
+            .one(self.db.as_ref())
+            .await
+    }
+
+    pub async fn create(
+        &self,
+        model: ImportSelectionActiveModel,
+    ) -> Result<ImportSelectionModel, DbErr> {
+        model.insert(self.db.as_ref()).await
+    }
+
+    pub async fn update(
+        &self,
+        model: ImportSelectionActiveModel,
+    ) -> Result<ImportSelectionModel, DbErr> {
+        model.update(self.db.as_ref()).await
+    }
+
+    pub async fn delete(
+        &self,
+        user_id: i32,
+        origin: PlaylistOrigin,
+    ) -> Result<DeleteResult, DbErr> {
+        ImportSelectionEntity::delete_many()
+            .filter(ImportSelectionColumn::UserId.eq(user_id))
+            .filter(ImportSelectionColumn::Origin.eq(origin))
+            .exec(self.db.as_ref())
+            .await
+    }
+}
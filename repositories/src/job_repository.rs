@@ -0,0 +1,156 @@
+use chrono::{DateTime, FixedOffset};
+use sea_orm::sea_query::{Expr, LockBehavior, LockType};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, JsonValue, QueryFilter, QueryOrder, QuerySelect,
+    TransactionTrait,
+};
+use std::sync::Arc;
+use swaptun_models::{JobActiveModel, JobColumn, JobEntity, JobKind, JobModel, JobStatus};
+
+#[derive(Clone)]
+pub struct JobRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl JobRepository {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, job: JobActiveModel) -> Result<JobModel, DbErr> {
+        job.insert(self.db.as_ref()).await
+    }
+
+    pub async fn update(&self, job: JobActiveModel) -> Result<JobModel, DbErr> {
+        job.update(self.db.as_ref()).await
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> Result<Option<JobModel>, DbErr> {
+        JobEntity::find_by_id(id).one(self.db.as_ref()).await
+    }
+
+    /// Most recent jobs first
+    pub async fn find_by_user(
+        &self,
+        user_id: i32,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<JobModel>, DbErr> {
+        JobEntity::find()
+            .filter(JobColumn::UserId.eq(user_id))
+            .order_by_desc(JobColumn::CreatedOn)
+            .order_by_desc(JobColumn::Id)
+            .limit(limit)
+            .offset(offset)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    /// Jobs of the given kind still waiting for a worker or running
+    pub async fn find_pending(&self, kind: JobKind) -> Result<Vec<JobModel>, DbErr> {
+        JobEntity::find()
+            .filter(JobColumn::Kind.eq(kind))
+            .filter(JobColumn::Status.is_in([JobStatus::Queued, JobStatus::Running]))
+            .all(self.db.as_ref())
+            .await
+    }
+
+    /// Only touches the progress columns, the worker owns the status of the job
+    pub async fn update_progress(
+        &self,
+        id: i32,
+        total_tracks: i32,
+        processed_tracks: i32,
+        current_playlist: Option<String>,
+        playlist_errors: JsonValue,
+    ) -> Result<(), DbErr> {
+        JobEntity::update_many()
+            .col_expr(JobColumn::TotalTracks, Expr::value(total_tracks))
+            .col_expr(JobColumn::ProcessedTracks, Expr::value(processed_tracks))
+            .col_expr(JobColumn::CurrentPlaylist, Expr::value(current_playlist))
+            .col_expr(JobColumn::PlaylistErrors, Expr::value(playlist_errors))
+            .filter(JobColumn::Id.eq(id))
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Marks as failed the jobs still running since before `stale_before` that have no attempt
+    /// left, their worker died and running them again is not allowed
+    pub async fn fail_stale(
+        &self,
+        now: DateTime<FixedOffset>,
+        stale_before: DateTime<FixedOffset>,
+        error: &str,
+    ) -> Result<(), DbErr> {
+        let jobs = JobEntity::find()
+            .filter(JobColumn::Status.eq(JobStatus::Running))
+            .filter(JobColumn::StartedOn.lt(stale_before))
+            .filter(Expr::col(JobColumn::Attempts).gte(Expr::col(JobColumn::MaxAttempts)))
+            .all(self.db.as_ref())
+            .await?;
+        for job in jobs {
+            let mut active_model = job.into_active_model();
+            active_model.status = Set(JobStatus::Failed);
+            active_model.error = Set(Some(error.to_string()));
+            active_model.finished_on = Set(Some(now));
+            active_model.update(self.db.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    /// Marks the next job due at `now` as running and returns it. Jobs still running since
+    /// before `stale_before` belong to a worker that died and are picked up again while they
+    /// have attempts left.
+    /// Rows locked by another worker are skipped so each job is claimed only once.
+    pub async fn claim_next(
+        &self,
+        now: DateTime<FixedOffset>,
+        stale_before: DateTime<FixedOffset>,
+    ) -> Result<Option<JobModel>, DbErr> {
+        let txn = self.db.begin().await?;
+        let job = JobEntity::find()
+            .filter(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(JobColumn::Status.eq(JobStatus::Queued))
+                            .add(JobColumn::RunAt.lte(now)),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(JobColumn::Status.eq(JobStatus::Running))
+                            .add(JobColumn::StartedOn.lt(stale_before))
+                            .add(
+                                Expr::col(JobColumn::Attempts)
+                                    .lt(Expr::col(JobColumn::MaxAttempts)),
+                            ),
+                    ),
+            )
+            .order_by_asc(JobColumn::RunAt)
+            .order_by_asc(JobColumn::Id)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .one(&txn)
+            .await?;
+
+        let job = match job {
+            Some(job) => {
+                let attempts = job.attempts + 1;
+                let mut active_model = job.into_active_model();
+                active_model.status = Set(JobStatus::Running);
+                active_model.attempts = Set(attempts);
+                active_model.started_on = Set(Some(now));
+                // A new attempt starts over
+                active_model.total_tracks = Set(0);
+                active_model.processed_tracks = Set(0);
+                active_model.current_playlist = Set(None);
+                active_model.playlist_errors = Set(JsonValue::Array(Vec::new()));
+                Some(active_model.update(&txn).await?)
+            }
+            None => None,
+        };
+        txn.commit().await?;
+        Ok(job)
+    }
+}
@@ -1,26 +1,48 @@
+pub mod album_repository;
 pub mod apple_token_repository;
+pub mod artist_repository;
 pub mod deezer_token_repository;
 pub mod fcm_token_repository;
 pub mod friendship_repository;
+pub mod import_selection_repository;
+pub mod job_repository;
+pub mod music_artist_repository;
+pub mod music_external_id_repository;
 pub mod music_playlist_repository;
 pub mod music_repository;
+pub mod musicbrainz_cache_repository;
+pub mod playlist_export_repository;
+pub mod playlist_mirror_repository;
+pub mod playlist_snapshot_repository;
 pub mod playlist_repository;
 pub mod spotify_code_repository;
 pub mod spotify_token_repository;
+pub mod track_match_override_repository;
 pub mod user_info_repository;
 pub mod user_repository;
 
 pub mod youtube_token_repository;
 
+pub use album_repository::*;
 pub use apple_token_repository::*;
+pub use artist_repository::*;
 pub use deezer_token_repository::*;
 pub use fcm_token_repository::*;
 pub use friendship_repository::*;
+pub use import_selection_repository::*;
+pub use job_repository::*;
+pub use music_artist_repository::*;
+pub use music_external_id_repository::*;
 pub use music_playlist_repository::*;
 pub use music_repository::*;
+pub use musicbrainz_cache_repository::*;
+pub use playlist_export_repository::*;
+pub use playlist_mirror_repository::*;
+pub use playlist_snapshot_repository::*;
 pub use playlist_repository::*;
 pub use spotify_code_repository::*;
 pub use spotify_token_repository::*;
+pub use track_match_override_repository::*;
 pub use user_info_repository::*;
 pub use user_repository::*;
 pub use youtube_token_repository::*;
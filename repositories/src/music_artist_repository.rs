@@ -0,0 +1,42 @@
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait,
+    QueryFilter, QueryOrder,
+};
+use std::sync::Arc;
+use swaptun_models::{
+    MusicArtistActiveModel, MusicArtistColumn, MusicArtistEntity, MusicArtistModel,
+};
+
+#[derive(Clone)]
+pub struct MusicArtistRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl MusicArtistRepository {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        music_artist: MusicArtistActiveModel,
+    ) -> Result<MusicArtistModel, DbErr> {
+        music_artist.insert(self.db.as_ref()).await
+    }
+
+    /// Credits of the music, in credit order
+    pub async fn find_by_music_id(&self, music_id: i32) -> Result<Vec<MusicArtistModel>, DbErr> {
+        MusicArtistEntity::find()
+            .filter(MusicArtistColumn::MusicId.eq(music_id))
+            .order_by_asc(MusicArtistColumn::Position)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    pub async fn delete_by_music_id(&self, music_id: i32) -> Result<DeleteResult, DbErr> {
+        MusicArtistEntity::delete_many()
+            .filter(MusicArtistColumn::MusicId.eq(music_id))
+            .exec(self.db.as_ref())
+            .await
+    }
+}
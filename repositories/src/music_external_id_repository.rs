@@ -0,0 +1,36 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use std::sync::Arc;
+use swaptun_models::{
+    MusicExternalIdActiveModel, MusicExternalIdColumn, MusicExternalIdEntity, MusicExternalIdModel,
+    PlaylistOrigin,
+};
+
+#[derive(Clone)]
+pub struct MusicExternalIdRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl MusicExternalIdRepository {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    pub async fn save(
+        &self,
+        model: MusicExternalIdActiveModel,
+    ) -> Result<MusicExternalIdActiveModel, DbErr> {
+        model.save(self.db.as_ref()).await
+    }
+
+    pub async fn find(
+        &self,
+        music_id: i32,
+        origin: PlaylistOrigin,
+    ) -> Result<Option<MusicExternalIdModel>, DbErr> {
+        MusicExternalIdEntity::find()
+            .filter(MusicExternalIdColumn::MusicId.eq(music_id))
+            .filter(MusicExternalIdColumn::Origin.eq(origin))
+            .one(self.db.as_ref())
+            .await
+    }
+}
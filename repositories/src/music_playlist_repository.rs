@@ -1,6 +1,6 @@
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait,
-    ModelTrait, QueryFilter,
+    ModelTrait, QueryFilter, QueryOrder, TransactionTrait,
 };
 use std::sync::Arc;
 use swaptun_models::{
@@ -25,6 +25,49 @@ impl MusicPlaylistRepository {
         music_playlist.insert(self.db.as_ref()).await
     }
 
+    pub async fn update(
+        &self,
+        music_playlist: MusicPlaylistActiveModel,
+    ) -> Result<MusicPlaylistModel, DbErr> {
+        music_playlist.update(self.db.as_ref()).await
+    }
+
+    /// Updates the entries in one transaction so a reorder is never left half applied
+    pub async fn update_all(
+        &self,
+        music_playlists: Vec<MusicPlaylistActiveModel>,
+    ) -> Result<(), DbErr> {
+        let txn = self.db.begin().await?;
+        for music_playlist in music_playlists {
+            music_playlist.update(&txn).await?;
+        }
+        txn.commit().await
+    }
+
+    /// Applies a new track list of a playlist in one transaction so a failure never leaves
+    /// it truncated
+    pub async fn replace(
+        &self,
+        deleted: Vec<i32>,
+        updated: Vec<MusicPlaylistActiveModel>,
+        created: Vec<MusicPlaylistActiveModel>,
+    ) -> Result<(), DbErr> {
+        let txn = self.db.begin().await?;
+        if !deleted.is_empty() {
+            MusicPlaylistEntity::delete_many()
+                .filter(MusicPlaylistColumn::Id.is_in(deleted))
+                .exec(&txn)
+                .await?;
+        }
+        for music_playlist in updated {
+            music_playlist.update(&txn).await?;
+        }
+        for music_playlist in created {
+            music_playlist.insert(&txn).await?;
+        }
+        txn.commit().await
+    }
+
     pub async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr> {
         MusicPlaylistEntity::delete_by_id(id)
             .exec(self.db.as_ref())
@@ -38,13 +81,9 @@ impl MusicPlaylistRepository {
     ) -> Result<DeleteResult, DbErr> {
         MusicPlaylistEntity::delete_many()
             .filter(
-                MusicPlaylistColumn::PlaylistId.eq(playlist.id).and(
-                    MusicPlaylistColumn::MusicAlbum.eq(music.album.clone()).and(
-                        MusicPlaylistColumn::MusicArtist
-                            .eq(music.artist.clone())
-                            .and(MusicPlaylistColumn::MusicTitle.eq(music.title.clone())),
-                    ),
-                ),
+                MusicPlaylistColumn::PlaylistId
+                    .eq(playlist.id)
+                    .and(MusicPlaylistColumn::MusicId.eq(music.id)),
             )
             .exec(self.db.as_ref())
             .await
@@ -56,10 +95,34 @@ impl MusicPlaylistRepository {
     ) -> Result<Vec<MusicModel>, DbErr> {
         playlist
             .find_related(MusicEntity)
+            .order_by_asc(MusicPlaylistColumn::Position)
             .all(self.db.as_ref())
             .await
     }
 
+    /// Entries of the playlist in their display order
+    pub async fn find_by_playlist_id(
+        &self,
+        playlist_id: i32,
+    ) -> Result<Vec<MusicPlaylistModel>, DbErr> {
+        MusicPlaylistEntity::find()
+            .filter(MusicPlaylistColumn::PlaylistId.eq(playlist_id))
+            .order_by_asc(MusicPlaylistColumn::Position)
+            .order_by_asc(MusicPlaylistColumn::Id)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    /// Position right after the last entry of the playlist
+    pub async fn next_position(&self, playlist_id: i32) -> Result<i32, DbErr> {
+        Ok(MusicPlaylistEntity::find()
+            .filter(MusicPlaylistColumn::PlaylistId.eq(playlist_id))
+            .order_by_desc(MusicPlaylistColumn::Position)
+            .one(self.db.as_ref())
+            .await?
+            .map_or(0, |last| last.position + 1))
+    }
+
     pub async fn find_by_id(&self, id: i32) -> Result<Option<MusicPlaylistModel>, DbErr> {
         MusicPlaylistEntity::find_by_id(id)
             .one(self.db.as_ref())
@@ -73,13 +136,9 @@ impl MusicPlaylistRepository {
     ) -> Result<Option<MusicPlaylistModel>, DbErr> {
         MusicPlaylistEntity::find()
             .filter(
-                MusicPlaylistColumn::PlaylistId.eq(playlist_id.id).and(
-                    MusicPlaylistColumn::MusicAlbum.eq(music_id.album).and(
-                        MusicPlaylistColumn::MusicArtist
-                            .eq(music_id.artist)
-                            .and(MusicPlaylistColumn::MusicTitle.eq(music_id.title)),
-                    ),
-                ),
+                MusicPlaylistColumn::PlaylistId
+                    .eq(playlist_id.id)
+                    .and(MusicPlaylistColumn::MusicId.eq(music_id.id)),
             )
             .one(self.db.as_ref())
             .await
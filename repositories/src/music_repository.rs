@@ -1,7 +1,13 @@
 use std::sync::Arc;
-use swaptun_models::{MusicActiveModel, MusicEntity, MusicModel, PlaylistModel};
+use swaptun_models::{
+    MusicActiveModel, MusicColumn, MusicEntity, MusicModel, MusicPlaylistColumn, PlaylistModel,
+};
 
-use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait, ModelTrait};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbErr, DeleteResult,
+    EntityTrait, ModelTrait, QueryFilter, QueryOrder, QuerySelect,
+};
 #[derive(Clone)]
 pub struct MusicRepository {
     db: Arc<DatabaseConnection>,
@@ -12,17 +18,31 @@ impl MusicRepository {
         Self { db }
     }
 
-    pub async fn find_by_id(
+    pub async fn find_by_id(&self, id: i32) -> Result<Option<MusicModel>, DbErr> {
+        MusicEntity::find_by_id(id).one(self.db.as_ref()).await
+    }
+
+    pub async fn find_by_title_artist_album(
         &self,
-        name: String,
+        title: String,
         artist: String,
         album: String,
     ) -> Result<Option<MusicModel>, DbErr> {
-        MusicEntity::find_by_id((name, artist, album))
+        MusicEntity::find()
+            .filter(MusicColumn::Title.eq(title))
+            .filter(MusicColumn::Artist.eq(artist))
+            .filter(MusicColumn::Album.eq(album))
             .one(self.db.as_ref())
             .await
     }
 
+    pub async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<MusicModel>, DbErr> {
+        MusicEntity::find()
+            .filter(MusicColumn::Id.is_in(ids.to_vec()))
+            .all(self.db.as_ref())
+            .await
+    }
+
     pub async fn find_all(&self) -> Result<Vec<MusicModel>, DbErr> {
         MusicEntity::find().all(self.db.as_ref()).await
     }
@@ -35,15 +55,8 @@ impl MusicRepository {
         music.update(self.db.as_ref()).await
     }
 
-    pub async fn delete(
-        &self,
-        name: String,
-        artist: String,
-        album: String,
-    ) -> Result<DeleteResult, DbErr> {
-        MusicEntity::delete_by_id((name, artist, album))
-            .exec(self.db.as_ref())
-            .await
+    pub async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr> {
+        MusicEntity::delete_by_id(id).exec(self.db.as_ref()).await
     }
 
     pub async fn find_by_playlist(
@@ -52,6 +65,35 @@ impl MusicRepository {
     ) -> Result<Vec<MusicModel>, DbErr> {
         playlist_model
             .find_related(MusicEntity)
+            .order_by_asc(MusicPlaylistColumn::Position)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    /// Musics never looked up on MusicBrainz that lack a genre or only have the placeholder
+    /// release date, oldest first. Musics failed `max_attempts` times are given up on and
+    /// those that failed after `failed_before` wait for a later run.
+    pub async fn find_pending_enrichment(
+        &self,
+        limit: u64,
+        max_attempts: i32,
+        failed_before: DateTime<FixedOffset>,
+    ) -> Result<Vec<MusicModel>, DbErr> {
+        MusicEntity::find()
+            .filter(MusicColumn::EnrichedOn.is_null())
+            .filter(MusicColumn::EnrichmentAttempts.lt(max_attempts))
+            .filter(
+                Condition::any()
+                    .add(MusicColumn::EnrichmentFailedOn.is_null())
+                    .add(MusicColumn::EnrichmentFailedOn.lt(failed_before)),
+            )
+            .filter(
+                Condition::any()
+                    .add(MusicColumn::Genre.is_null())
+                    .add(MusicColumn::ReleaseDate.eq(NaiveDate::default())),
+            )
+            .order_by_asc(MusicColumn::Id)
+            .limit(limit)
             .all(self.db.as_ref())
             .await
     }
@@ -0,0 +1,36 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use std::sync::Arc;
+use swaptun_models::{
+    MusicbrainzCacheActiveModel, MusicbrainzCacheColumn, MusicbrainzCacheEntity,
+    MusicbrainzCacheModel,
+};
+
+#[derive(Clone)]
+pub struct MusicbrainzCacheRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl MusicbrainzCacheRepository {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    pub async fn save(
+        &self,
+        model: MusicbrainzCacheActiveModel,
+    ) -> Result<MusicbrainzCacheActiveModel, DbErr> {
+        model.save(self.db.as_ref()).await
+    }
+
+    pub async fn find(
+        &self,
+        title: &str,
+        artist: &str,
+    ) -> Result<Option<MusicbrainzCacheModel>, DbErr> {
+        MusicbrainzCacheEntity::find()
+            .filter(MusicbrainzCacheColumn::Title.eq(title))
+            .filter(MusicbrainzCacheColumn::Artist.eq(artist))
+            .one(self.db.as_ref())
+            .await
+    }
+}
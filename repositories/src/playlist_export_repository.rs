@@ -0,0 +1,38 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use std::sync::Arc;
+use swaptun_models::{
+    PlaylistExportActiveModel, PlaylistExportColumn, PlaylistExportEntity, PlaylistExportModel,
+    PlaylistOrigin,
+};
+
+#[derive(Clone)]
+pub struct PlaylistExportRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl PlaylistExportRepository {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    pub async fn save(
+        &self,
+        model: PlaylistExportActiveModel,
+    ) -> Result<PlaylistExportActiveModel, DbErr> {
+        model.save(self.db.as_ref()).await
+    }
+
+    pub async fn find(
+        &self,
+        playlist_id: i32,
+        user_id: i32,
+        destination: PlaylistOrigin,
+    ) -> Result<Option<PlaylistExportModel>, DbErr> {
+        PlaylistExportEntity::find()
+            .filter(PlaylistExportColumn::PlaylistId.eq(playlist_id))
+            .filter(PlaylistExportColumn::UserId.eq(user_id))
+            .filter(PlaylistExportColumn::Destination.eq(destination))
+            .one(self.db.as_ref())
+            .await
+    }
+}
@@ -0,0 +1,71 @@
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait,
+    QueryFilter, QueryOrder,
+};
+use std::sync::Arc;
+use swaptun_models::{
+    PlaylistMirrorActiveModel, PlaylistMirrorColumn, PlaylistMirrorEntity, PlaylistMirrorModel,
+    PlaylistOrigin,
+};
+
+#[derive(Clone)]
+pub struct PlaylistMirrorRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl PlaylistMirrorRepository {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        model: PlaylistMirrorActiveModel,
+    ) -> Result<PlaylistMirrorModel, DbErr> {
+        model.insert(self.db.as_ref()).await
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> Result<Option<PlaylistMirrorModel>, DbErr> {
+        PlaylistMirrorEntity::find_by_id(id)
+            .one(self.db.as_ref())
+            .await
+    }
+
+    pub async fn find(
+        &self,
+        playlist_id: i32,
+        user_id: i32,
+        destination: PlaylistOrigin,
+    ) -> Result<Option<PlaylistMirrorModel>, DbErr> {
+        PlaylistMirrorEntity::find()
+            .filter(PlaylistMirrorColumn::PlaylistId.eq(playlist_id))
+            .filter(PlaylistMirrorColumn::UserId.eq(user_id))
+            .filter(PlaylistMirrorColumn::Destination.eq(destination))
+            .one(self.db.as_ref())
+            .await
+    }
+
+    pub async fn find_by_user(&self, user_id: i32) -> Result<Vec<PlaylistMirrorModel>, DbErr> {
+        PlaylistMirrorEntity::find()
+            .filter(PlaylistMirrorColumn::UserId.eq(user_id))
+            .order_by_asc(PlaylistMirrorColumn::CreatedOn)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    pub async fn find_by_playlist(
+        &self,
+        playlist_id: i32,
+    ) -> Result<Vec<PlaylistMirrorModel>, DbErr> {
+        PlaylistMirrorEntity::find()
+            .filter(PlaylistMirrorColumn::PlaylistId.eq(playlist_id))
+            .all(self.db.as_ref())
+            .await
+    }
+
+    pub async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr> {
+        PlaylistMirrorEntity::delete_by_id(id)
+            .exec(self.db.as_ref())
+            .await
+    }
+}
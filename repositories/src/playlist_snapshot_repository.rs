@@ -0,0 +1,65 @@
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect,
+};
+use std::sync::Arc;
+use swaptun_models::{
+    PlaylistSnapshotActiveModel, PlaylistSnapshotColumn, PlaylistSnapshotEntity,
+    PlaylistSnapshotModel,
+};
+
+#[derive(Clone)]
+pub struct PlaylistSnapshotRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl PlaylistSnapshotRepository {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        model: PlaylistSnapshotActiveModel,
+    ) -> Result<PlaylistSnapshotModel, DbErr> {
+        model.insert(self.db.as_ref()).await
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> Result<Option<PlaylistSnapshotModel>, DbErr> {
+        PlaylistSnapshotEntity::find_by_id(id)
+            .one(self.db.as_ref())
+            .await
+    }
+
+    /// Snapshots of the playlist, most recent first
+    pub async fn find_by_playlist(
+        &self,
+        playlist_id: i32,
+    ) -> Result<Vec<PlaylistSnapshotModel>, DbErr> {
+        PlaylistSnapshotEntity::find()
+            .filter(PlaylistSnapshotColumn::PlaylistId.eq(playlist_id))
+            .order_by_desc(PlaylistSnapshotColumn::CreatedOn)
+            .order_by_desc(PlaylistSnapshotColumn::Id)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    /// Deletes the snapshots of the playlist older than the `keep` most recent ones
+    pub async fn delete_oldest(&self, playlist_id: i32, keep: u64) -> Result<DeleteResult, DbErr> {
+        let old_ids: Vec<i32> = PlaylistSnapshotEntity::find()
+            .select_only()
+            .column(PlaylistSnapshotColumn::Id)
+            .filter(PlaylistSnapshotColumn::PlaylistId.eq(playlist_id))
+            .order_by_desc(PlaylistSnapshotColumn::CreatedOn)
+            .order_by_desc(PlaylistSnapshotColumn::Id)
+            .offset(keep)
+            .into_tuple()
+            .all(self.db.as_ref())
+            .await?;
+
+        PlaylistSnapshotEntity::delete_many()
+            .filter(PlaylistSnapshotColumn::Id.is_in(old_ids))
+            .exec(self.db.as_ref())
+            .await
+    }
+}
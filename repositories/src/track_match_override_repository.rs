@@ -0,0 +1,67 @@
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait,
+    QueryFilter,
+};
+use std::sync::Arc;
+use swaptun_models::{
+    PlaylistOrigin, TrackMatchOverrideActiveModel, TrackMatchOverrideColumn,
+    TrackMatchOverrideEntity, TrackMatchOverrideModel,
+};
+
+#[derive(Clone)]
+pub struct TrackMatchOverrideRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl TrackMatchOverrideRepository {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    pub async fn save(
+        &self,
+        model: TrackMatchOverrideActiveModel,
+    ) -> Result<TrackMatchOverrideActiveModel, DbErr> {
+        model.save(self.db.as_ref()).await
+    }
+
+    pub async fn find(
+        &self,
+        user_id: i32,
+        music_id: i32,
+        origin: PlaylistOrigin,
+    ) -> Result<Option<TrackMatchOverrideModel>, DbErr> {
+        TrackMatchOverrideEntity::find()
+            .filter(TrackMatchOverrideColumn::UserId.eq(user_id))
+            .filter(TrackMatchOverrideColumn::MusicId.eq(music_id))
+            .filter(TrackMatchOverrideColumn::Origin.eq(origin))
+            .one(self.db.as_ref())
+            .await
+    }
+
+    pub async fn find_by_user_and_origin(
+        &self,
+        user_id: i32,
+        origin: PlaylistOrigin,
+    ) -> Result<Vec<TrackMatchOverrideModel>, DbErr> {
+        TrackMatchOverrideEntity::find()
+            .filter(TrackMatchOverrideColumn::UserId.eq(user_id))
+            .filter(TrackMatchOverrideColumn::Origin.eq(origin))
+            .all(self.db.as_ref())
+            .await
+    }
+
+    pub async fn delete(
+        &self,
+        user_id: i32,
+        music_id: i32,
+        origin: PlaylistOrigin,
+    ) -> Result<DeleteResult, DbErr> {
+        TrackMatchOverrideEntity::delete_many()
+            .filter(TrackMatchOverrideColumn::UserId.eq(user_id))
+            .filter(TrackMatchOverrideColumn::MusicId.eq(music_id))
+            .filter(TrackMatchOverrideColumn::Origin.eq(origin))
+            .exec(self.db.as_ref())
+            .await
+    }
+}
@@ -9,6 +9,7 @@ use swaptun_models::{
     FriendshipColumn, FriendshipEntity, UserActiveModel, UserColumn, UserEntity, UserModel,
 };
 
+#[derive(Clone)]
 pub struct UserRepository {
     db: Arc<DatabaseConnection>,
 }
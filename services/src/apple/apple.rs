@@ -1,75 +1,43 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::{error::AppError, GetDeveloperToken};
 use crate::{
-    AddTokenRequest, CreateMusicRequest, CreatePlaylistRequest, MusicService, NotificationService,
-    PlaylistService,
+    error::AppError, AppleCatalogSong, AppleLibraryPlaylist, AppleLibrarySong, AppleResourceRef,
+    AppleResponse, AppleSearchResponse, AppleStorefront, ArtistCredit, GetDeveloperToken,
+};
+use crate::{
+    find_override, import_each, report_import, AddTokenRequest, CreateMusicRequest,
+    CreatePlaylistRequest, EventHub, ImportSelection, JobProgress, MusicProvider, MusicService,
+    NotificationService, PlaylistDiff, PlaylistExport, PlaylistService, RemotePlaylist,
+    ScoredMatch, TrackCandidate, TrackMatchEntry, TrackMatchOverrideService, TrackMatcher,
+    TrackQuery, UserEvent, LIKED_SONGS_NAME, LIKED_SONGS_ORIGIN_ID,
 };
-use apple_music_api::catalog::Song;
 use apple_music_api::config::ClientConfigBuilder;
 use apple_music_api::library::LibraryPlaylistsResponse;
+use async_trait::async_trait;
+use chrono::NaiveDate;
 use futures::{stream, StreamExt};
 
 use apple_music_api::{create_developer_token, AppleMusicClient};
-use log::{error, info};
+use log::{error, info, warn};
+use reqwest::{StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use sea_orm::{DatabaseConnection, IntoActiveModel, Set};
-use swaptun_models::{playlist, AppleTokenActiveModel, AppleTokenModel, MusicModel, UserModel};
+use swaptun_models::{
+    playlist, AppleTokenActiveModel, AppleTokenModel, MusicModel, PlaylistModel, UserModel,
+};
 use swaptun_repositories::AppleTokenRepository;
-/// Normalize a string for fuzzy matching
-fn normalize_string(s: &str) -> String {
-    s.to_lowercase()
-        .chars()
-        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-        .collect::<String>()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
-}
 
-/// Check if two strings match with fuzzy logic
-fn fuzzy_match(str1: &str, str2: &str) -> bool {
-    let norm1 = normalize_string(str1);
-    let norm2 = normalize_string(str2);
-
-    if norm1 == norm2 {
-        return true;
-    }
-
-    if norm1.contains(&norm2) || norm2.contains(&norm1) {
-        return true;
-    }
+const APPLE_MUSIC_API_URL: &str = "https://api.music.apple.com/v1";
 
-    false
-}
-
-/// Check if artist matches (Apple Music artist is a String)
-fn artist_matches_apple(apple_artist: &str, db_artist: &str) -> bool {
-    let db_artist_norm = normalize_string(db_artist);
-    let apple_artist_norm = normalize_string(apple_artist);
-
-    if fuzzy_match(&apple_artist_norm, &db_artist_norm) {
-        return true;
-    }
-
-    let db_artist_parts: Vec<&str> = db_artist
-        .split(&['&', ','][..])
-        .chain(db_artist.split(" feat "))
-        .chain(db_artist.split(" feat. "))
-        .chain(db_artist.split(" ft "))
-        .chain(db_artist.split(" ft. "))
-        .chain(db_artist.split(" with "))
-        .map(|s| s.trim())
-        .collect();
-
-    for db_part in &db_artist_parts {
-        let db_part_norm = normalize_string(db_part);
-        if fuzzy_match(&apple_artist_norm, &db_part_norm) {
-            return true;
-        }
-    }
-
-    false
+/// Tokens and storefront needed to call the Apple Music catalog directly
+#[derive(Clone)]
+pub struct AppleCatalogSession {
+    developer_token: String,
+    user_token: String,
+    storefront: String,
 }
 
 #[derive(Clone)]
@@ -77,6 +45,9 @@ pub struct AppleMusicService {
     apple_token_repository: AppleTokenRepository,
     playlist_service: PlaylistService,
     music_service: MusicService,
+    matcher: TrackMatcher,
+    track_match_override_service: TrackMatchOverrideService,
+    http_client: reqwest::Client,
     db: Arc<DatabaseConnection>,
 }
 
@@ -89,6 +60,9 @@ impl AppleMusicService {
             apple_token_repository,
             playlist_service,
             music_service,
+            matcher: TrackMatcher::from_env(),
+            track_match_override_service: TrackMatchOverrideService::new(db.clone()),
+            http_client: reqwest::Client::new(),
             db,
         }
     }
@@ -193,52 +167,87 @@ impl AppleMusicService {
         Ok(client.get_library_playlists().await?)
     }
 
-    pub async fn import_playlists(&self, user: &UserModel) -> Result<(), AppError> {
+    pub async fn import_playlists(
+        &self,
+        user: &UserModel,
+        selection: &ImportSelection,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
         let client = self.get_apple_client(user).await?;
         let playlists = client.get_library_playlists().await?;
         info!("Found {} Apple Music playlists", playlists.data.len());
 
-        // Spawn background task to import playlists
-        let service = self.clone();
-        let user_clone = user.clone();
-        let playlist_ids: Vec<String> = playlists.data.iter().map(|p| p.id.clone()).collect();
+        let playlist_ids = playlists
+            .data
+            .into_iter()
+            .map(|playlist| playlist.id)
+            .filter(|id| selection.includes(id));
+        let client = &client;
+        let mut result = import_each(
+            playlist_ids,
+            |id| id.clone(),
+            progress,
+            |id| async move { self.import_playlist(user, client, id, progress).await },
+        )
+        .await;
 
-        tokio::spawn(async move {
-            info!(
-                "Starting background import of {} Apple Music playlists",
-                playlist_ids.len()
-            );
+        if selection.includes(LIKED_SONGS_ORIGIN_ID) {
+            let library_songs = self.import_library_songs(user, progress).await;
+            result = result.and(report_import(LIKED_SONGS_NAME, library_songs, progress).await);
+        }
 
-            match service.get_apple_client(&user_clone).await {
-                Ok(client) => {
-                    for playlist_id in playlist_ids {
-                        if let Err(e) = service
-                            .import_playlist(&user_clone, &client, playlist_id.clone())
-                            .await
-                        {
-                            error!(
-                                "Error importing Apple Music playlist {}: {:?}",
-                                playlist_id, e
-                            );
-                        } else {
-                            info!(
-                                "Successfully imported Apple Music playlist: {}",
-                                playlist_id
-                            );
-                        }
-                    }
-                    info!("Background import of Apple Music playlists completed");
-                }
-                Err(e) => {
-                    error!(
-                        "Failed to get Apple Music client for background import: {:?}",
-                        e
-                    );
+        result
+    }
+
+    /// Lists the library songs and the library playlists without importing them. Apple Music
+    /// only counts the tracks of a playlist when it is read.
+    pub async fn list_playlists(&self, user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError> {
+        let session = self.catalog_session(user).await?;
+        let mut remote_playlists = vec![RemotePlaylist {
+            id: LIKED_SONGS_ORIGIN_ID.to_string(),
+            name: LIKED_SONGS_NAME.to_string(),
+            track_count: None,
+            owned: Some(true),
+            image_url: None,
+            selected: false,
+        }];
+
+        let mut offset = 0;
+        loop {
+            // An empty library has no playlists resource at all
+            let page = match self
+                .api_get::<AppleResponse<AppleLibraryPlaylist>>(
+                    &session,
+                    "/me/library/playlists",
+                    &[("limit", "100"), ("offset", &offset.to_string())],
+                )
+                .await
+            {
+                Ok(page) => page.data,
+                Err(AppError::NotFound(_)) => Vec::new(),
+                Err(e) => return Err(e),
+            };
+            let last_page = page.len() < 100;
+            offset += page.len();
+            remote_playlists.extend(page.into_iter().map(|playlist| {
+                RemotePlaylist {
+                    id: playlist.id,
+                    name: playlist.attributes.name,
+                    track_count: None,
+                    owned: playlist.attributes.can_edit,
+                    image_url: playlist
+                        .attributes
+                        .artwork
+                        .map(|artwork| artwork.url_square(600)),
+                    selected: false,
                 }
+            }));
+            if last_page {
+                break;
             }
-        });
+        }
 
-        Ok(())
+        Ok(remote_playlists)
     }
 
     pub async fn import_playlist(
@@ -246,6 +255,7 @@ impl AppleMusicService {
         user: &UserModel,
         client: &AppleMusicClient,
         playlist_id: String,
+        progress: &JobProgress,
     ) -> Result<(), AppError> {
         let playlist = client
             .get_library_playlist_with_tracks(&playlist_id)
@@ -273,65 +283,171 @@ impl AppleMusicService {
             .tracks
             .unwrap_or_default()
             .data;
+        progress.start_playlist(&title, songs.len()).await;
 
-        let mut local_tracks = self
-            .music_service
-            .find_by_playlist(&created_playlist)
-            .await?;
+        // Library songs don't expose their ISRC nor most metadata, they are read from their
+        // catalog version
+        let catalog_ids: Vec<String> = songs
+            .iter()
+            .filter_map(|song| catalog_song_id(&song.id).map(str::to_string))
+            .collect();
+        let mut catalog_songs = match self.catalog_session(user).await {
+            Ok(session) => self.get_catalog_songs(&session, &catalog_ids).await,
+            Err(e) => {
+                error!("Could not open Apple Music catalog session: {:?}", e);
+                HashMap::new()
+            }
+        };
 
+        let mut music_ids = Vec::new();
         let music_service = &self.music_service;
-        let playlist_service = &self.playlist_service;
-        let created_playlist = &created_playlist;
         for song in songs {
+            progress.track_done().await;
+            let catalog = catalog_song_id(&song.id).and_then(|id| catalog_songs.remove(id));
             let attributes = song.attributes;
-            let title = attributes.name.expect("Failed to get track name").clone();
-            let artist = attributes
-                .artist_name
-                .expect("Failed to get artist name")
-                .clone();
-            let album = attributes
-                .album_name
-                .expect("Failed to get album name")
-                .clone();
-            // Check if track already exists
-            if let Some(pos) = local_tracks.iter().position(|local_track| {
-                local_track.title == title
-                    && local_track.artist == artist
-                    && local_track.album == album
-            }) {
-                local_tracks.remove(pos);
-                return Ok(());
+            let title = match (attributes.name, &catalog) {
+                (Some(name), _) => name,
+                (None, Some(catalog)) => catalog.attributes.name.clone(),
+                (None, None) => {
+                    warn!("Skipping Apple Music track {} without a name", song.id);
+                    continue;
+                }
+            };
+            let create_music_request = library_music_request(
+                title,
+                attributes.artist_name.unwrap_or_default(),
+                attributes.album_name.unwrap_or_default(),
+                attributes.genre_names.first().cloned(),
+                attributes
+                    .release_date
+                    .map(|date| NaiveDate::from(date.naive_local()))
+                    .unwrap_or_default(),
+                catalog,
+            );
+            let music = music_service.create(create_music_request).await?;
+            if let Some(catalog_id) = catalog_song_id(&song.id) {
+                music_service
+                    .save_external_id(music.id, playlist::PlaylistOrigin::AppleMusic, catalog_id)
+                    .await?;
             }
+            music_ids.push(music.id);
+        }
 
-            let create_music_request = CreateMusicRequest {
-                title: title,
-                artist: artist,
-                album: album,
-                description: None,
-                genre: attributes.genre_names.get(0).cloned(),
-                release_date: attributes
-                    .release_date
-                    .expect("Failed to get release date")
-                    .clone()
-                    .naive_local()
-                    .into(),
+        // Adds the new tracks and removes the ones no longer in the playlist
+        self.playlist_service
+            .sync_musics(&created_playlist, &music_ids)
+            .await?;
+        progress.finish_playlist().await;
+        self.notify_playlist_imported(user, &created_playlist).await;
+
+        Ok(())
+    }
+
+    /// Imports the songs added to the user's library as their liked songs playlist
+    pub async fn import_library_songs(
+        &self,
+        user: &UserModel,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        let session = self.catalog_session(user).await?;
+        let mut songs = Vec::new();
+        loop {
+            let offset = songs.len().to_string();
+            // An empty library has no songs resource at all
+            let page = match self
+                .api_get::<AppleResponse<AppleLibrarySong>>(
+                    &session,
+                    "/me/library/songs",
+                    &[("limit", "100"), ("offset", &offset)],
+                )
+                .await
+            {
+                Ok(page) => page.data,
+                Err(AppError::NotFound(_)) => Vec::new(),
+                Err(e) => return Err(e),
             };
-            let music = music_service.create(create_music_request).await?;
-            playlist_service.add_music(&created_playlist, music).await?;
+            let last_page = page.len() < 100;
+            songs.extend(page);
+            if last_page {
+                break;
+            }
         }
 
-        // Remove tracks that are no longer in the playlist
-        for local_track in local_tracks {
-            info!("Removing track from playlist: {:?}", local_track);
-            self.playlist_service
-                .remove_music(&created_playlist, &local_track)
-                .await?;
+        let request = CreatePlaylistRequest::liked_songs(playlist::PlaylistOrigin::AppleMusic);
+        progress.start_playlist(&request.name, songs.len()).await;
+        let playlist = self.playlist_service.create_or_get(request, user).await?;
+
+        let catalog_ids: Vec<String> = songs
+            .iter()
+            .filter_map(|song| song.catalog_id().map(str::to_string))
+            .collect();
+        let mut catalog_songs = self.get_catalog_songs(&session, &catalog_ids).await;
+
+        let mut music_ids = Vec::new();
+        for song in songs {
+            progress.track_done().await;
+            let catalog_id = song.catalog_id().map(str::to_string);
+            let catalog = catalog_id.as_ref().and_then(|id| catalog_songs.remove(id));
+            let Some(attributes) = song.attributes else {
+                warn!(
+                    "Skipping Apple Music library song {} without attributes",
+                    song.id
+                );
+                continue;
+            };
+            let title = match (attributes.name, &catalog) {
+                (Some(name), _) => name,
+                (None, Some(catalog)) => catalog.attributes.name.clone(),
+                (None, None) => {
+                    warn!(
+                        "Skipping Apple Music library song {} without a name",
+                        song.id
+                    );
+                    continue;
+                }
+            };
+            let create_music_request = library_music_request(
+                title,
+                attributes.artist_name.unwrap_or_default(),
+                attributes.album_name.unwrap_or_default(),
+                attributes.genre_names.into_iter().next(),
+                attributes
+                    .release_date
+                    .and_then(|date| date.parse().ok())
+                    .unwrap_or_default(),
+                catalog,
+            );
+            let music = self.music_service.create(create_music_request).await?;
+            if let Some(catalog_id) = catalog_id {
+                self.music_service
+                    .save_external_id(music.id, playlist::PlaylistOrigin::AppleMusic, &catalog_id)
+                    .await?;
+            }
+            music_ids.push(music.id);
         }
 
+        self.playlist_service
+            .sync_musics(&playlist, &music_ids)
+            .await?;
+        progress.finish_playlist().await;
+        self.notify_playlist_imported(user, &playlist).await;
+
+        Ok(())
+    }
+
+    async fn notify_playlist_imported(&self, user: &UserModel, playlist: &PlaylistModel) {
+        EventHub::global().publish(
+            user.id,
+            UserEvent::PlaylistImported {
+                playlist_id: playlist.id,
+                origin: playlist::PlaylistOrigin::AppleMusic,
+            },
+        );
+
         // Send silent notification with only playlist ID (lightweight)
         let notification_data = serde_json::json!({
             "type": "playlist_sync",
-            "playlist_id": created_playlist.id.to_string(),
+            "playlist_id": playlist.id.to_string(),
             "origin": "AppleMusic",
         });
 
@@ -343,28 +459,24 @@ impl AppleMusicService {
             {
                 error!(
                     "Failed to send silent notification for playlist {}: {:?}",
-                    title, e
+                    playlist.name, e
                 );
             } else {
-                info!("Silent notification sent for playlist: {}", title);
+                info!("Silent notification sent for playlist: {}", playlist.name);
             }
         }
-
-        Ok(())
     }
 
-    /// Exports a playlist from our database to Apple Music
-    pub async fn export_playlist_to_apple(
+    /// Searches Apple Music for every track of a database playlist without writing anything
+    pub async fn match_playlist_tracks(
         &self,
         playlist_id: i32,
         user: &UserModel,
-    ) -> Result<String, AppError> {
+        progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError> {
         // Get the database playlist
         let playlist = self.playlist_service.get_playlist(playlist_id).await?;
 
-        // Get Apple Music client
-        let client = self.get_apple_client(user).await?;
-
         // Get tracks from the database playlist
         let tracks = self
             .music_service
@@ -375,21 +487,74 @@ impl AppleMusicService {
                 AppError::InternalServerError
             })?;
 
+        let overrides = self
+            .track_match_override_service
+            .get_overrides(user, playlist::PlaylistOrigin::AppleMusic)
+            .await?;
+        let overrides = &overrides;
+
+        let session = self.catalog_session(user).await?;
+        let session = &session;
+        progress.start_playlist(&playlist.name, tracks.len()).await;
+
         let concurrency_limit = 3; // ajuste selon les quotas de l’API Apple
 
-        let songs: Vec<Song> = stream::iter(tracks.clone().into_iter().map(|track| {
-            let client = client.clone(); // si ton client est clonable
-            async move {
-                let query = format!("{} {}", track.title, track.artist);
-                self.search_song(&client, query, track).await
-            }
-        }))
-        .buffer_unordered(concurrency_limit)
-        .filter_map(|res| async move { res }) // garde seulement les Some(Song)
-        .collect()
-        .await;
-        let apple_track_ids: Vec<String> =
-            songs.iter().map(|opt_song| opt_song.id.clone()).collect();
+        // `buffered` keeps the playlist order so the report lines up with the tracks
+        let report: Vec<TrackMatchEntry> =
+            stream::iter(tracks.into_iter().map(|track| async move {
+                if let Some(platform_track_id) = find_override(overrides, &track) {
+                    return TrackMatchEntry::overridden(&track, platform_track_id);
+                }
+                match self
+                    .music_service
+                    .find_external_id(&track, playlist::PlaylistOrigin::AppleMusic)
+                    .await
+                {
+                    Ok(Some(external_id)) => return TrackMatchEntry::known(&track, &external_id),
+                    Ok(None) => {}
+                    Err(e) => error!("Error getting Apple Music id of {:?}: {:?}", track, e),
+                }
+                if let Some(isrc) = &track.isrc {
+                    if let Some(found) = self.find_song_by_isrc(session, isrc).await {
+                        return found.report_entry(&track, found.item.id.clone());
+                    }
+                }
+                match self.search_song(session, &track).await {
+                    Some(best) => best.report_entry(&track, best.item.id.clone()),
+                    None => TrackMatchEntry::not_found(&track),
+                }
+            }))
+            .buffered(concurrency_limit)
+            .then(|entry| async move {
+                progress.track_matched(&entry).await;
+                entry
+            })
+            .collect()
+            .await;
+
+        progress.finish_playlist().await;
+        Ok(report)
+    }
+
+    /// Exports a playlist from our database to Apple Music with the matched tracks
+    pub async fn export_playlist_to_apple(
+        &self,
+        playlist_id: i32,
+        user: &UserModel,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        // Get the database playlist
+        let playlist = self.playlist_service.get_playlist(playlist_id).await?;
+
+        // Get Apple Music client
+        let client = self.get_apple_client(user).await?;
+
+        let apple_track_ids: Vec<String> = report
+            .iter()
+            .filter_map(|entry| entry.platform_track_id.clone())
+            .collect();
+        let added = apple_track_ids.len();
         // Create the playlist on Apple Music in the Swaptun folder
         let apple_playlist = client
             .create_library_playlist(
@@ -408,49 +573,293 @@ impl AppleMusicService {
             "Created Apple Music playlist with ID: {}",
             apple_playlist.id
         );
+        progress.tracks_done(added).await;
 
-        Ok(apple_playlist.id)
+        Ok(PlaylistExport {
+            playlist_id: apple_playlist.id,
+            report,
+        })
+    }
+
+    /// Adds the missing matched tracks to an Apple Music playlist created by a previous send.
+    /// The API can't remove nor move the tracks of a library playlist, they are left as is.
+    pub async fn update_apple_playlist(
+        &self,
+        platform_playlist_id: &str,
+        user: &UserModel,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        let session = self.catalog_session(user).await?;
+        let playlist_path = format!("/me/library/playlists/{}", platform_playlist_id);
+
+        // Fails with `NotFound` when the playlist was deleted
+        self.api_get::<serde_json::Value>(&session, &playlist_path, &[])
+            .await?;
+
+        let tracks_path = format!("{}/tracks", playlist_path);
+        let mut current = Vec::new();
+        loop {
+            let offset = current.len().to_string();
+            // An empty playlist has no tracks resource at all
+            let page = match self
+                .api_get::<AppleResponse<AppleLibrarySong>>(
+                    &session,
+                    &tracks_path,
+                    &[("limit", "100"), ("offset", &offset)],
+                )
+                .await
+            {
+                Ok(page) => page.data,
+                Err(AppError::NotFound(_)) => Vec::new(),
+                Err(e) => return Err(e),
+            };
+            let last_page = page.len() < 100;
+            current.extend(page.iter().map(|song| {
+                song.catalog_id()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| song.id.clone())
+            }));
+            if last_page {
+                break;
+            }
+        }
+
+        let desired: Vec<String> = report
+            .iter()
+            .filter_map(|entry| entry.platform_track_id.clone())
+            .collect();
+        let diff = PlaylistDiff::between(&current, &desired);
+        // Tracks already in the playlist are done
+        progress
+            .tracks_done(desired.len().saturating_sub(diff.to_add.len()))
+            .await;
+        if !diff.to_remove.is_empty() || diff.reorder {
+            info!(
+                "Apple Music playlist {} keeps {} dropped tracks and its order",
+                platform_playlist_id,
+                diff.to_remove.len()
+            );
+        }
+
+        for chunk in diff.to_add.chunks(100) {
+            let body = AppleResponse {
+                data: chunk.iter().map(|id| AppleResourceRef::song(id)).collect(),
+            };
+            self.api_post(&session, &tracks_path, &body).await?;
+            progress.tracks_done(chunk.len()).await;
+        }
+        info!(
+            "Added {} tracks to Apple Music playlist {}",
+            diff.to_add.len(),
+            platform_playlist_id
+        );
+
+        Ok(PlaylistExport {
+            playlist_id: platform_playlist_id.to_string(),
+            report,
+        })
+    }
+
+    /// Opens a session on the Apple Music catalog in the storefront of the user
+    pub async fn catalog_session(&self, user: &UserModel) -> Result<AppleCatalogSession, AppError> {
+        let developer_token = self.generate_developer_token().await?.developer_token;
+        let user_token = match self.get_token(user).await? {
+            Some(token) => token.access_token,
+            None => {
+                error!("No apple token found for user {}", user.id);
+                return Err(AppError::InternalServerError);
+            }
+        };
+
+        let mut session = AppleCatalogSession {
+            developer_token,
+            user_token,
+            storefront: String::new(),
+        };
+        let storefronts: AppleResponse<AppleStorefront> =
+            self.api_get(&session, "/me/storefront", &[]).await?;
+        session.storefront = storefronts
+            .data
+            .into_iter()
+            .next()
+            .map(|storefront| storefront.id)
+            .ok_or(AppError::InternalServerError)?;
+
+        Ok(session)
+    }
+
+    async fn api_get<T: DeserializeOwned>(
+        &self,
+        session: &AppleCatalogSession,
+        path: &str,
+        params: &[(&str, &str)],
+    ) -> Result<T, AppError> {
+        let url = Url::parse_with_params(&format!("{}{}", APPLE_MUSIC_API_URL, path), params)?;
+
+        self.http_client
+            .get(url)
+            .bearer_auth(&session.developer_token)
+            .header("Music-User-Token", &session.user_token)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| {
+                error!("Apple Music request to {} failed: {:?}", path, e);
+                if e.status() == Some(StatusCode::NOT_FOUND) {
+                    AppError::NotFound(format!("Apple Music resource {} not found", path))
+                } else {
+                    AppError::InternalServerError
+                }
+            })?
+            .json()
+            .await
+            .map_err(|e| {
+                error!(
+                    "Failed to decode Apple Music response for {}: {:?}",
+                    path, e
+                );
+                AppError::InternalServerError
+            })
+    }
+
+    /// Performs a POST with a JSON body, the API answers without content
+    async fn api_post<B: Serialize>(
+        &self,
+        session: &AppleCatalogSession,
+        path: &str,
+        body: &B,
+    ) -> Result<(), AppError> {
+        self.http_client
+            .post(format!("{}{}", APPLE_MUSIC_API_URL, path))
+            .bearer_auth(&session.developer_token)
+            .header("Music-User-Token", &session.user_token)
+            .json(body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| {
+                error!("Apple Music request to {} failed: {:?}", path, e);
+                if e.status() == Some(StatusCode::NOT_FOUND) {
+                    AppError::NotFound(format!("Apple Music resource {} not found", path))
+                } else {
+                    AppError::InternalServerError
+                }
+            })?;
+        Ok(())
+    }
+
+    /// Looks a catalog song up by its ISRC, which identifies the exact recording
+    pub async fn find_song_by_isrc(
+        &self,
+        session: &AppleCatalogSession,
+        isrc: &str,
+    ) -> Option<ScoredMatch<AppleCatalogSong>> {
+        let path = format!("/catalog/{}/songs", session.storefront);
+        match self
+            .api_get::<AppleResponse<AppleCatalogSong>>(session, &path, &[("filter[isrc]", isrc)])
+            .await
+        {
+            Ok(response) => response.data.into_iter().next().map(|song| {
+                info!("Found track on Apple Music by ISRC {}: {}", isrc, song.id);
+                ScoredMatch::exact(
+                    song.clone(),
+                    song.attributes.name,
+                    song.attributes.artist_name,
+                )
+            }),
+            Err(e) => {
+                error!("Error searching Apple Music for ISRC {}: {:?}", isrc, e);
+                None
+            }
+        }
+    }
+
+    /// Returns the given catalog songs along with their artists, keyed by song id
+    pub async fn get_catalog_songs(
+        &self,
+        session: &AppleCatalogSession,
+        catalog_ids: &[String],
+    ) -> HashMap<String, AppleCatalogSong> {
+        let path = format!("/catalog/{}/songs", session.storefront);
+        let mut songs = HashMap::new();
+
+        // The catalog accepts up to 300 ids per request
+        for chunk in catalog_ids.chunks(300) {
+            let ids = chunk.join(",");
+            match self
+                .api_get::<AppleResponse<AppleCatalogSong>>(
+                    session,
+                    &path,
+                    &[("ids", &ids), ("include", "artists")],
+                )
+                .await
+            {
+                Ok(response) => {
+                    songs.extend(
+                        response
+                            .data
+                            .into_iter()
+                            .map(|song| (song.id.clone(), song)),
+                    );
+                }
+                Err(e) => error!("Error getting Apple Music catalog songs: {:?}", e),
+            }
+        }
+
+        songs
     }
 
     pub async fn search_song(
         &self,
-        client: &AppleMusicClient,
-        query: String,
-        track: MusicModel,
-    ) -> Option<Song> {
-        match client.search_songs(&query).await {
-            Ok(songs) => {
-                for apple_song in songs.iter().take(5) {
-                    // Use fuzzy matching for title and artist
-                    let title_matches = fuzzy_match(&apple_song.attributes.name, &track.title);
-                    let artist_matches =
-                        artist_matches_apple(&apple_song.attributes.artist_name, &track.artist);
-
-                    if title_matches && artist_matches {
-                        // Add song to library first
+        session: &AppleCatalogSession,
+        track: &MusicModel,
+    ) -> Option<ScoredMatch<AppleCatalogSong>> {
+        let path = format!("/catalog/{}/search", session.storefront);
+        let term = format!("{} {}", track.title, track.artist);
+        match self
+            .api_get::<AppleSearchResponse>(
+                session,
+                &path,
+                &[("term", &term), ("types", "songs"), ("limit", "5")],
+            )
+            .await
+        {
+            Ok(response) => {
+                let songs = response.results.songs.map(|songs| songs.data);
+                let candidates = songs
+                    .into_iter()
+                    .flatten()
+                    .map(|apple_song| TrackCandidate {
+                        title: apple_song.attributes.name.clone(),
+                        artists: vec![apple_song.attributes.artist_name.clone()],
+                        album: apple_song.attributes.album_name.clone(),
+                        duration_ms: apple_song
+                            .attributes
+                            .duration_in_millis
+                            .and_then(|duration| u32::try_from(duration).ok()),
+                        item: apple_song,
+                    });
 
+                match self
+                    .matcher
+                    .best_match(&TrackQuery::from(track), candidates)
+                {
+                    Some(best) => {
                         info!(
-                            "Found track on Apple Music: {} - {} (matched with: {} - {})",
-                            track.artist,
-                            track.title,
-                            apple_song.attributes.artist_name,
-                            apple_song.attributes.name
+                            "Found track on Apple Music: {} - {} (id: {}, confidence: {:.2})",
+                            track.artist, track.title, best.item.id, best.score
                         );
-                        return Some(apple_song.clone());
-                    } else {
+                        Some(best)
+                    }
+                    None => {
                         info!(
-                                "Track found but does not match - Title match: {}, Artist match: {} | Apple: {} - {}, DB: {} - {}",
-                                title_matches,
-                                artist_matches,
-                                apple_song.attributes.artist_name,
-                                apple_song.attributes.name,
-                                track.artist,
-                                track.title
-                            );
+                            "Track not found on Apple Music: {} - {}",
+                            track.artist, track.title
+                        );
+                        None
                     }
                 }
-
-                return None;
             }
             Err(e) => {
                 error!(
@@ -484,3 +893,140 @@ impl AppleMusicService {
         Ok(())
     }
 }
+
+#[async_trait]
+impl MusicProvider for AppleMusicService {
+    fn origin(&self) -> playlist::PlaylistOrigin {
+        playlist::PlaylistOrigin::AppleMusic
+    }
+
+    async fn connect(&self, user: &UserModel, request: AddTokenRequest) -> Result<(), AppError> {
+        self.add_user_token(request, user.id).await
+    }
+
+    async fn list_playlists(&self, user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError> {
+        AppleMusicService::list_playlists(self, user).await
+    }
+
+    async fn import_playlists(
+        &self,
+        user: &UserModel,
+        selection: &ImportSelection,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        AppleMusicService::import_playlists(self, user, selection, progress).await
+    }
+
+    async fn search_track(
+        &self,
+        user: &UserModel,
+        music: &MusicModel,
+    ) -> Result<TrackMatchEntry, AppError> {
+        let session = self.catalog_session(user).await?;
+        Ok(match self.search_song(&session, music).await {
+            Some(best) => best.report_entry(music, best.item.id.clone()),
+            None => TrackMatchEntry::not_found(music),
+        })
+    }
+
+    async fn preview_playlist(
+        &self,
+        user: &UserModel,
+        playlist_id: i32,
+        progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError> {
+        self.match_playlist_tracks(playlist_id, user, progress)
+            .await
+    }
+
+    async fn create_playlist_from_matches(
+        &self,
+        user: &UserModel,
+        playlist_id: i32,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        self.export_playlist_to_apple(playlist_id, user, report, progress)
+            .await
+    }
+
+    async fn update_playlist_from_matches(
+        &self,
+        user: &UserModel,
+        platform_playlist_id: &str,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        self.update_apple_playlist(platform_playlist_id, user, report, progress)
+            .await
+    }
+
+    async fn connected_users(&self) -> Result<Vec<i32>, AppError> {
+        Ok(self
+            .apple_token_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|token| token.user_id)
+            .collect())
+    }
+
+    async fn disconnect(&self, user: &UserModel) -> Result<(), AppError> {
+        AppleMusicService::disconnect(self, user).await
+    }
+}
+
+/// Songs of a library playlist may only carry a personal `i.` library id, which can't be used to
+/// build another playlist. Only catalog ids are worth remembering.
+fn catalog_song_id(id: &str) -> Option<&str> {
+    (!id.starts_with("i.")).then_some(id)
+}
+
+/// Builds a music from the metadata of a library song, completed by its catalog version when
+/// there is one
+fn library_music_request(
+    title: String,
+    artist: String,
+    album: String,
+    genre: Option<String>,
+    release_date: NaiveDate,
+    catalog: Option<AppleCatalogSong>,
+) -> CreateMusicRequest {
+    let catalog_artists = catalog
+        .as_ref()
+        .map(AppleCatalogSong::artist_names)
+        .unwrap_or_default();
+    // Library songs only carry a joined credit such as "SDM & Werenoi"
+    let artists = if catalog_artists.is_empty() {
+        ArtistCredit::parse(&artist)
+    } else {
+        ArtistCredit::from_names(catalog_artists)
+    };
+    let catalog = catalog.map(|catalog| catalog.attributes);
+    CreateMusicRequest {
+        title,
+        artist,
+        album,
+        description: None,
+        genre,
+        release_date,
+        isrc: catalog.as_ref().and_then(|catalog| catalog.isrc.clone()),
+        duration_ms: catalog
+            .as_ref()
+            .and_then(|catalog| catalog.duration_in_millis),
+        explicit: catalog
+            .as_ref()
+            .and_then(|catalog| catalog.content_rating.as_deref())
+            .map(|rating| rating == "explicit"),
+        artwork_url: catalog
+            .as_ref()
+            .and_then(|catalog| catalog.artwork.as_ref())
+            .map(|artwork| artwork.url_square(600)),
+        disc_number: catalog.as_ref().and_then(|catalog| catalog.disc_number),
+        track_number: catalog.as_ref().and_then(|catalog| catalog.track_number),
+        preview_url: catalog
+            .and_then(|catalog| catalog.previews.into_iter().next())
+            .map(|preview| preview.url),
+        artists,
+    }
+}
@@ -11,3 +11,333 @@ pub struct AppleUrlResponse {
 pub struct GetDeveloperToken {
     pub developer_token: String,
 }
+
+/// Envelope of the Apple Music API resources, `data` holds the requested objects
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppleResponse<T> {
+    pub data: Vec<T>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AppleStorefront {
+    pub id: String,
+}
+
+/// Catalog song as returned by `GET /v1/catalog/{storefront}/songs` and the catalog search
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppleCatalogSong {
+    pub id: String,
+    pub attributes: AppleCatalogSongAttributes,
+    /// Only filled when the request asked for `include=artists`
+    #[serde(default)]
+    pub relationships: Option<AppleCatalogSongRelationships>,
+}
+
+impl AppleCatalogSong {
+    /// Names of every credited artist, empty when the artists weren't included
+    pub fn artist_names(&self) -> Vec<String> {
+        self.relationships
+            .iter()
+            .flat_map(|relationships| relationships.artists.iter())
+            .flat_map(|artists| artists.data.iter())
+            .filter_map(|artist| artist.attributes.as_ref())
+            .map(|attributes| attributes.name.clone())
+            .collect()
+    }
+}
+
+/// Body of `GET /v1/catalog/{storefront}/search`, a kind without results is left out
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppleSearchResponse {
+    pub results: AppleSearchResults,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppleSearchResults {
+    pub songs: Option<AppleResponse<AppleCatalogSong>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppleCatalogSongRelationships {
+    pub artists: Option<AppleResponse<AppleCatalogArtist>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppleCatalogArtist {
+    pub id: String,
+    pub attributes: Option<AppleCatalogArtistAttributes>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppleCatalogArtistAttributes {
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AppleCatalogSongAttributes {
+    pub name: String,
+    pub artist_name: String,
+    pub album_name: Option<String>,
+    pub isrc: Option<String>,
+    pub duration_in_millis: Option<i32>,
+    /// `explicit` or `clean`, left out for tracks without a rating
+    pub content_rating: Option<String>,
+    pub artwork: Option<AppleArtwork>,
+    pub disc_number: Option<i32>,
+    pub track_number: Option<i32>,
+    #[serde(default)]
+    pub previews: Vec<ApplePreview>,
+}
+
+/// Artwork url template, `{w}` and `{h}` are replaced by the wanted size
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppleArtwork {
+    pub url: String,
+}
+
+impl AppleArtwork {
+    pub fn url_square(&self, size: u32) -> String {
+        self.url
+            .replace("{w}", &size.to_string())
+            .replace("{h}", &size.to_string())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ApplePreview {
+    pub url: String,
+}
+
+/// Playlist of the user's library as returned by `GET /v1/me/library/playlists`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppleLibraryPlaylist {
+    pub id: String,
+    pub attributes: AppleLibraryPlaylistAttributes,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AppleLibraryPlaylistAttributes {
+    pub name: String,
+    /// False for catalog playlists the user added to their library
+    pub can_edit: Option<bool>,
+    pub artwork: Option<AppleArtwork>,
+}
+
+/// Library song as returned by `GET /v1/me/library/songs` or
+/// `GET /v1/me/library/playlists/{id}/tracks`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AppleLibrarySong {
+    /// Personal `i.` library id
+    pub id: String,
+    pub attributes: Option<AppleLibrarySongAttributes>,
+}
+
+impl AppleLibrarySong {
+    /// Catalog id of the song, missing for songs uploaded by the user
+    pub fn catalog_id(&self) -> Option<&str> {
+        self.attributes
+            .as_ref()
+            .and_then(|attributes| attributes.play_params.as_ref())
+            .and_then(|play_params| play_params.catalog_id.as_deref())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AppleLibrarySongAttributes {
+    pub name: Option<String>,
+    pub artist_name: Option<String>,
+    pub album_name: Option<String>,
+    #[serde(default)]
+    pub genre_names: Vec<String>,
+    /// `YYYY-MM-DD`, left out when the release date is unknown
+    pub release_date: Option<String>,
+    pub play_params: Option<ApplePlayParams>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplePlayParams {
+    pub catalog_id: Option<String>,
+}
+
+/// Reference to a resource in a request body, such as the songs added to a playlist
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct AppleResourceRef {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+impl AppleResourceRef {
+    pub fn song(id: &str) -> Self {
+        AppleResourceRef {
+            id: id.to_string(),
+            kind: "songs".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apple_catalog_songs_deserialization() {
+        let json = r#"{
+            "data": [{
+                "id": "1440818839",
+                "type": "songs",
+                "attributes": {
+                    "name": "Harder, Better, Faster, Stronger",
+                    "artistName": "Daft Punk",
+                    "isrc": "GBDUW0000059",
+                    "durationInMillis": 224693
+                }
+            }]
+        }"#;
+        let response: AppleResponse<AppleCatalogSong> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.data.len(), 1);
+        assert_eq!(response.data[0].attributes.artist_name, "Daft Punk");
+        assert_eq!(
+            response.data[0].attributes.isrc.as_deref(),
+            Some("GBDUW0000059")
+        );
+        assert_eq!(response.data[0].attributes.duration_in_millis, Some(224693));
+        assert!(response.data[0].attributes.previews.is_empty());
+        assert!(response.data[0].artist_names().is_empty());
+    }
+
+    #[test]
+    fn test_apple_catalog_song_included_artists() {
+        let json = r#"{
+            "id": "1725834519",
+            "type": "songs",
+            "attributes": {"name": "Tout va bien", "artistName": "SDM & Werenoi"},
+            "relationships": {
+                "artists": {
+                    "data": [
+                        {"id": "1", "type": "artists", "attributes": {"name": "SDM"}},
+                        {"id": "2", "type": "artists", "attributes": {"name": "Werenoi"}}
+                    ]
+                }
+            }
+        }"#;
+        let song: AppleCatalogSong = serde_json::from_str(json).unwrap();
+
+        assert_eq!(song.artist_names(), vec!["SDM", "Werenoi"]);
+    }
+
+    #[test]
+    fn test_apple_search_songs_deserialization() {
+        let json = r#"{
+            "results": {
+                "songs": {
+                    "data": [{
+                        "id": "1440818839",
+                        "type": "songs",
+                        "attributes": {
+                            "name": "Harder, Better, Faster, Stronger",
+                            "artistName": "Daft Punk",
+                            "albumName": "Discovery",
+                            "durationInMillis": 224693
+                        }
+                    }]
+                }
+            }
+        }"#;
+        let response: AppleSearchResponse = serde_json::from_str(json).unwrap();
+        let songs = response.results.songs.unwrap().data;
+
+        assert_eq!(songs[0].attributes.album_name.as_deref(), Some("Discovery"));
+        assert_eq!(songs[0].attributes.duration_in_millis, Some(224693));
+
+        let empty: AppleSearchResponse = serde_json::from_str(r#"{"results": {}}"#).unwrap();
+        assert!(empty.results.songs.is_none());
+    }
+
+    #[test]
+    fn test_apple_artwork_url_square() {
+        let artwork = AppleArtwork {
+            url: "https://is1-ssl.mzstatic.com/image/thumb/Music/{w}x{h}bb.jpg".to_string(),
+        };
+
+        assert_eq!(
+            artwork.url_square(600),
+            "https://is1-ssl.mzstatic.com/image/thumb/Music/600x600bb.jpg"
+        );
+    }
+
+    #[test]
+    fn test_apple_library_song_catalog_id() {
+        let json = r#"{
+            "data": [
+                {
+                    "id": "i.vMX9JbYsKPoK3g",
+                    "type": "library-songs",
+                    "attributes": {
+                        "name": "Song",
+                        "playParams": {"id": "i.vMX9JbYsKPoK3g", "kind": "song", "catalogId": "1440818839"}
+                    }
+                },
+                {"id": "i.uploaded", "type": "library-songs", "attributes": {"playParams": {"id": "i.uploaded"}}}
+            ]
+        }"#;
+        let response: AppleResponse<AppleLibrarySong> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.data[0].catalog_id(), Some("1440818839"));
+        assert_eq!(response.data[1].catalog_id(), None);
+    }
+
+    #[test]
+    fn test_apple_library_song_attributes() {
+        let json = r#"{
+            "id": "i.vMX9JbYsKPoK3g",
+            "type": "library-songs",
+            "attributes": {
+                "name": "One More Time",
+                "artistName": "Daft Punk",
+                "albumName": "Discovery",
+                "genreNames": ["Electronic"],
+                "releaseDate": "2000-11-13"
+            }
+        }"#;
+        let song: AppleLibrarySong = serde_json::from_str(json).unwrap();
+        let attributes = song.attributes.unwrap();
+
+        assert_eq!(attributes.album_name.as_deref(), Some("Discovery"));
+        assert_eq!(attributes.genre_names, vec!["Electronic"]);
+        assert_eq!(attributes.release_date.as_deref(), Some("2000-11-13"));
+        assert!(attributes.play_params.is_none());
+    }
+
+    #[test]
+    fn test_apple_library_playlists_deserialization() {
+        let json = r#"{
+            "data": [
+                {"id": "p.mine", "type": "library-playlists", "attributes": {"name": "Mine", "canEdit": true}},
+                {"id": "p.editorial", "type": "library-playlists", "attributes": {"name": "Today's Hits", "canEdit": false, "artwork": {"url": "https://is1-ssl.mzstatic.com/{w}x{h}bb.jpg"}}}
+            ]
+        }"#;
+        let response: AppleResponse<AppleLibraryPlaylist> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.data[0].attributes.can_edit, Some(true));
+        assert!(response.data[0].attributes.artwork.is_none());
+        assert_eq!(response.data[1].attributes.can_edit, Some(false));
+    }
+
+    #[test]
+    fn test_apple_resource_ref_serialization() {
+        let body = AppleResponse {
+            data: vec![AppleResourceRef::song("1440818839")],
+        };
+
+        assert_eq!(
+            serde_json::to_string(&body).unwrap(),
+            r#"{"data":[{"id":"1440818839","type":"songs"}]}"#
+        );
+    }
+}
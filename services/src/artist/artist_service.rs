@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use sea_orm::{ActiveValue::Set, DatabaseConnection, DbErr};
+use swaptun_models::{
+    AlbumActiveModel, AlbumModel, ArtistActiveModel, ArtistModel, ArtistRole,
+    MusicArtistActiveModel, MusicModel,
+};
+use swaptun_repositories::{AlbumRepository, ArtistRepository, MusicArtistRepository};
+
+use crate::error::AppError;
+use crate::{ArtistCredit, ArtistResponse};
+
+#[derive(Clone)]
+pub struct ArtistService {
+    artist_repository: ArtistRepository,
+    album_repository: AlbumRepository,
+    music_artist_repository: MusicArtistRepository,
+}
+
+impl ArtistService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            artist_repository: ArtistRepository::new(db.clone()),
+            album_repository: AlbumRepository::new(db.clone()),
+            music_artist_repository: MusicArtistRepository::new(db),
+        }
+    }
+
+    pub async fn get_artist(&self, id: i32) -> Result<ArtistResponse, AppError> {
+        let artist = self
+            .artist_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Artist with id {} not found", id)))?;
+        let albums = self.album_repository.find_by_artist(artist.id).await?;
+        let musics = self.artist_repository.find_musics(&artist).await?;
+
+        Ok(ArtistResponse {
+            artist,
+            albums,
+            musics,
+        })
+    }
+
+    /// Artists credited on the music, in credit order
+    pub async fn find_by_music(&self, music: &MusicModel) -> Result<Vec<ArtistModel>, DbErr> {
+        self.artist_repository.find_by_music(music).await
+    }
+
+    /// Concurrent imports may create the same artist, the insert skips an existing one and
+    /// the row is read back either way
+    pub async fn find_or_create(&self, name: &str) -> Result<ArtistModel, DbErr> {
+        if let Some(artist) = self.artist_repository.find_by_name(name).await? {
+            return Ok(artist);
+        }
+        self.artist_repository
+            .create_if_missing(ArtistActiveModel {
+                name: Set(name.to_string()),
+                ..Default::default()
+            })
+            .await?;
+        self.artist_repository
+            .find_by_name(name)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("Artist {} not found", name)))
+    }
+
+    pub async fn find_or_create_album(
+        &self,
+        title: &str,
+        artist_id: Option<i32>,
+    ) -> Result<AlbumModel, DbErr> {
+        if let Some(album) = self
+            .album_repository
+            .find_by_title_and_artist(title, artist_id)
+            .await?
+        {
+            return Ok(album);
+        }
+        self.album_repository
+            .create_if_missing(AlbumActiveModel {
+                title: Set(title.to_string()),
+                artist_id: Set(artist_id),
+                ..Default::default()
+            })
+            .await?;
+        self.album_repository
+            .find_by_title_and_artist(title, artist_id)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("Album {} not found", title)))
+    }
+
+    /// Album titled `title` by the first of `artists`, `None` for musics without an album
+    pub async fn resolve_album(
+        &self,
+        title: &str,
+        artists: &[(ArtistModel, ArtistRole)],
+    ) -> Result<Option<AlbumModel>, DbErr> {
+        if title.trim().is_empty() {
+            return Ok(None);
+        }
+        let artist_id = artists.first().map(|(artist, _)| artist.id);
+        self.find_or_create_album(title, artist_id).await.map(Some)
+    }
+
+    /// Finds or creates the credited artists. An artist credited twice keeps its first role.
+    pub async fn resolve_credits(
+        &self,
+        credits: &[ArtistCredit],
+    ) -> Result<Vec<(ArtistModel, ArtistRole)>, DbErr> {
+        let mut artists: Vec<(ArtistModel, ArtistRole)> = Vec::with_capacity(credits.len());
+        for credit in credits {
+            let artist = self.find_or_create(&credit.name).await?;
+            if artists.iter().all(|(known, _)| known.id != artist.id) {
+                artists.push((artist, credit.role.clone()));
+            }
+        }
+        Ok(artists)
+    }
+
+    /// Replaces the credits of the music, left untouched when they didn't change
+    pub async fn set_music_artists(
+        &self,
+        music: &MusicModel,
+        artists: &[(ArtistModel, ArtistRole)],
+    ) -> Result<(), DbErr> {
+        let existing = self
+            .music_artist_repository
+            .find_by_music_id(music.id)
+            .await?;
+        let unchanged = existing.len() == artists.len()
+            && existing
+                .iter()
+                .zip(artists)
+                .all(|(link, (artist, role))| link.artist_id == artist.id && link.role == *role);
+        if unchanged {
+            return Ok(());
+        }
+
+        self.music_artist_repository
+            .delete_by_music_id(music.id)
+            .await?;
+        for (position, (artist, role)) in artists.iter().enumerate() {
+            self.music_artist_repository
+                .create(MusicArtistActiveModel {
+                    music_id: Set(music.id),
+                    artist_id: Set(artist.id),
+                    role: Set(role.clone()),
+                    position: Set(position as i32),
+                    ..Default::default()
+                })
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use swaptun_models::{AlbumModel, ArtistModel, ArtistRole, MusicModel};
+
+/// Markers introducing the featured artists of a credit, longer forms first
+const FEATURING_MARKERS: [&str; 5] = [" featuring ", " feat. ", " feat ", " ft. ", " ft "];
+
+/// An artist credited on a music, as sent by a platform
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ArtistCredit {
+    pub name: String,
+    pub role: ArtistRole,
+}
+
+impl ArtistCredit {
+    /// Credits of a platform listing every artist separately, the first one being the main artist
+    pub fn from_names<I, S>(names: I) -> Vec<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        names
+            .into_iter()
+            .map(Into::into)
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .enumerate()
+            .map(|(index, name)| ArtistCredit {
+                name,
+                role: if index == 0 {
+                    ArtistRole::Primary
+                } else {
+                    ArtistRole::Featured
+                },
+            })
+            .collect()
+    }
+
+    /// Splits a single credit string such as "SDM feat. Werenoi & Ninho".
+    /// The main part is kept whole since "&" and "," also appear in band names
+    /// like "Earth, Wind & Fire", only the featured part is split further.
+    pub fn parse(credit: &str) -> Vec<Self> {
+        let lowercase = credit.to_ascii_lowercase();
+        let featuring = FEATURING_MARKERS
+            .iter()
+            .filter_map(|marker| lowercase.find(marker).map(|index| (index, marker.len())))
+            .min_by_key(|(index, _)| *index);
+
+        let (main, featured) = match featuring {
+            Some((index, length)) => (&credit[..index], &credit[index + length..]),
+            None => (credit, ""),
+        };
+
+        let mut credits = Self::from_names([main]);
+        credits.extend(
+            featured
+                .split(|c| c == ',' || c == '&')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| ArtistCredit {
+                    name: name.to_string(),
+                    role: ArtistRole::Featured,
+                }),
+        );
+        credits
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ArtistResponse {
+    pub artist: ArtistModel,
+    pub albums: Vec<AlbumModel>,
+    pub musics: Vec<MusicModel>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_names_marks_the_first_artist_as_primary() {
+        let credits = ArtistCredit::from_names(["SDM", "Werenoi", " "]);
+
+        assert_eq!(credits.len(), 2);
+        assert_eq!(credits[0].name, "SDM");
+        assert_eq!(credits[0].role, ArtistRole::Primary);
+        assert_eq!(credits[1].name, "Werenoi");
+        assert_eq!(credits[1].role, ArtistRole::Featured);
+    }
+
+    #[test]
+    fn test_parse_splits_featured_artists() {
+        let credits = ArtistCredit::parse("SDM Feat. Werenoi & Ninho");
+
+        let names: Vec<&str> = credits.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["SDM", "Werenoi", "Ninho"]);
+        assert_eq!(credits[0].role, ArtistRole::Primary);
+        assert_eq!(credits[2].role, ArtistRole::Featured);
+    }
+
+    #[test]
+    fn test_parse_keeps_band_names_whole() {
+        let credits = ArtistCredit::parse("Earth, Wind & Fire");
+
+        assert_eq!(credits.len(), 1);
+        assert_eq!(credits[0].name, "Earth, Wind & Fire");
+        assert_eq!(credits[0].role, ArtistRole::Primary);
+    }
+
+    #[test]
+    fn test_artist_credit_deserialization() {
+        let json = r#"{"name":"Werenoi","role":"Featured"}"#;
+        let credit: ArtistCredit = serde_json::from_str(json).unwrap();
+
+        assert_eq!(credit.role, ArtistRole::Featured);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod artist_request;
+pub use artist_request::*;
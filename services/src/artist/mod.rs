@@ -0,0 +1,6 @@
+pub mod dto;
+pub use dto::*;
+#[cfg(feature = "full")]
+pub mod artist_service;
+#[cfg(feature = "full")]
+pub use artist_service::*;
@@ -1,5 +1,5 @@
 use actix_web::error::ErrorUnauthorized;
-use actix_web::{dev, Error, HttpMessage};
+use actix_web::{dev, web, Error, HttpMessage};
 use chrono::{Duration, Utc};
 use futures::future::{ready, Ready};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
@@ -43,6 +43,9 @@ pub struct Claims {
     pub user_id: i32,
     pub username: String,
     pub role: String,
+    /// Restricts what the token may be used for, `None` for a regular session token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
 }
 
 impl Claims {
@@ -57,6 +60,15 @@ impl Claims {
     pub fn is_user_or_above(&self) -> bool {
         matches!(self.role.to_lowercase().as_str(), "admin" | "user")
     }
+
+    pub fn is_stream_token(&self) -> bool {
+        self.scope.as_deref() == Some(STREAM_SCOPE)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.exp
+            .is_some_and(|exp| exp < Utc::now().timestamp() as usize)
+    }
 }
 
 static JWT_SECRET: Lazy<String> = Lazy::new(|| {
@@ -71,6 +83,7 @@ pub fn generate_token(user: &Model) -> Result<String, AppError> {
         username: user.username.clone(),
         role: user.role.clone(),
         exp: None,
+        scope: None,
     };
 
     encode(
@@ -96,6 +109,7 @@ pub fn generate_token_expiration(user: &Model, duration: Duration) -> Result<Str
         user_id: user.id,
         username: user.username.clone(),
         role: user.role.clone(),
+        scope: None,
     };
 
     encode(
@@ -109,6 +123,41 @@ pub fn generate_token_expiration(user: &Model, duration: Duration) -> Result<Str
     })
 }
 
+/// Lifetime of the tokens passed in the query of the event stream, which can not send headers
+const STREAM_TOKEN_DURATION_SECS: i64 = 60;
+
+/// Path of the event stream, authenticated by a `token` query parameter
+const STREAM_PATH: &str = "/api/events";
+
+/// Scope of the stream tokens, which are refused anywhere but in the event stream URL
+const STREAM_SCOPE: &str = "stream";
+
+/// Short-lived copy of the claims, the only kind of token accepted in the event stream URL
+pub fn generate_stream_token(claims: &Claims) -> Result<String, AppError> {
+    let now = Utc::now();
+    let claims = Claims {
+        exp: Some((now + Duration::seconds(STREAM_TOKEN_DURATION_SECS)).timestamp() as usize),
+        iat: now.timestamp() as usize,
+        scope: Some(STREAM_SCOPE.to_string()),
+        ..claims.clone()
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(JWT_SECRET.as_bytes()),
+    )
+    .map_err(|e| {
+        log::error!("Error generating token: {}", e);
+        AppError::InternalServerError
+    })
+}
+
+#[derive(Deserialize)]
+struct StreamTokenQuery {
+    token: String,
+}
+
 pub fn validate_token(token: &str) -> Result<TokenData<Claims>, AppError> {
     let mut validation = Validation::default();
     validation.validate_exp = false; // Désactive la vérification d'expiration
@@ -123,6 +172,11 @@ pub fn validate_token(token: &str) -> Result<TokenData<Claims>, AppError> {
         AppError::Unauthorized("Invalid token {}".into())
     })?;
 
+    // Session tokens have no expiration, but the ones that do must still be honoured
+    if token_data.claims.is_expired() {
+        return Err(AppError::Unauthorized("Token has expired".into()));
+    }
+
     if !UserRole::is_valid_role(&token_data.claims.role) {
         log::error!("Token contains invalid role: {}", token_data.claims.role);
         return Err(AppError::Unauthorized("Invalid role in token".into()));
@@ -200,6 +254,21 @@ where
         }
 
         let auth_header = req.headers().get("Authorization");
+        if auth_header.is_none() && req.path() == STREAM_PATH {
+            // EventSource can not set headers, the stream takes a short-lived token instead
+            let token_data = match web::Query::<StreamTokenQuery>::from_query(req.query_string())
+                .map_err(|_| ())
+                .and_then(|query| validate_token_exp(&query.token).map_err(|_| ()))
+                .and_then(|data| data.claims.is_stream_token().then_some(data).ok_or(()))
+            {
+                Ok(data) => data,
+                Err(_) => {
+                    return Box::pin(async { Err(ErrorUnauthorized("Invalid or expired token")) });
+                }
+            };
+            req.extensions_mut().insert(token_data.claims);
+            return Box::pin(async move { service.call(req).await });
+        }
         if auth_header.is_none() {
             return Box::pin(async { Err(ErrorUnauthorized("Authorization header not found")) });
         }
@@ -222,8 +291,9 @@ where
         let token = auth_str.trim_start_matches("Bearer ").trim();
 
         let token_data = match validate_token(token) {
-            Ok(data) => data,
-            Err(_) => {
+            // Stream tokens travel in URLs and logs, they must not open the rest of the API
+            Ok(data) if !data.claims.is_stream_token() => data,
+            _ => {
                 return Box::pin(async { Err(ErrorUnauthorized("Invalid or expired token")) });
             }
         };
@@ -3,7 +3,7 @@ pub mod jwt;
 #[cfg(feature = "full")]
 pub mod password;
 #[cfg(feature = "full")]
-pub use jwt::{Claims, JwtMiddleware, generate_token, validate_token};
+pub use jwt::{Claims, JwtMiddleware, generate_stream_token, generate_token, validate_token};
 #[cfg(feature = "full")]
 pub use password::{hash_password, verify_password};
 
@@ -1,25 +1,399 @@
+use std::env::var;
 use std::sync::Arc;
 
 use crate::error::AppError;
 use crate::playlist::playlist_service::PlaylistService;
-use crate::{AddTokenRequest, DeleteTokenRequest, UpdateTokenRequest};
+use crate::{
+    find_override, import_each, music::dto::CreateMusicRequest, ArtistCredit,
+    CreatePlaylistRequest, DeezerAccessTokenResponse, DeezerCreatedPlaylist, DeezerPage,
+    DeezerPlaylist, DeezerTrack, DeezerUrlResponse, DeezerUser, EventHub, ImportSelection,
+    MusicProvider, MusicService, NotificationService, PlaylistDiff, PlaylistExport, RemotePlaylist,
+    ScoredMatch, TrackCandidate, TrackMatchEntry, TrackMatchOverrideService, TrackMatcher,
+    TrackQuery, UserEvent,
+};
+use crate::{
+    AddTokenRequest, DeleteTokenRequest, JobPayload, JobProgress, JobService, UpdateTokenRequest,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
 use log::{error, info};
+use reqwest::{Method, Url};
 use sea_orm::IntoActiveModel;
 use sea_orm::{ActiveValue::Set, DatabaseConnection};
-use swaptun_models::{playlist::PlaylistOrigin, DeezerTokenActiveModel, UserModel};
+use serde::de::DeserializeOwned;
+use swaptun_models::{playlist::PlaylistOrigin, DeezerTokenActiveModel, MusicModel, UserModel};
 use swaptun_repositories::deezer_token_repository::DeezerTokenRepository;
 
+const DEEZER_API_URL: &str = "https://api.deezer.com";
+const DEEZER_CONNECT_URL: &str = "https://connect.deezer.com/oauth";
+
+#[derive(Clone)]
 pub struct DeezerService {
     deezer_token_repository: DeezerTokenRepository,
     playlist_service: PlaylistService,
+    music_service: MusicService,
+    http_client: reqwest::Client,
+    matcher: TrackMatcher,
+    track_match_override_service: TrackMatchOverrideService,
+    job_service: JobService,
+    db: Arc<DatabaseConnection>,
 }
 
 impl DeezerService {
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
         Self {
             deezer_token_repository: DeezerTokenRepository::new(db.clone()),
-            playlist_service: PlaylistService::new(db),
+            playlist_service: PlaylistService::new(db.clone()),
+            music_service: MusicService::new(db.clone()),
+            http_client: reqwest::Client::new(),
+            matcher: TrackMatcher::from_env(),
+            track_match_override_service: TrackMatchOverrideService::new(db.clone()),
+            job_service: JobService::new(db.clone()),
+            db,
+        }
+    }
+
+    pub fn get_app_id(&self) -> Result<String, AppError> {
+        var("DEEZER_APP_ID").map_err(|e| {
+            error!("Failed to get DEEZER_APP_ID from environment: {}", e);
+            AppError::InternalServerError
+        })
+    }
+
+    pub fn get_app_secret(&self) -> Result<String, AppError> {
+        var("DEEZER_APP_SECRET").map_err(|e| {
+            error!("Failed to get DEEZER_APP_SECRET from environment: {}", e);
+            AppError::InternalServerError
+        })
+    }
+
+    pub fn get_redirect_uri(&self) -> String {
+        "https://swaptun.com/open/deezer".to_string()
+    }
+
+    pub async fn get_authorization_url(&self) -> Result<DeezerUrlResponse, AppError> {
+        let url = Url::parse_with_params(
+            &format!("{}/auth.php", DEEZER_CONNECT_URL),
+            &[
+                ("app_id", self.get_app_id()?),
+                ("redirect_uri", self.get_redirect_uri()),
+                (
+                    "perms",
+                    "basic_access,offline_access,manage_library".to_string(),
+                ),
+            ],
+        )?;
+
+        Ok(DeezerUrlResponse {
+            url: url.to_string(),
+        })
+    }
+
+    /// Exchanges the authorization code received on the redirect uri for an access token,
+    /// stores it and starts the import of the user's playlists
+    pub async fn auth_callback(
+        &self,
+        user: &UserModel,
+        req: AddTokenRequest,
+    ) -> Result<(), AppError> {
+        let url = Url::parse_with_params(
+            &format!("{}/access_token.php", DEEZER_CONNECT_URL),
+            &[
+                ("app_id", self.get_app_id()?),
+                ("secret", self.get_app_secret()?),
+                ("code", req.token),
+                ("output", "json".to_string()),
+            ],
+        )?;
+
+        let body = self
+            .http_client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Failed to exchange Deezer code for token: {:?}", e);
+                AppError::InternalServerError
+            })?
+            .text()
+            .await
+            .map_err(|e| {
+                error!("Failed to read Deezer token response: {:?}", e);
+                AppError::InternalServerError
+            })?;
+
+        // Deezer answers with a plain text body such as "wrong code" when the exchange fails
+        let token: DeezerAccessTokenResponse = serde_json::from_str(&body).map_err(|_| {
+            error!("Unexpected Deezer token response: {}", body);
+            AppError::Unauthorized("Invalid Deezer authorization code".to_string())
+        })?;
+
+        self.add_token(
+            AddTokenRequest {
+                token: token.access_token,
+            },
+            user.clone(),
+        )
+        .await?;
+
+        info!("Successfully saved Deezer token for user {}", user.id);
+
+        self.job_service
+            .enqueue(
+                user.id,
+                JobPayload::ImportPlaylists {
+                    origin: PlaylistOrigin::Deezer,
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Performs a GET on the Deezer API and decodes the response.
+    /// Deezer reports errors with a 200 status and an `error` object in the body.
+    async fn api_get<T: DeserializeOwned>(&self, url: Url) -> Result<T, AppError> {
+        let value: serde_json::Value = self
+            .http_client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Deezer request to {} failed: {:?}", url.path(), e);
+                AppError::InternalServerError
+            })?
+            .json()
+            .await
+            .map_err(|e| {
+                error!(
+                    "Failed to decode Deezer response for {}: {:?}",
+                    url.path(),
+                    e
+                );
+                AppError::InternalServerError
+            })?;
+
+        if let Some(api_error) = value.get("error") {
+            error!("Deezer API error on {}: {}", url.path(), api_error);
+            return match api_error.get("type").and_then(|t| t.as_str()) {
+                Some("OAuthException") => Err(AppError::Unauthorized(
+                    "Deezer token is invalid or expired".to_string(),
+                )),
+                Some("DataException") => {
+                    Err(AppError::NotFound("Deezer resource not found".to_string()))
+                }
+                _ => Err(AppError::InternalServerError),
+            };
+        }
+
+        serde_json::from_value(value).map_err(|e| {
+            error!("Unexpected Deezer payload for {}: {:?}", url.path(), e);
+            AppError::InternalServerError
+        })
+    }
+
+    /// Walks every page of a paginated Deezer endpoint
+    async fn api_get_all<T: DeserializeOwned>(&self, url: Url) -> Result<Vec<T>, AppError> {
+        let mut items = Vec::new();
+        let mut next = Some(url);
+        while let Some(url) = next.take() {
+            let page: DeezerPage<T> = self.api_get(url).await?;
+            items.extend(page.data);
+            next = match page.next {
+                Some(next_url) => Some(Url::parse(&next_url)?),
+                None => None,
+            };
+        }
+        Ok(items)
+    }
+
+    /// Performs a POST on the Deezer API, parameters are sent in the query string
+    async fn api_post<T: DeserializeOwned>(&self, url: Url) -> Result<T, AppError> {
+        self.api_send(Method::POST, url).await
+    }
+
+    /// Performs a DELETE on the Deezer API, parameters are sent in the query string
+    async fn api_delete<T: DeserializeOwned>(&self, url: Url) -> Result<T, AppError> {
+        self.api_send(Method::DELETE, url).await
+    }
+
+    async fn api_send<T: DeserializeOwned>(&self, method: Method, url: Url) -> Result<T, AppError> {
+        let value: serde_json::Value = self
+            .http_client
+            .request(method, url.clone())
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Deezer request to {} failed: {:?}", url.path(), e);
+                AppError::InternalServerError
+            })?
+            .json()
+            .await
+            .map_err(|e| {
+                error!(
+                    "Failed to decode Deezer response for {}: {:?}",
+                    url.path(),
+                    e
+                );
+                AppError::InternalServerError
+            })?;
+
+        if let Some(api_error) = value.get("error") {
+            error!("Deezer API error on {}: {}", url.path(), api_error);
+            return Err(AppError::InternalServerError);
+        }
+
+        serde_json::from_value(value).map_err(|e| {
+            error!("Unexpected Deezer payload for {}: {:?}", url.path(), e);
+            AppError::InternalServerError
+        })
+    }
+
+    fn api_url(&self, path: &str, access_token: &str) -> Result<Url, AppError> {
+        Ok(Url::parse_with_params(
+            &format!("{}{}", DEEZER_API_URL, path),
+            &[("access_token", access_token)],
+        )?)
+    }
+
+    pub async fn get_user_playlists(
+        &self,
+        user: &UserModel,
+        selection: &ImportSelection,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        let access_token = self.get_token(user.clone()).await?.token;
+        let mut playlists: Vec<DeezerPlaylist> = self
+            .api_get_all(self.api_url("/user/me/playlists", &access_token)?)
+            .await?;
+        playlists.retain(|playlist| selection.includes(&playlist.id.to_string()));
+
+        info!("Found {} Deezer playlists", playlists.len());
+
+        let access_token = &access_token;
+        import_each(
+            playlists,
+            |playlist| playlist.title.clone(),
+            progress,
+            |playlist| async move {
+                self.import_playlist(&playlist, user, access_token, progress)
+                    .await
+            },
+        )
+        .await
+    }
+
+    /// Lists the playlists of the user's library without importing them, the loved tracks
+    /// are one of them
+    pub async fn list_playlists(&self, user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError> {
+        let access_token = self.get_token(user.clone()).await?.token;
+        let deezer_user: DeezerUser = self
+            .api_get(self.api_url("/user/me", &access_token)?)
+            .await?;
+        let playlists: Vec<DeezerPlaylist> = self
+            .api_get_all(self.api_url("/user/me/playlists", &access_token)?)
+            .await?;
+
+        Ok(playlists
+            .into_iter()
+            .map(|playlist| RemotePlaylist {
+                id: playlist.id.to_string(),
+                name: playlist.title,
+                track_count: playlist.nb_tracks,
+                owned: playlist.creator.map(|creator| creator.id == deezer_user.id),
+                image_url: playlist.picture_xl,
+                selected: false,
+            })
+            .collect())
+    }
+
+    pub async fn import_playlist(
+        &self,
+        playlist: &DeezerPlaylist,
+        user: &UserModel,
+        access_token: &str,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        let tracks: Vec<DeezerTrack> = self
+            .api_get_all(self.api_url(&format!("/playlist/{}/tracks", playlist.id), access_token)?)
+            .await?;
+        progress.start_playlist(&playlist.title, tracks.len()).await;
+
+        let request = CreatePlaylistRequest {
+            name: playlist.title.clone(),
+            origin: PlaylistOrigin::Deezer,
+            description: playlist.description.clone().filter(|d| !d.is_empty()),
+            origin_id: playlist.id.to_string(),
+            image_url: playlist.picture_xl.clone(),
+        };
+        let playlist_model = self.playlist_service.create_or_get(request, user).await?;
+
+        let mut music_ids = Vec::new();
+
+        for track in tracks {
+            // Track listings don't carry the release date nor the contributors, featured artists
+            // are read from the credit and the release date is left to the default value
+            let artists = ArtistCredit::parse(&track.artist.name);
+            let create_music_request = CreateMusicRequest {
+                title: track.title,
+                artist: track.artist.name,
+                album: track.album.title,
+                release_date: NaiveDate::default(),
+                genre: None,
+                description: None,
+                isrc: track.isrc,
+                duration_ms: track.duration.map(|seconds| seconds as i32 * 1000),
+                explicit: track.explicit_lyrics,
+                artwork_url: track.album.cover_xl,
+                disc_number: None,
+                track_number: None,
+                preview_url: track.preview,
+                artists,
+            };
+            let music = self.music_service.create(create_music_request).await?;
+            self.music_service
+                .save_external_id(music.id, PlaylistOrigin::Deezer, &track.id.to_string())
+                .await?;
+            music_ids.push(music.id);
+            progress.track_done().await;
+        }
+
+        self.playlist_service
+            .sync_musics(&playlist_model, &music_ids)
+            .await?;
+        progress.finish_playlist().await;
+
+        EventHub::global().publish(
+            user.id,
+            UserEvent::PlaylistImported {
+                playlist_id: playlist_model.id,
+                origin: PlaylistOrigin::Deezer,
+            },
+        );
+
+        // Send silent notification with only playlist ID (lightweight)
+        let notification_data = serde_json::json!({
+            "type": "playlist_sync",
+            "playlist_id": playlist_model.id.to_string(),
+            "origin": "Deezer",
+        });
+
+        // Send silent notification (ignore errors to not block import)
+        if let Ok(notification_service) = NotificationService::new(self.db.clone()).await {
+            if let Err(e) = notification_service
+                .send_silent_data_to_user(user.id, notification_data)
+                .await
+            {
+                error!(
+                    "Failed to send silent notification for playlist {}: {:?}",
+                    playlist.title, e
+                );
+            } else {
+                info!("Silent notification sent for playlist: {}", playlist.title);
+            }
         }
+
+        Ok(())
     }
 
     pub async fn add_token(
@@ -108,6 +482,269 @@ impl DeezerService {
             .map_err(AppError::from)
     }
 
+    /// Searches Deezer for a track of our database and returns the best matching result
+    pub async fn search_deezer_track(
+        &self,
+        track: &MusicModel,
+        access_token: &str,
+    ) -> Result<Option<ScoredMatch<DeezerTrack>>, AppError> {
+        if let Some(isrc) = &track.isrc {
+            let url = self.api_url(&format!("/track/isrc:{}", isrc), access_token)?;
+            match self.api_get::<DeezerTrack>(url).await {
+                Ok(deezer_track) => {
+                    info!(
+                        "Found track on Deezer by ISRC {}: {} - {}",
+                        isrc, track.artist, track.title
+                    );
+                    return Ok(Some(ScoredMatch::exact(
+                        deezer_track.clone(),
+                        deezer_track.title,
+                        deezer_track.artist.name,
+                    )));
+                }
+                // Unknown ISRC on Deezer, fall back to the text search
+                Err(AppError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let query = format!("artist:\"{}\" track:\"{}\"", track.artist, track.title);
+        let url = Url::parse_with_params(
+            &format!("{}/search/track", DEEZER_API_URL),
+            &[
+                ("q", query.as_str()),
+                ("limit", "5"),
+                ("access_token", access_token),
+            ],
+        )?;
+        let results: DeezerPage<DeezerTrack> = self.api_get(url).await?;
+
+        let candidates = results.data.into_iter().map(|deezer_track| TrackCandidate {
+            title: deezer_track.title.clone(),
+            artists: vec![deezer_track.artist.name.clone()],
+            album: Some(deezer_track.album.title.clone()),
+            duration_ms: deezer_track.duration.map(|seconds| seconds * 1000),
+            item: deezer_track,
+        });
+
+        match self
+            .matcher
+            .best_match(&TrackQuery::from(track), candidates)
+        {
+            Some(best) => {
+                info!(
+                    "Found track on Deezer: {} - {} (id: {}, confidence: {:.2})",
+                    track.artist, track.title, best.item.id, best.score
+                );
+                Ok(Some(best))
+            }
+            None => {
+                info!(
+                    "Track not found on Deezer: {} - {}",
+                    track.artist, track.title
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// Searches Deezer for every track of a database playlist without writing anything
+    pub async fn match_playlist_tracks(
+        &self,
+        playlist_id: i32,
+        user: &UserModel,
+        progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError> {
+        let playlist = self.playlist_service.get_playlist(playlist_id).await?;
+        let access_token = self.get_token(user.clone()).await?.token;
+
+        let tracks = self
+            .music_service
+            .find_by_playlist(&playlist)
+            .await
+            .map_err(|e| {
+                error!("Error getting playlist tracks: {:?}", e);
+                AppError::InternalServerError
+            })?;
+
+        let overrides = self
+            .track_match_override_service
+            .get_overrides(user, PlaylistOrigin::Deezer)
+            .await?;
+
+        progress.start_playlist(&playlist.name, tracks.len()).await;
+
+        let mut report = Vec::new();
+        for track in tracks {
+            let entry = if let Some(platform_track_id) = find_override(&overrides, &track) {
+                TrackMatchEntry::overridden(&track, platform_track_id)
+            } else if let Some(external_id) = self
+                .music_service
+                .find_external_id(&track, PlaylistOrigin::Deezer)
+                .await?
+            {
+                TrackMatchEntry::known(&track, &external_id)
+            } else {
+                match self.search_deezer_track(&track, &access_token).await {
+                    Ok(Some(best)) => best.report_entry(&track, best.item.id.to_string()),
+                    Ok(None) => TrackMatchEntry::not_found(&track),
+                    Err(e) => {
+                        error!(
+                            "Error searching for track {} - {}: {:?}",
+                            track.artist, track.title, e
+                        );
+                        TrackMatchEntry::not_found(&track)
+                    }
+                }
+            };
+            progress.track_matched(&entry).await;
+            report.push(entry);
+        }
+
+        progress.finish_playlist().await;
+        Ok(report)
+    }
+
+    /// Exports a playlist from our database to Deezer with the matched tracks and returns the
+    /// new Deezer playlist id
+    pub async fn export_playlist_to_deezer(
+        &self,
+        playlist_id: i32,
+        user: &UserModel,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        let playlist = self.playlist_service.get_playlist(playlist_id).await?;
+        let access_token = self.get_token(user.clone()).await?.token;
+
+        let url = Url::parse_with_params(
+            &format!("{}/user/me/playlists", DEEZER_API_URL),
+            &[
+                ("title", playlist.name.as_str()),
+                ("access_token", access_token.as_str()),
+            ],
+        )?;
+        let created: DeezerCreatedPlaylist = self.api_post(url).await?;
+        info!("Created Deezer playlist with ID: {}", created.id);
+
+        let deezer_track_ids = deezer_track_ids(&report);
+
+        for chunk in deezer_track_ids.chunks(50) {
+            let url = Url::parse_with_params(
+                &format!("{}/playlist/{}/tracks", DEEZER_API_URL, created.id),
+                &[
+                    ("songs", chunk.join(",").as_str()),
+                    ("access_token", access_token.as_str()),
+                ],
+            )?;
+            let added: bool = self.api_post(url).await?;
+            if !added {
+                error!("Deezer refused to add tracks to playlist {}", created.id);
+                return Err(AppError::InternalServerError);
+            }
+            info!("Added {} tracks to Deezer playlist", chunk.len());
+            progress.tracks_done(chunk.len()).await;
+        }
+
+        Ok(PlaylistExport {
+            playlist_id: created.id.to_string(),
+            report,
+        })
+    }
+
+    /// Brings a Deezer playlist created by a previous send in line with the matched tracks
+    pub async fn update_deezer_playlist(
+        &self,
+        platform_playlist_id: &str,
+        user: &UserModel,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        let access_token = self.get_token(user.clone()).await?.token;
+        let tracks_path = format!("/playlist/{}/tracks", platform_playlist_id);
+
+        // Fails with `NotFound` when the playlist was deleted
+        let current: Vec<String> = self
+            .api_get_all::<DeezerTrack>(self.api_url(&tracks_path, &access_token)?)
+            .await?
+            .into_iter()
+            .map(|track| track.id.to_string())
+            .collect();
+        let desired = deezer_track_ids(&report);
+        let diff = PlaylistDiff::between(&current, &desired);
+        // Tracks already in the playlist are done
+        progress
+            .tracks_done(desired.len().saturating_sub(diff.to_add.len()))
+            .await;
+
+        for chunk in diff.to_remove.chunks(50) {
+            let url = Url::parse_with_params(
+                &format!("{}{}", DEEZER_API_URL, tracks_path),
+                &[
+                    ("songs", chunk.join(",").as_str()),
+                    ("access_token", access_token.as_str()),
+                ],
+            )?;
+            let removed: bool = self.api_delete(url).await?;
+            if !removed {
+                error!(
+                    "Deezer refused to remove tracks from playlist {}",
+                    platform_playlist_id
+                );
+                return Err(AppError::InternalServerError);
+            }
+        }
+
+        for chunk in diff.to_add.chunks(50) {
+            let url = Url::parse_with_params(
+                &format!("{}{}", DEEZER_API_URL, tracks_path),
+                &[
+                    ("songs", chunk.join(",").as_str()),
+                    ("access_token", access_token.as_str()),
+                ],
+            )?;
+            let added: bool = self.api_post(url).await?;
+            if !added {
+                error!(
+                    "Deezer refused to add tracks to playlist {}",
+                    platform_playlist_id
+                );
+                return Err(AppError::InternalServerError);
+            }
+            progress.tracks_done(chunk.len()).await;
+        }
+
+        if diff.reorder {
+            let url = Url::parse_with_params(
+                &format!("{}{}", DEEZER_API_URL, tracks_path),
+                &[
+                    ("order", desired.join(",").as_str()),
+                    ("access_token", access_token.as_str()),
+                ],
+            )?;
+            let reordered: bool = self.api_post(url).await?;
+            if !reordered {
+                error!(
+                    "Deezer refused to reorder playlist {}",
+                    platform_playlist_id
+                );
+                return Err(AppError::InternalServerError);
+            }
+        }
+
+        info!(
+            "Updated Deezer playlist {}: {} added, {} removed",
+            platform_playlist_id,
+            diff.to_add.len(),
+            diff.to_remove.len()
+        );
+
+        Ok(PlaylistExport {
+            playlist_id: platform_playlist_id.to_string(),
+            report,
+        })
+    }
+
     pub async fn disconnect(&self, user: &UserModel) -> Result<(), AppError> {
         info!("Disconnecting Deezer for user {}", user.id);
 
@@ -129,3 +766,104 @@ impl DeezerService {
         Ok(())
     }
 }
+
+#[async_trait]
+impl MusicProvider for DeezerService {
+    fn origin(&self) -> PlaylistOrigin {
+        PlaylistOrigin::Deezer
+    }
+
+    async fn connect(&self, user: &UserModel, request: AddTokenRequest) -> Result<(), AppError> {
+        self.auth_callback(user, request).await
+    }
+
+    async fn list_playlists(&self, user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError> {
+        DeezerService::list_playlists(self, user).await
+    }
+
+    async fn import_playlists(
+        &self,
+        user: &UserModel,
+        selection: &ImportSelection,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        self.get_user_playlists(user, selection, progress).await
+    }
+
+    async fn search_track(
+        &self,
+        user: &UserModel,
+        music: &MusicModel,
+    ) -> Result<TrackMatchEntry, AppError> {
+        let access_token = self.get_token(user.clone()).await?.token;
+        Ok(
+            match self.search_deezer_track(music, &access_token).await? {
+                Some(best) => best.report_entry(music, best.item.id.to_string()),
+                None => TrackMatchEntry::not_found(music),
+            },
+        )
+    }
+
+    async fn preview_playlist(
+        &self,
+        user: &UserModel,
+        playlist_id: i32,
+        progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError> {
+        self.match_playlist_tracks(playlist_id, user, progress)
+            .await
+    }
+
+    async fn create_playlist_from_matches(
+        &self,
+        user: &UserModel,
+        playlist_id: i32,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        self.export_playlist_to_deezer(playlist_id, user, report, progress)
+            .await
+    }
+
+    async fn update_playlist_from_matches(
+        &self,
+        user: &UserModel,
+        platform_playlist_id: &str,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        self.update_deezer_playlist(platform_playlist_id, user, report, progress)
+            .await
+    }
+
+    async fn connected_users(&self) -> Result<Vec<i32>, AppError> {
+        Ok(self
+            .deezer_token_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|token| token.user_id)
+            .collect())
+    }
+
+    async fn disconnect(&self, user: &UserModel) -> Result<(), AppError> {
+        DeezerService::disconnect(self, user).await
+    }
+}
+
+/// Deezer ids of the matched tracks of a report, in playlist order. Deezer playlists can't hold
+/// the same track twice, repeated tracks keep their first slot.
+fn deezer_track_ids(report: &[TrackMatchEntry]) -> Vec<String> {
+    let mut deezer_track_ids: Vec<String> = Vec::new();
+    for track_id in report
+        .iter()
+        .filter_map(|entry| entry.platform_track_id.clone())
+    {
+        if deezer_track_ids.contains(&track_id) {
+            info!("Skipping repeated track {} on Deezer", track_id);
+            continue;
+        }
+        deezer_track_ids.push(track_id);
+    }
+    deezer_track_ids
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use validator::Validate;
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct DeezerUrlResponse {
+    pub url: String,
+}
+
+/// Response of `connect.deezer.com/oauth/access_token.php` with `output=json`
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DeezerAccessTokenResponse {
+    pub access_token: String,
+    /// Lifetime in seconds, 0 when the `offline_access` permission was granted
+    #[serde(default)]
+    pub expires: i64,
+}
+
+/// Paginated list returned by the Deezer API (`next` is the full url of the next page)
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DeezerPage<T> {
+    pub data: Vec<T>,
+    pub total: Option<u32>,
+    pub next: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DeezerPlaylist {
+    pub id: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub nb_tracks: Option<u32>,
+    pub picture_xl: Option<String>,
+    /// Owner of the playlist, the user themselves or the author of a followed playlist
+    pub creator: Option<DeezerUser>,
+}
+
+/// Deezer account as returned by `GET /user/me` or as the creator of a playlist
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DeezerUser {
+    pub id: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DeezerArtist {
+    pub id: u64,
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DeezerAlbum {
+    pub id: u64,
+    pub title: String,
+    pub cover_xl: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DeezerTrack {
+    pub id: u64,
+    pub title: String,
+    /// Duration in seconds
+    pub duration: Option<u32>,
+    pub artist: DeezerArtist,
+    pub album: DeezerAlbum,
+    /// Only sent by the track endpoints, playlist listings leave it out
+    #[serde(default)]
+    pub isrc: Option<String>,
+    #[serde(default)]
+    pub explicit_lyrics: Option<bool>,
+    /// 30 seconds mp3 extract
+    #[serde(default)]
+    pub preview: Option<String>,
+}
+
+/// Response of `POST /user/me/playlists`
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DeezerCreatedPlaylist {
+    pub id: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deezer_tracks_page_deserialization() {
+        let json = r#"{
+            "data": [{
+                "id": 3135556,
+                "title": "Harder, Better, Faster, Stronger",
+                "duration": 224,
+                "artist": {"id": 27, "name": "Daft Punk"},
+                "album": {"id": 302127, "title": "Discovery", "cover_xl": null}
+            }],
+            "total": 1
+        }"#;
+        let page: DeezerPage<DeezerTrack> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(page.data.len(), 1);
+        assert_eq!(page.data[0].artist.name, "Daft Punk");
+        assert_eq!(page.data[0].album.title, "Discovery");
+        assert!(page.next.is_none());
+        assert!(page.data[0].isrc.is_none());
+    }
+
+    #[test]
+    fn test_deezer_track_with_isrc_deserialization() {
+        let json = r#"{
+            "id": 3135556,
+            "title": "Harder, Better, Faster, Stronger",
+            "isrc": "GBDUW0000059",
+            "duration": 224,
+            "artist": {"id": 27, "name": "Daft Punk"},
+            "album": {"id": 302127, "title": "Discovery", "cover_xl": null}
+        }"#;
+        let track: DeezerTrack = serde_json::from_str(json).unwrap();
+
+        assert_eq!(track.isrc.as_deref(), Some("GBDUW0000059"));
+        assert!(track.explicit_lyrics.is_none());
+    }
+
+    #[test]
+    fn test_deezer_track_metadata_deserialization() {
+        let json = r#"{
+            "id": 3135556,
+            "title": "Harder, Better, Faster, Stronger",
+            "duration": 224,
+            "explicit_lyrics": false,
+            "preview": "https://cdns-preview-d.dzcdn.net/stream/c-deda7fa9316d9e9e880d2c6207e92260-8.mp3",
+            "artist": {"id": 27, "name": "Daft Punk"},
+            "album": {"id": 302127, "title": "Discovery", "cover_xl": "https://e-cdns-images.dzcdn.net/images/cover/1000x1000.jpg"}
+        }"#;
+        let track: DeezerTrack = serde_json::from_str(json).unwrap();
+
+        assert_eq!(track.explicit_lyrics, Some(false));
+        assert!(track.preview.is_some());
+        assert!(track.album.cover_xl.is_some());
+    }
+
+    #[test]
+    fn test_deezer_playlist_creator_deserialization() {
+        let json = r#"{
+            "data": [
+                {"id": 908622995, "title": "Loved Tracks", "nb_tracks": 42, "creator": {"id": 5, "name": "me"}},
+                {"id": 3155776842, "title": "Pop Hits", "nb_tracks": 1200}
+            ]
+        }"#;
+        let page: DeezerPage<DeezerPlaylist> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            page.data[0].creator.as_ref().map(|creator| creator.id),
+            Some(5)
+        );
+        assert!(page.data[1].creator.is_none());
+    }
+
+    #[test]
+    fn test_deezer_access_token_deserialization() {
+        let json = r#"{"access_token":"frXyz","expires":0}"#;
+        let token: DeezerAccessTokenResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(token.access_token, "frXyz");
+        assert_eq!(token.expires, 0);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod deezer;
+pub use deezer::*;
@@ -2,3 +2,6 @@
 pub mod deezer;
 #[cfg(feature = "full")]
 pub use deezer::*;
+
+pub mod dto;
+pub use dto::*;
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{NaiveDate, Utc};
+use log::{error, info, warn};
+use sea_orm::{ActiveValue::Set, DatabaseConnection, IntoActiveModel};
+use swaptun_models::MusicModel;
+use swaptun_repositories::MusicRepository;
+use tokio::task::JoinHandle;
+
+use crate::error::AppError;
+use crate::{ArtistCredit, MusicBrainzService};
+
+const DEFAULT_ENRICHMENT_INTERVAL_SECS: u64 = 600;
+const ENRICHMENT_BATCH_SIZE: u64 = 50;
+/// Candidates below this MusicBrainz score are too likely to be another recording
+const MIN_MUSICBRAINZ_SCORE: u8 = 80;
+/// Musics whose lookup keeps failing are given up on after this many attempts
+const MAX_ENRICHMENT_ATTEMPTS: i32 = 5;
+/// Delay before a music whose lookup failed is looked up again
+const ENRICHMENT_RETRY_DELAY_SECS: i64 = 3600;
+
+/// Fills the genre and release date that platforms did not give us from MusicBrainz
+#[derive(Clone)]
+pub struct EnrichmentService {
+    music_repository: MusicRepository,
+    musicbrainz_service: MusicBrainzService,
+}
+
+impl EnrichmentService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        let musicbrainz_service = MusicBrainzService::new(db.clone());
+        Self::with_musicbrainz(db, musicbrainz_service)
+    }
+
+    pub fn with_musicbrainz(
+        db: Arc<DatabaseConnection>,
+        musicbrainz_service: MusicBrainzService,
+    ) -> Self {
+        EnrichmentService {
+            music_repository: MusicRepository::new(db),
+            musicbrainz_service,
+        }
+    }
+
+    /// Runs the enrichment in the background, every `ENRICHMENT_INTERVAL_SECS` seconds once
+    /// nothing is left to enrich. Progress is stored on each music so a restart resumes where
+    /// the previous run stopped.
+    pub fn start(self) -> JoinHandle<()> {
+        let interval = std::env::var("ENRICHMENT_INTERVAL_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(DEFAULT_ENRICHMENT_INTERVAL_SECS));
+
+        tokio::spawn(async move {
+            loop {
+                match self.enrich_pending(ENRICHMENT_BATCH_SIZE).await {
+                    // More musics may be waiting, MusicBrainzService already paces the requests
+                    Ok(count) if count as u64 == ENRICHMENT_BATCH_SIZE => continue,
+                    Ok(count) => {
+                        if count > 0 {
+                            info!("Enriched {} musics from MusicBrainz", count);
+                        }
+                    }
+                    Err(e) => error!("Music enrichment failed, retrying later: {:?}", e),
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+
+    /// Looks up at most `limit` pending musics and returns how many were processed.
+    /// A failing lookup is recorded on the music, which waits `ENRICHMENT_RETRY_DELAY_SECS`
+    /// before being retried, and the batch goes on with the next musics.
+    pub async fn enrich_pending(&self, limit: u64) -> Result<usize, AppError> {
+        let failed_before = Utc::now() - chrono::Duration::seconds(ENRICHMENT_RETRY_DELAY_SECS);
+        let musics = self
+            .music_repository
+            .find_pending_enrichment(limit, MAX_ENRICHMENT_ATTEMPTS, failed_before.into())
+            .await?;
+        let mut processed = 0;
+        for music in musics {
+            if let Err(e) = self.enrich_music(music.clone()).await {
+                error!("Failed to enrich music {}: {:?}", music.id, e);
+                self.record_failure(music).await?;
+            }
+            processed += 1;
+        }
+        Ok(processed)
+    }
+
+    async fn record_failure(&self, music: MusicModel) -> Result<(), AppError> {
+        let attempts = music.enrichment_attempts + 1;
+        if attempts >= MAX_ENRICHMENT_ATTEMPTS {
+            warn!(
+                "Giving up on enriching music {} after {} attempts",
+                music.id, attempts
+            );
+        }
+        let mut active_model = music.into_active_model();
+        active_model.enrichment_attempts = Set(attempts);
+        active_model.enrichment_failed_on = Set(Some(Utc::now().into()));
+        self.music_repository.update(active_model).await?;
+        Ok(())
+    }
+
+    /// Fills the missing genre and placeholder release date of the music from its best
+    /// MusicBrainz recording, then marks it as enriched even when nothing was found
+    pub async fn enrich_music(&self, music: MusicModel) -> Result<MusicModel, AppError> {
+        // MusicBrainz credits featured artists separately, search on the main one only
+        let artist = ArtistCredit::parse(&music.artist)
+            .into_iter()
+            .next()
+            .map(|credit| credit.name)
+            .unwrap_or_else(|| music.artist.clone());
+
+        let recording = self
+            .musicbrainz_service
+            .search_track(&music.title, &artist)
+            .await?
+            .filter(|recording| recording.score >= MIN_MUSICBRAINZ_SCORE);
+
+        let mut active_model = music.clone().into_active_model();
+        match recording {
+            Some(recording) => {
+                if music.genre.is_none() && recording.genre.is_some() {
+                    active_model.genre = Set(recording.genre);
+                }
+                if music.release_date == NaiveDate::default() {
+                    if let Some(release_date) = recording
+                        .release_date
+                        .as_deref()
+                        .and_then(parse_release_date)
+                    {
+                        active_model.release_date = Set(release_date);
+                    }
+                }
+                active_model.musicbrainz_id = Set(Some(recording.musicbrainz_id));
+            }
+            None => warn!(
+                "No MusicBrainz recording for music {}: {} - {}",
+                music.id, music.artist, music.title
+            ),
+        }
+        active_model.enriched_on = Set(Some(Utc::now().into()));
+
+        Ok(self.music_repository.update(active_model).await?)
+    }
+}
+
+/// MusicBrainz dates may stop at the year or the month, the missing parts default to the first
+pub fn parse_release_date(date: &str) -> Option<NaiveDate> {
+    let mut parts = date.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next().map_or(Some(1), |month| month.parse().ok())?;
+    let day = parts.next().map_or(Some(1), |day| day.parse().ok())?;
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_release_date_with_full_date() {
+        assert_eq!(
+            parse_release_date("2023-09-15"),
+            NaiveDate::from_ymd_opt(2023, 9, 15)
+        );
+    }
+
+    #[test]
+    fn test_parse_release_date_with_partial_date() {
+        assert_eq!(
+            parse_release_date("2023-09"),
+            NaiveDate::from_ymd_opt(2023, 9, 1)
+        );
+        assert_eq!(
+            parse_release_date("2023"),
+            NaiveDate::from_ymd_opt(2023, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_parse_release_date_rejects_invalid_dates() {
+        assert_eq!(parse_release_date(""), None);
+        assert_eq!(parse_release_date("2023-13-01"), None);
+        assert_eq!(parse_release_date("unknown"), None);
+    }
+}
@@ -0,0 +1,4 @@
+#[cfg(feature = "full")]
+pub mod enrichment_service;
+#[cfg(feature = "full")]
+pub use enrichment_service::*;
@@ -0,0 +1,2 @@
+pub mod user_event;
+pub use user_event::*;
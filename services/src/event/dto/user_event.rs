@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use swaptun_models::PlaylistOrigin;
+
+use crate::TrackMatchEntry;
+
+/// Live update streamed to the connected clients of a user
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UserEvent {
+    /// A playlist was imported or synced from a platform
+    PlaylistImported {
+        playlist_id: i32,
+        origin: PlaylistOrigin,
+    },
+    /// One track of a playlist being exported was looked up on the destination platform
+    TrackMatched { job_id: i32, entry: TrackMatchEntry },
+    /// An export job is over, `error` is set when it failed for good
+    ExportFinished {
+        job_id: i32,
+        playlist_id: i32,
+        destination: PlaylistOrigin,
+        platform_playlist_id: Option<String>,
+        error: Option<String>,
+    },
+    /// Another user shared one of their playlists
+    PlaylistShared {
+        playlist_id: i32,
+        shared_by_id: i32,
+        shared_by_username: String,
+    },
+}
+
+impl UserEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            UserEvent::PlaylistImported { .. } => "playlist_imported",
+            UserEvent::TrackMatched { .. } => "track_matched",
+            UserEvent::ExportFinished { .. } => "export_finished",
+            UserEvent::PlaylistShared { .. } => "playlist_shared",
+        }
+    }
+
+    /// Server-Sent Events frame carrying the event as JSON
+    pub fn to_sse_frame(&self) -> String {
+        let data = serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string());
+        format!("event: {}\ndata: {}\n\n", self.name(), data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_is_tagged_with_its_name() {
+        let event = UserEvent::PlaylistImported {
+            playlist_id: 4,
+            origin: PlaylistOrigin::Deezer,
+        };
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["type"], event.name());
+        assert_eq!(value["playlist_id"], 4);
+    }
+
+    #[test]
+    fn test_sse_frame() {
+        let event = UserEvent::PlaylistShared {
+            playlist_id: 1,
+            shared_by_id: 2,
+            shared_by_username: "bob".to_string(),
+        };
+        assert_eq!(
+            event.to_sse_frame(),
+            "event: playlist_shared\n\
+             data: {\"type\":\"playlist_shared\",\"playlist_id\":1,\"shared_by_id\":2,\"shared_by_username\":\"bob\"}\n\n"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use tokio::sync::broadcast;
+
+use crate::UserEvent;
+
+/// Events a slow client can fall behind by before it misses some
+const CHANNEL_CAPACITY: usize = 64;
+
+static GLOBAL_HUB: Lazy<EventHub> = Lazy::new(EventHub::new);
+
+/// In-process broadcast of the `UserEvent`s to every open stream of their user
+#[derive(Default)]
+pub struct EventHub {
+    channels: Mutex<HashMap<i32, broadcast::Sender<UserEvent>>>,
+}
+
+impl EventHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hub shared by the services publishing events and the streaming endpoint
+    pub fn global() -> &'static EventHub {
+        &GLOBAL_HUB
+    }
+
+    pub fn subscribe(&self, user_id: i32) -> broadcast::Receiver<UserEvent> {
+        let mut channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        channels
+            .entry(user_id)
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Closes one stream of the user, their channel is dropped with their last stream
+    pub fn unsubscribe(&self, user_id: i32, receiver: broadcast::Receiver<UserEvent>) {
+        let mut channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        drop(receiver);
+        if channels
+            .get(&user_id)
+            .is_some_and(|sender| sender.receiver_count() == 0)
+        {
+            channels.remove(&user_id);
+        }
+    }
+
+    pub fn has_streams(&self, user_id: i32) -> bool {
+        let channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        channels.contains_key(&user_id)
+    }
+
+    /// Sends the event to the user's open streams, it is dropped when there are none
+    pub fn publish(&self, user_id: i32, event: UserEvent) {
+        let mut channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(sender) = channels.get(&user_id) else {
+            return;
+        };
+        if sender.send(event).is_err() {
+            // Every stream of the user was closed since the last event
+            channels.remove(&user_id);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod dto;
+pub use dto::*;
+#[cfg(feature = "full")]
+pub mod event_hub;
+#[cfg(feature = "full")]
+pub use event_hub::*;
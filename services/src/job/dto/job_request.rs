@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use swaptun_models::{JobKind, PlaylistOrigin};
+
+use crate::TrackMatchEntry;
+
+/// Parameters of a job, stored as JSON in its `payload` column
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum JobPayload {
+    /// Imports every playlist of the user's library on the platform
+    ImportPlaylists { origin: PlaylistOrigin },
+    /// Creates one of our playlists on the destination platform
+    ExportPlaylist {
+        playlist_id: i32,
+        destination: PlaylistOrigin,
+        /// Matches confirmed from a preview, the tracks are searched again when absent
+        #[serde(default)]
+        matches: Option<Vec<TrackMatchEntry>>,
+        /// Creates another playlist instead of updating the one of the previous export
+        #[serde(default)]
+        create_new: bool,
+    },
+}
+
+/// Page of the user's jobs, the 20 most recent when no limit is given
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct GetJobsParams {
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+/// Playlist skipped by a job, listed in its `playlist_errors`
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct JobPlaylistError {
+    pub playlist: String,
+    pub error: String,
+}
+
+impl JobPayload {
+    pub fn kind(&self) -> JobKind {
+        match self {
+            JobPayload::ImportPlaylists { .. } => JobKind::ImportPlaylists,
+            JobPayload::ExportPlaylist { .. } => JobKind::ExportPlaylist,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_round_trip_keeps_the_variant() {
+        let payload = JobPayload::ExportPlaylist {
+            playlist_id: 42,
+            destination: PlaylistOrigin::Deezer,
+            matches: None,
+            create_new: true,
+        };
+
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["type"], "ExportPlaylist");
+        assert_eq!(json["playlist_id"], 42);
+        assert_eq!(json["create_new"], true);
+
+        let decoded: JobPayload = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, payload);
+        assert_eq!(decoded.kind(), JobKind::ExportPlaylist);
+    }
+
+    #[test]
+    fn test_export_payload_queued_before_create_new_updates() {
+        let payload: JobPayload = serde_json::from_str(
+            r#"{"type": "ExportPlaylist", "playlist_id": 1, "destination": "Spotify"}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            payload,
+            JobPayload::ExportPlaylist {
+                create_new: false,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_import_payload_kind() {
+        let payload: JobPayload =
+            serde_json::from_str(r#"{"type": "ImportPlaylists", "origin": "Spotify"}"#).unwrap();
+
+        assert_eq!(
+            payload,
+            JobPayload::ImportPlaylists {
+                origin: PlaylistOrigin::Spotify
+            }
+        );
+        assert_eq!(payload.kind(), JobKind::ImportPlaylists);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod job_request;
+pub use job_request::*;
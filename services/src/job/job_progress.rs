@@ -0,0 +1,142 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::error;
+use swaptun_models::JobModel;
+use swaptun_repositories::JobRepository;
+use tokio::sync::Mutex;
+
+use crate::error::AppError;
+use crate::{EventHub, JobPlaylistError, TrackMatchEntry, UserEvent};
+
+/// Processed tracks are saved once this many are waiting or after `SAVE_INTERVAL`, whichever
+/// comes first, instead of writing the job after every track
+const SAVE_EVERY_TRACKS: i32 = 25;
+const SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+struct ProgressState {
+    total_tracks: i32,
+    processed_tracks: i32,
+    current_playlist: Option<String>,
+    playlist_errors: Vec<JobPlaylistError>,
+    unsaved_tracks: i32,
+    saved_at: Option<Instant>,
+}
+
+struct JobProgressInner {
+    job_id: i32,
+    user_id: i32,
+    job_repository: JobRepository,
+    state: Mutex<ProgressState>,
+}
+
+/// Reports how far a transfer went on its job. `JobProgress::none()` ignores every report,
+/// for transfers run outside of a job such as a preview.
+#[derive(Clone, Default)]
+pub struct JobProgress {
+    inner: Option<Arc<JobProgressInner>>,
+}
+
+impl JobProgress {
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn for_job(job: &JobModel, job_repository: JobRepository) -> Self {
+        JobProgress {
+            inner: Some(Arc::new(JobProgressInner {
+                job_id: job.id,
+                user_id: job.user_id,
+                job_repository,
+                state: Mutex::new(ProgressState::default()),
+            })),
+        }
+    }
+
+    /// A playlist of `tracks` tracks is being transferred
+    pub async fn start_playlist(&self, name: &str, tracks: usize) {
+        self.report(true, |state| {
+            state.total_tracks += i32::try_from(tracks).unwrap_or(i32::MAX);
+            state.current_playlist = Some(name.to_string());
+        })
+        .await
+    }
+
+    pub async fn track_done(&self) {
+        self.tracks_done(1).await
+    }
+
+    pub async fn tracks_done(&self, tracks: usize) {
+        let tracks = i32::try_from(tracks).unwrap_or(i32::MAX);
+        self.report(false, |state| {
+            state.processed_tracks += tracks;
+            state.unsaved_tracks += tracks;
+        })
+        .await
+    }
+
+    /// Saves the tracks processed since the last save, once the current playlist is done
+    pub async fn finish_playlist(&self) {
+        self.report(true, |_| {}).await
+    }
+
+    /// A track of an export was looked up, the match is streamed to the user
+    pub async fn track_matched(&self, entry: &TrackMatchEntry) {
+        if let Some(inner) = &self.inner {
+            EventHub::global().publish(
+                inner.user_id,
+                UserEvent::TrackMatched {
+                    job_id: inner.job_id,
+                    entry: entry.clone(),
+                },
+            );
+        }
+        self.track_done().await
+    }
+
+    pub async fn playlist_failed(&self, name: &str, error: &AppError) {
+        self.report(true, |state| {
+            state.playlist_errors.push(JobPlaylistError {
+                playlist: name.to_string(),
+                error: error.to_string(),
+            })
+        })
+        .await
+    }
+
+    /// Progress is informative only, failing to save it never fails the transfer. Processed
+    /// tracks are saved in batches unless `save` asks for the state to be saved right away.
+    async fn report(&self, save: bool, update: impl FnOnce(&mut ProgressState)) {
+        let Some(inner) = &self.inner else {
+            return;
+        };
+
+        // Held while saving so concurrent reports reach the database in order
+        let mut state = inner.state.lock().await;
+        update(&mut state);
+
+        let recently_saved = state
+            .saved_at
+            .is_some_and(|saved_at| saved_at.elapsed() < SAVE_INTERVAL);
+        if !save && recently_saved && state.unsaved_tracks < SAVE_EVERY_TRACKS {
+            return;
+        }
+        state.unsaved_tracks = 0;
+        state.saved_at = Some(Instant::now());
+
+        if let Err(e) = inner
+            .job_repository
+            .update_progress(
+                inner.job_id,
+                state.total_tracks,
+                state.processed_tracks,
+                state.current_playlist.clone(),
+                serde_json::to_value(&state.playlist_errors).unwrap_or_default(),
+            )
+            .await
+        {
+            error!("Failed to save progress of job {}: {:?}", inner.job_id, e);
+        }
+    }
+}
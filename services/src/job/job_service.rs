@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use log::{error, warn};
+use sea_orm::{ActiveValue::Set, DatabaseConnection, IntoActiveModel};
+use serde_json::Value;
+use swaptun_models::{JobActiveModel, JobKind, JobModel, JobStatus, PlaylistOrigin};
+use swaptun_repositories::JobRepository;
+
+use crate::error::AppError;
+use crate::{GetJobsParams, JobPayload, JobProgress};
+
+const DEFAULT_MAX_ATTEMPTS: i32 = 5;
+/// Exports are not idempotent, an attempt failing halfway may already have created the
+/// playlist on the destination and another attempt would create it twice
+const EXPORT_MAX_ATTEMPTS: i32 = 1;
+const RETRY_BASE_DELAY_SECS: i64 = 30;
+const RETRY_MAX_DELAY_SECS: i64 = 3600;
+const DEFAULT_JOBS_LIMIT: u64 = 20;
+const MAX_JOBS_LIMIT: u64 = 100;
+/// A job running for longer than this is considered lost with its worker, it runs again when
+/// it has attempts left and fails otherwise
+const JOB_TIMEOUT_SECS: i64 = 3600;
+
+/// Persistent queue of the imports and exports run by the `JobWorker`s
+#[derive(Clone)]
+pub struct JobService {
+    job_repository: JobRepository,
+}
+
+impl JobService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        JobService {
+            job_repository: JobRepository::new(db),
+        }
+    }
+
+    pub async fn enqueue(&self, user_id: i32, payload: JobPayload) -> Result<JobModel, AppError> {
+        self.enqueue_at(user_id, payload, Utc::now()).await
+    }
+
+    /// Queues a job that no worker picks up before `run_at`
+    pub async fn enqueue_at(
+        &self,
+        user_id: i32,
+        payload: JobPayload,
+        run_at: DateTime<Utc>,
+    ) -> Result<JobModel, AppError> {
+        let job = JobActiveModel {
+            user_id: Set(user_id),
+            kind: Set(payload.kind()),
+            status: Set(JobStatus::Queued),
+            payload: Set(serde_json::to_value(&payload).map_err(|e| {
+                error!("Failed to serialize job payload: {:?}", e);
+                AppError::InternalServerError
+            })?),
+            attempts: Set(0),
+            max_attempts: Set(max_attempts(&payload)),
+            run_at: Set(run_at.into()),
+            ..Default::default()
+        };
+        Ok(self.job_repository.create(job).await?)
+    }
+
+    pub async fn get_job(&self, id: i32) -> Result<JobModel, AppError> {
+        self.job_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Job {} not found", id)))
+    }
+
+    /// Jobs of the user, most recent first
+    pub async fn get_user_jobs(
+        &self,
+        user_id: i32,
+        params: GetJobsParams,
+    ) -> Result<Vec<JobModel>, AppError> {
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_JOBS_LIMIT)
+            .min(MAX_JOBS_LIMIT);
+        Ok(self
+            .job_repository
+            .find_by_user(user_id, limit, params.offset.unwrap_or(0))
+            .await?)
+    }
+
+    /// Accounts with an import queued or running, as `(user_id, origin)`
+    pub async fn pending_imports(&self) -> Result<Vec<(i32, PlaylistOrigin)>, AppError> {
+        Ok(self
+            .job_repository
+            .find_pending(JobKind::ImportPlaylists)
+            .await?
+            .into_iter()
+            .filter_map(|job| match serde_json::from_value(job.payload) {
+                Ok(JobPayload::ImportPlaylists { origin }) => Some((job.user_id, origin)),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Exports waiting for a worker, as `(user_id, playlist_id, destination)`
+    pub async fn queued_exports(&self) -> Result<Vec<(i32, i32, PlaylistOrigin)>, AppError> {
+        Ok(self
+            .job_repository
+            .find_pending(JobKind::ExportPlaylist)
+            .await?
+            .into_iter()
+            .filter(|job| job.status == JobStatus::Queued)
+            .filter_map(|job| match serde_json::from_value(job.payload) {
+                Ok(JobPayload::ExportPlaylist {
+                    playlist_id,
+                    destination,
+                    ..
+                }) => Some((job.user_id, playlist_id, destination)),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Handle through which a running job reports its progress
+    pub fn progress(&self, job: &JobModel) -> JobProgress {
+        JobProgress::for_job(job, self.job_repository.clone())
+    }
+
+    /// Next job to run, already marked as running so no other worker picks it up
+    pub async fn claim_next(&self) -> Result<Option<JobModel>, AppError> {
+        let now = Utc::now();
+        let stale_before = now - Duration::seconds(JOB_TIMEOUT_SECS);
+        self.job_repository
+            .fail_stale(now.into(), stale_before.into(), "Job interrupted")
+            .await?;
+        Ok(self
+            .job_repository
+            .claim_next(now.into(), stale_before.into())
+            .await?)
+    }
+
+    pub async fn succeed(
+        &self,
+        job: JobModel,
+        result: Option<Value>,
+    ) -> Result<JobModel, AppError> {
+        let mut active_model = job.into_active_model();
+        active_model.status = Set(JobStatus::Succeeded);
+        active_model.result = Set(result);
+        active_model.error = Set(None);
+        active_model.finished_on = Set(Some(Utc::now().into()));
+        Ok(self.job_repository.update(active_model).await?)
+    }
+
+    /// Queues the job again after a growing delay, or marks it as failed once it has no
+    /// attempts left or the error will not go away by retrying
+    pub async fn fail(&self, job: JobModel, error: &AppError) -> Result<JobModel, AppError> {
+        let retryable = matches!(error, AppError::Database(_) | AppError::InternalServerError);
+        let attempts = job.attempts;
+        let max_attempts = job.max_attempts;
+        let id = job.id;
+
+        let mut active_model = job.into_active_model();
+        active_model.error = Set(Some(error.to_string()));
+        if retryable && attempts < max_attempts {
+            let delay = retry_delay(attempts);
+            warn!(
+                "Job {} failed on attempt {}/{}, retrying in {}s: {}",
+                id,
+                attempts,
+                max_attempts,
+                delay.num_seconds(),
+                error
+            );
+            active_model.status = Set(JobStatus::Queued);
+            active_model.run_at = Set((Utc::now() + delay).into());
+        } else {
+            error!("Job {} failed after {} attempts: {}", id, attempts, error);
+            active_model.status = Set(JobStatus::Failed);
+            active_model.finished_on = Set(Some(Utc::now().into()));
+        }
+        Ok(self.job_repository.update(active_model).await?)
+    }
+}
+
+fn max_attempts(payload: &JobPayload) -> i32 {
+    match payload {
+        JobPayload::ImportPlaylists { .. } => DEFAULT_MAX_ATTEMPTS,
+        JobPayload::ExportPlaylist { .. } => EXPORT_MAX_ATTEMPTS,
+    }
+}
+
+/// Exponential backoff: 30s after the first attempt, then 1min, 2min... up to an hour
+pub fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    Duration::seconds((RETRY_BASE_DELAY_SECS << exponent).min(RETRY_MAX_DELAY_SECS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles_after_each_attempt() {
+        assert_eq!(retry_delay(1), Duration::seconds(30));
+        assert_eq!(retry_delay(2), Duration::seconds(60));
+        assert_eq!(retry_delay(3), Duration::seconds(120));
+    }
+
+    #[test]
+    fn test_retry_delay_is_capped() {
+        assert_eq!(retry_delay(10), Duration::seconds(RETRY_MAX_DELAY_SECS));
+        assert_eq!(
+            retry_delay(i32::MAX),
+            Duration::seconds(RETRY_MAX_DELAY_SECS)
+        );
+    }
+}
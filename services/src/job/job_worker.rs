@@ -0,0 +1,188 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{error, info};
+use sea_orm::DatabaseConnection;
+use serde_json::Value;
+use swaptun_models::{JobModel, JobStatus};
+use swaptun_repositories::UserRepository;
+use tokio::task::JoinHandle;
+
+use crate::error::AppError;
+use crate::{
+    EventHub, ImportSelectionService, JobPayload, JobService, PlaylistExportService,
+    ProviderRegistry, SendPlaylistResponse, UserEvent,
+};
+
+const DEFAULT_JOB_WORKERS: usize = 4;
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Runs the queued jobs, several workers can share the same queue
+#[derive(Clone)]
+pub struct JobWorker {
+    job_service: JobService,
+    user_repository: UserRepository,
+    playlist_export_service: PlaylistExportService,
+    import_selection_service: ImportSelectionService,
+    registry: ProviderRegistry,
+}
+
+impl JobWorker {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        let registry = ProviderRegistry::new(db.clone());
+        Self::with_registry(db, registry)
+    }
+
+    pub fn with_registry(db: Arc<DatabaseConnection>, registry: ProviderRegistry) -> Self {
+        JobWorker {
+            job_service: JobService::new(db.clone()),
+            user_repository: UserRepository::new(db.clone()),
+            playlist_export_service: PlaylistExportService::new(db.clone()),
+            import_selection_service: ImportSelectionService::new(db),
+            registry,
+        }
+    }
+
+    /// Spawns `JOB_WORKERS` workers polling the queue
+    pub fn start(self) -> Vec<JoinHandle<()>> {
+        let workers = std::env::var("JOB_WORKERS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .filter(|workers| *workers > 0)
+            .unwrap_or(DEFAULT_JOB_WORKERS);
+        info!("Starting {} job workers", workers);
+
+        (0..workers)
+            .map(|_| {
+                let worker = self.clone();
+                tokio::spawn(async move {
+                    loop {
+                        match worker.run_next().await {
+                            Ok(true) => continue,
+                            Ok(false) => {}
+                            Err(e) => error!("Job worker failed to reach the queue: {:?}", e),
+                        }
+                        tokio::time::sleep(POLL_INTERVAL).await;
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Runs the next due job, returns `false` when the queue is empty
+    pub async fn run_next(&self) -> Result<bool, AppError> {
+        let Some(job) = self.job_service.claim_next().await? else {
+            return Ok(false);
+        };
+
+        info!(
+            "Running job {} ({:?}), attempt {}",
+            job.id, job.kind, job.attempts
+        );
+        // A panicking job must not take its worker down nor stay running until it times out
+        let worker = self.clone();
+        let claimed = job.clone();
+        let outcome = tokio::spawn(async move { worker.execute(&claimed).await }).await;
+        let job = match outcome {
+            Ok(Ok(result)) => self.job_service.succeed(job, result).await?,
+            Ok(Err(e)) => self.job_service.fail(job, &e).await?,
+            Err(e) => {
+                error!("Job {} panicked: {:?}", job.id, e);
+                self.job_service
+                    .fail(job, &AppError::InternalServerError)
+                    .await?
+            }
+        };
+        if matches!(job.status, JobStatus::Succeeded | JobStatus::Failed) {
+            publish_export_finished(&job);
+        }
+        Ok(true)
+    }
+
+    async fn execute(&self, job: &JobModel) -> Result<Option<Value>, AppError> {
+        let payload: JobPayload = serde_json::from_value(job.payload.clone())
+            .map_err(|e| AppError::Validation(format!("Invalid job payload: {}", e)))?;
+        let user = self
+            .user_repository
+            .find_by_id(job.user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("User {} not found", job.user_id)))?;
+        let progress = self.job_service.progress(job);
+
+        match payload {
+            JobPayload::ImportPlaylists { origin } => {
+                let selection = self
+                    .import_selection_service
+                    .get(user.id, origin.clone())
+                    .await?;
+                self.registry
+                    .get(&origin)?
+                    .import_playlists(&user, &selection, &progress)
+                    .await?;
+                Ok(None)
+            }
+            JobPayload::ExportPlaylist {
+                playlist_id,
+                destination,
+                matches,
+                create_new,
+            } => {
+                let provider = self.registry.get(&destination)?;
+                // Matches confirmed by the client are not trusted for the shared cache
+                let (report, cache_matches) = match matches {
+                    Some(matches) => (matches, false),
+                    None => (
+                        provider
+                            .preview_playlist(&user, playlist_id, &progress)
+                            .await?,
+                        true,
+                    ),
+                };
+                let response = self
+                    .playlist_export_service
+                    .export(
+                        provider.as_ref(),
+                        &user,
+                        playlist_id,
+                        report,
+                        create_new,
+                        cache_matches,
+                        &progress,
+                    )
+                    .await?;
+                serde_json::to_value(response).map(Some).map_err(|e| {
+                    error!("Failed to serialize export of job {}: {:?}", job.id, e);
+                    AppError::InternalServerError
+                })
+            }
+        }
+    }
+}
+
+/// Tells the user's open streams that an export job is over
+fn publish_export_finished(job: &JobModel) {
+    let Ok(JobPayload::ExportPlaylist {
+        playlist_id,
+        destination,
+        ..
+    }) = serde_json::from_value(job.payload.clone())
+    else {
+        return;
+    };
+    let platform_playlist_id = job
+        .result
+        .clone()
+        .and_then(|result| serde_json::from_value::<SendPlaylistResponse>(result).ok())
+        .map(|response| response.playlist_id);
+
+    EventHub::global().publish(
+        job.user_id,
+        UserEvent::ExportFinished {
+            job_id: job.id,
+            playlist_id,
+            destination,
+            platform_playlist_id,
+            error: job.error.clone(),
+        },
+    );
+}
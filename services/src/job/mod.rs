@@ -0,0 +1,14 @@
+pub mod dto;
+pub use dto::*;
+#[cfg(feature = "full")]
+pub mod job_progress;
+#[cfg(feature = "full")]
+pub use job_progress::*;
+#[cfg(feature = "full")]
+pub mod job_service;
+#[cfg(feature = "full")]
+pub use job_service::*;
+#[cfg(feature = "full")]
+pub mod job_worker;
+#[cfg(feature = "full")]
+pub use job_worker::*;
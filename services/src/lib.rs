@@ -2,17 +2,25 @@
 
 pub mod auth;
 pub mod deezer;
+pub mod enrichment;
 
 pub mod apple;
+pub mod artist;
 pub mod error;
+pub mod event;
+pub mod job;
 pub mod mail;
+pub mod matcher;
 pub mod music;
 pub mod musicbrainz;
 pub mod notification;
 pub mod playlist;
+pub mod provider;
+pub mod scheduler;
 pub mod spotify;
 #[cfg(feature = "full")]
 pub mod test;
+pub mod track_match_override;
 pub mod user;
 pub mod user_info;
 pub mod validators;
@@ -21,19 +29,30 @@ pub mod yt_music;
 pub use test::*;
 
 pub use deezer::*;
+pub use enrichment::*;
+pub use event::*;
+pub use job::*;
+pub use matcher::*;
 pub use music::*;
 
 pub use musicbrainz::*;
 
 pub use playlist::*;
 
+pub use provider::*;
+
+pub use scheduler::*;
+
 pub use spotify::*;
 
+pub use track_match_override::*;
+
 pub use user::*;
 
 pub use yt_music::*;
 
 pub use apple::*;
+pub use artist::*;
 pub use notification::*;
 
 pub use swaptun_models::*;
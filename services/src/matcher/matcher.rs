@@ -0,0 +1,517 @@
+use std::env::var;
+
+use log::warn;
+use swaptun_models::MusicModel;
+
+use crate::{TrackMatchEntry, TrackMatchStatus};
+
+const DEFAULT_MATCH_THRESHOLD: f64 = 0.75;
+const DEFAULT_AMBIGUITY_MARGIN: f64 = 0.05;
+
+const TITLE_WEIGHT: f64 = 0.5;
+const ARTIST_WEIGHT: f64 = 0.3;
+const ALBUM_WEIGHT: f64 = 0.1;
+const DURATION_WEIGHT: f64 = 0.1;
+
+/// Durations closer than this are considered identical
+const DURATION_TOLERANCE_MS: u32 = 2_000;
+/// Durations further apart than this get no duration score at all
+const DURATION_MAX_GAP_MS: u32 = 15_000;
+
+/// Separators used in artist credits, longer forms first so " feat. " wins over " feat "
+const ARTIST_SEPARATORS: [&str; 10] = [
+    "&",
+    ",",
+    "/",
+    " featuring ",
+    " feat. ",
+    " feat ",
+    " ft. ",
+    " ft ",
+    " with ",
+    " x ",
+];
+
+/// Factor applied to titles that only match without their "(Live)", "- Remastered" parts
+const DECORATION_PENALTY: f64 = 0.95;
+
+/// Similarity above which two artist names are considered the same artist
+const ARTIST_SIMILARITY: f64 = 0.85;
+
+/// The track we are looking for on a platform
+#[derive(Debug, Clone)]
+pub struct TrackQuery {
+    pub title: String,
+    pub artist: String,
+    pub album: Option<String>,
+    pub duration_ms: Option<u32>,
+}
+
+impl From<&MusicModel> for TrackQuery {
+    fn from(music: &MusicModel) -> Self {
+        Self {
+            title: music.title.clone(),
+            artist: music.artist.clone(),
+            album: Some(music.album.clone()).filter(|album| !album.trim().is_empty()),
+            duration_ms: music
+                .duration_ms
+                .and_then(|duration| u32::try_from(duration).ok()),
+        }
+    }
+}
+
+/// A search result returned by a platform, carrying the platform specific item
+#[derive(Debug, Clone)]
+pub struct TrackCandidate<T> {
+    pub item: T,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub duration_ms: Option<u32>,
+}
+
+/// The best candidate for a query along with its confidence score between 0 and 1.
+/// `ambiguous` is set when a different track scored almost as well.
+#[derive(Debug, Clone)]
+pub struct ScoredMatch<T> {
+    pub item: T,
+    pub title: String,
+    pub artist: String,
+    pub score: f64,
+    pub ambiguous: bool,
+}
+
+impl<T> ScoredMatch<T> {
+    /// Match found through an exact identifier such as an ISRC, without any scoring
+    pub fn exact(item: T, title: String, artist: String) -> Self {
+        ScoredMatch {
+            item,
+            title,
+            artist,
+            score: 1.0,
+            ambiguous: false,
+        }
+    }
+
+    /// Builds the export report entry of `music` for this match
+    pub fn report_entry(&self, music: &MusicModel, platform_track_id: String) -> TrackMatchEntry {
+        TrackMatchEntry {
+            status: if self.ambiguous {
+                TrackMatchStatus::Ambiguous
+            } else {
+                TrackMatchStatus::Matched
+            },
+            platform_track_id: Some(platform_track_id),
+            platform_title: Some(self.title.clone()),
+            platform_artist: Some(self.artist.clone()),
+            confidence: Some(self.score),
+            ..TrackMatchEntry::not_found(music)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatcherConfig {
+    pub threshold: f64,
+    pub ambiguity_margin: f64,
+}
+
+impl Default for MatcherConfig {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_MATCH_THRESHOLD,
+            ambiguity_margin: DEFAULT_AMBIGUITY_MARGIN,
+        }
+    }
+}
+
+impl MatcherConfig {
+    /// Reads `MATCH_THRESHOLD` and `MATCH_AMBIGUITY_MARGIN`, falling back to the defaults
+    pub fn from_env() -> Self {
+        Self {
+            threshold: fraction_from_env("MATCH_THRESHOLD", DEFAULT_MATCH_THRESHOLD),
+            ambiguity_margin: fraction_from_env("MATCH_AMBIGUITY_MARGIN", DEFAULT_AMBIGUITY_MARGIN),
+        }
+    }
+}
+
+fn fraction_from_env(name: &str, default: f64) -> f64 {
+    match var(name) {
+        Ok(value) => match value.parse::<f64>() {
+            Ok(fraction) if (0.0..=1.0).contains(&fraction) => fraction,
+            _ => {
+                warn!("Invalid {} '{}', using default {}", name, value, default);
+                default
+            }
+        },
+        Err(_) => default,
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TrackMatcher {
+    config: MatcherConfig,
+}
+
+impl TrackMatcher {
+    pub fn new(config: MatcherConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(MatcherConfig::from_env())
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.config.threshold
+    }
+
+    /// Scores a candidate against a query, weighting title, artists, album and duration.
+    /// Album and duration only count when both sides know them.
+    pub fn score<T>(&self, query: &TrackQuery, candidate: &TrackCandidate<T>) -> f64 {
+        let mut total = TITLE_WEIGHT * title_similarity(&query.title, &candidate.title)
+            + ARTIST_WEIGHT * artist_similarity(&query.artist, &candidate.artists);
+        let mut weights = TITLE_WEIGHT + ARTIST_WEIGHT;
+
+        if let (Some(query_album), Some(candidate_album)) = (&query.album, &candidate.album) {
+            total += ALBUM_WEIGHT * title_similarity(query_album, candidate_album);
+            weights += ALBUM_WEIGHT;
+        }
+
+        if let (Some(query_duration), Some(candidate_duration)) =
+            (query.duration_ms, candidate.duration_ms)
+        {
+            total += DURATION_WEIGHT * duration_similarity(query_duration, candidate_duration);
+            weights += DURATION_WEIGHT;
+        }
+
+        total / weights
+    }
+
+    /// Returns the highest scoring candidate, if it reaches the configured threshold.
+    /// The match is flagged ambiguous when another, different track is within the margin.
+    pub fn best_match<T>(
+        &self,
+        query: &TrackQuery,
+        candidates: impl IntoIterator<Item = TrackCandidate<T>>,
+    ) -> Option<ScoredMatch<T>> {
+        let mut scored: Vec<(f64, TrackCandidate<T>)> = candidates
+            .into_iter()
+            .map(|candidate| (self.score(query, &candidate), candidate))
+            .filter(|(score, _)| *score >= self.config.threshold)
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let mut scored = scored.into_iter();
+        let (score, best) = scored.next()?;
+        let ambiguous = scored.any(|(other_score, other)| {
+            score - other_score < self.config.ambiguity_margin && !same_track(&best, &other)
+        });
+
+        Some(ScoredMatch {
+            title: best.title,
+            artist: best.artists.join(", "),
+            item: best.item,
+            score,
+            ambiguous,
+        })
+    }
+}
+
+/// Whether two candidates are the same recording listed twice, e.g. on an album and a single
+fn same_track<T>(a: &TrackCandidate<T>, b: &TrackCandidate<T>) -> bool {
+    let artists = |candidate: &TrackCandidate<T>| {
+        let mut artists: Vec<String> = candidate.artists.iter().map(|a| normalize(a)).collect();
+        artists.sort();
+        artists
+    };
+    normalize(&a.title) == normalize(&b.title) && artists(a) == artists(b)
+}
+
+/// Lowercases and keeps only alphanumeric words separated by single spaces
+fn normalize(s: &str) -> String {
+    s.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Removes bracketed parts and dash suffixes, e.g. "Song (Remastered 2011) - Live" becomes "Song"
+fn strip_decorations(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut depth = 0usize;
+    for c in s.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => result.push(c),
+            _ => {}
+        }
+    }
+    match result.find(" - ") {
+        Some(index) => result[..index].to_string(),
+        None => result,
+    }
+}
+
+/// Ratio between 0 and 1 derived from the Levenshtein distance of two strings
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f64 / max_len as f64
+}
+
+/// Compares titles both as given and without version decorations, keeping the best of the two.
+/// Matching only once decorations are removed scores slightly lower so the plain version wins.
+fn title_similarity(a: &str, b: &str) -> f64 {
+    let full = similarity(&normalize(a), &normalize(b));
+    let core_a = normalize(&strip_decorations(a));
+    let core_b = normalize(&strip_decorations(b));
+    if core_a.is_empty() || core_b.is_empty() {
+        return full;
+    }
+    full.max(DECORATION_PENALTY * similarity(&core_a, &core_b))
+}
+
+/// Splits an artist credit such as "SDM feat. Werenoi & Ninho" into individual names
+fn split_artists(artist: &str) -> Vec<String> {
+    let mut credit = format!(" {} ", artist.to_lowercase());
+    for separator in ARTIST_SEPARATORS {
+        credit = credit.replace(separator, "|");
+    }
+    credit
+        .split('|')
+        .map(normalize)
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// Scores how well the credited artists overlap. The best matching artist counts most so
+/// a platform only exposing the main artist still scores well, and full overlap scores 1.
+fn artist_similarity(query_artist: &str, candidate_artists: &[String]) -> f64 {
+    let query_parts = split_artists(query_artist);
+    let candidate_parts: Vec<String> = candidate_artists
+        .iter()
+        .flat_map(|artist| split_artists(artist))
+        .collect();
+    if query_parts.is_empty() || candidate_parts.is_empty() {
+        return 0.0;
+    }
+
+    let scores: Vec<f64> = query_parts
+        .iter()
+        .map(|query_part| {
+            candidate_parts
+                .iter()
+                .map(|candidate_part| similarity(query_part, candidate_part))
+                .fold(0.0, f64::max)
+        })
+        .collect();
+
+    let best = scores.iter().cloned().fold(0.0, f64::max);
+    let overlap = scores
+        .iter()
+        .filter(|score| **score >= ARTIST_SIMILARITY)
+        .count() as f64
+        / scores.len() as f64;
+
+    0.6 * best + 0.4 * overlap
+}
+
+fn duration_similarity(a: u32, b: u32) -> f64 {
+    let gap = a.abs_diff(b);
+    if gap <= DURATION_TOLERANCE_MS {
+        1.0
+    } else if gap >= DURATION_MAX_GAP_MS {
+        0.0
+    } else {
+        1.0 - (gap - DURATION_TOLERANCE_MS) as f64
+            / (DURATION_MAX_GAP_MS - DURATION_TOLERANCE_MS) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(title: &str, artist: &str) -> TrackQuery {
+        TrackQuery {
+            title: title.to_string(),
+            artist: artist.to_string(),
+            album: None,
+            duration_ms: None,
+        }
+    }
+
+    fn candidate(id: u32, title: &str, artists: &[&str]) -> TrackCandidate<u32> {
+        TrackCandidate {
+            item: id,
+            title: title.to_string(),
+            artists: artists.iter().map(|a| a.to_string()).collect(),
+            album: None,
+            duration_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_exact_match_scores_one() {
+        let matcher = TrackMatcher::default();
+        let score = matcher.score(
+            &query("Bohemian Rhapsody", "Queen"),
+            &candidate(1, "Bohemian Rhapsody", &["Queen"]),
+        );
+        assert!((score - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_short_title_is_not_matched_by_containment() {
+        let matcher = TrackMatcher::default();
+        let result = matcher.best_match(
+            &query("Intro", "The xx"),
+            vec![candidate(1, "Introspection Interlude", &["The xx"])],
+        );
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_version_decorations_are_ignored() {
+        let matcher = TrackMatcher::default();
+        let result = matcher.best_match(
+            &query("Let It Be", "The Beatles"),
+            vec![candidate(
+                1,
+                "Let It Be - Remastered 2009",
+                &["The Beatles"],
+            )],
+        );
+        assert_eq!(result.map(|m| m.item), Some(1));
+    }
+
+    #[test]
+    fn test_featured_artists_are_split() {
+        let matcher = TrackMatcher::default();
+        let result = matcher.best_match(
+            &query("Tout va bien", "SDM feat. Werenoi"),
+            vec![candidate(1, "Tout va bien", &["SDM", "Werenoi"])],
+        );
+        assert!(result.is_some_and(|m| m.score > 0.99));
+
+        let main_artist_only = matcher.best_match(
+            &query("Tout va bien", "SDM & Werenoi"),
+            vec![candidate(2, "Tout va bien", &["SDM"])],
+        );
+        assert_eq!(main_artist_only.map(|m| m.item), Some(2));
+    }
+
+    #[test]
+    fn test_best_candidate_wins_over_first() {
+        let matcher = TrackMatcher::default();
+        let result = matcher.best_match(
+            &query("Hello", "Adele"),
+            vec![
+                candidate(1, "Hello", &["Adele"]),
+                candidate(2, "Hello (Live at the BBC)", &["Adele"]),
+            ],
+        );
+        assert_eq!(result.map(|m| m.item), Some(1));
+    }
+
+    #[test]
+    fn test_wrong_artist_is_rejected() {
+        let matcher = TrackMatcher::default();
+        let result = matcher.best_match(
+            &query("Hello", "Adele"),
+            vec![candidate(1, "Hello", &["Lionel Richie"])],
+        );
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_duration_breaks_ties() {
+        let matcher = TrackMatcher::default();
+        let mut track = query("Hurt", "Johnny Cash");
+        track.duration_ms = Some(218_000);
+        let mut short = candidate(1, "Hurt", &["Johnny Cash"]);
+        short.duration_ms = Some(120_000);
+        let mut right = candidate(2, "Hurt", &["Johnny Cash"]);
+        right.duration_ms = Some(217_000);
+
+        let result = matcher.best_match(&track, vec![short, right]);
+        assert_eq!(result.map(|m| m.item), Some(2));
+    }
+
+    #[test]
+    fn test_threshold_is_configurable() {
+        let strict = TrackMatcher::new(MatcherConfig {
+            threshold: 1.0,
+            ..Default::default()
+        });
+        let result = strict.best_match(
+            &query("Halo", "Beyonce"),
+            vec![candidate(1, "Halo", &["Beyoncé"])],
+        );
+        assert!(result.is_none());
+
+        let lenient = TrackMatcher::new(MatcherConfig {
+            threshold: 0.5,
+            ..Default::default()
+        });
+        let result = lenient.best_match(
+            &query("Halo", "Beyonce"),
+            vec![candidate(1, "Halo", &["Beyoncé"])],
+        );
+        assert!(result.is_some());
+    }
+
+    #[test]
+    fn test_close_scores_of_different_tracks_are_ambiguous() {
+        let matcher = TrackMatcher::default();
+        let result = matcher
+            .best_match(
+                &query("Stay", "Rihanna"),
+                vec![
+                    candidate(1, "Stay", &["Rihanna", "Mikky Ekko"]),
+                    candidate(2, "Stay", &["Rihanna"]),
+                ],
+            )
+            .unwrap();
+        assert!(result.ambiguous);
+    }
+
+    #[test]
+    fn test_duplicate_listings_are_not_ambiguous() {
+        let matcher = TrackMatcher::default();
+        let result = matcher
+            .best_match(
+                &query("Stay", "Rihanna"),
+                vec![
+                    candidate(1, "Stay", &["Rihanna"]),
+                    candidate(2, "Stay", &["Rihanna"]),
+                ],
+            )
+            .unwrap();
+        assert!(!result.ambiguous);
+        assert_eq!(result.artist, "Rihanna");
+    }
+}
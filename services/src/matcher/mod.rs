@@ -0,0 +1,4 @@
+#[cfg(feature = "full")]
+pub mod matcher;
+#[cfg(feature = "full")]
+pub use matcher::*;
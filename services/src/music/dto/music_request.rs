@@ -1,7 +1,9 @@
 use sea_orm::entity::prelude::Date;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
-#[derive(Deserialize, Serialize, Validate)]
+
+use crate::artist::dto::ArtistCredit;
+#[derive(Deserialize, Serialize, Validate, Default)]
 pub struct CreateMusicRequest {
     pub title: String,
     pub description: Option<String>,
@@ -10,15 +12,33 @@ pub struct CreateMusicRequest {
     pub album: String,
     pub release_date: Date,
     pub genre: Option<String>,
+    #[serde(default)]
+    pub isrc: Option<String>,
+    #[serde(default)]
+    pub duration_ms: Option<i32>,
+    #[serde(default)]
+    pub explicit: Option<bool>,
+    #[serde(default)]
+    pub artwork_url: Option<String>,
+    #[serde(default)]
+    pub disc_number: Option<i32>,
+    #[serde(default)]
+    pub track_number: Option<i32>,
+    #[serde(default)]
+    pub preview_url: Option<String>,
+    /// Every credited artist, parsed from `artist` when left empty
+    #[serde(default)]
+    pub artists: Vec<ArtistCredit>,
 }
 
 #[derive(Deserialize, Serialize, Validate)]
 pub struct UpdateMusicRequest {
+    pub id: i32,
     pub name: Option<String>,
     pub description: Option<String>,
-    pub title: String,
-    pub artist: String,
-    pub album: String,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
     pub release_date: Option<Date>,
     pub genre: Option<String>,
 }
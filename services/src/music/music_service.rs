@@ -2,23 +2,31 @@ use std::sync::Arc;
 
 use crate::music::dto::CreateMusicRequest;
 use crate::music::dto::UpdateMusicRequest;
+use crate::{ArtistCredit, ArtistService, TrackMatchEntry, TrackMatchStatus};
+use chrono::{FixedOffset, Utc};
 use sea_orm::ActiveValue;
 use sea_orm::DatabaseConnection;
 use sea_orm::DbErr;
 use sea_orm::DeleteResult;
 use sea_orm::IntoActiveModel;
-use swaptun_models::{MusicActiveModel, MusicModel, PlaylistModel};
-use swaptun_repositories::MusicRepository;
+use swaptun_models::{
+    MusicActiveModel, MusicExternalIdActiveModel, MusicModel, PlaylistModel, PlaylistOrigin,
+};
+use swaptun_repositories::{MusicExternalIdRepository, MusicRepository};
 
 #[derive(Clone)]
 pub struct MusicService {
     pub music_repository: MusicRepository,
+    music_external_id_repository: MusicExternalIdRepository,
+    artist_service: ArtistService,
 }
 
 impl MusicService {
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
         Self {
-            music_repository: MusicRepository::new(db),
+            music_repository: MusicRepository::new(db.clone()),
+            music_external_id_repository: MusicExternalIdRepository::new(db.clone()),
+            artist_service: ArtistService::new(db),
         }
     }
 
@@ -26,13 +34,8 @@ impl MusicService {
         self.music_repository.find_all().await
     }
 
-    pub async fn find_by_id(
-        &self,
-        name: String,
-        artist: String,
-        album: String,
-    ) -> Result<Option<MusicModel>, DbErr> {
-        self.music_repository.find_by_id(name, artist, album).await
+    pub async fn find_by_id(&self, id: i32) -> Result<Option<MusicModel>, DbErr> {
+        self.music_repository.find_by_id(id).await
     }
 
     pub async fn _create(&self, model: MusicActiveModel) -> Result<MusicModel, DbErr> {
@@ -43,13 +46,8 @@ impl MusicService {
         self.music_repository.update(model).await
     }
 
-    pub async fn delete(
-        &self,
-        name: String,
-        artist: String,
-        album: String,
-    ) -> Result<DeleteResult, DbErr> {
-        self.music_repository.delete(name, artist, album).await
+    pub async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr> {
+        self.music_repository.delete(id).await
     }
 
     pub async fn find_by_playlist(
@@ -60,46 +58,209 @@ impl MusicService {
     }
 
     pub async fn create(&self, request: CreateMusicRequest) -> Result<MusicModel, DbErr> {
-        if let Some(music_model) = self
+        let credits = if request.artists.is_empty() {
+            ArtistCredit::parse(&request.artist)
+        } else {
+            request.artists
+        };
+        let artists = self.artist_service.resolve_credits(&credits).await?;
+        let album_id = self
+            .artist_service
+            .resolve_album(&request.album, &artists)
+            .await?
+            .map(|album| album.id);
+
+        let music = match self
             .music_repository
-            .find_by_id(
+            .find_by_title_artist_album(
                 request.title.clone(),
                 request.artist.clone(),
                 request.album.clone(),
             )
             .await?
         {
-            return Ok(music_model);
-        }
-        let new_music = MusicActiveModel {
-            title: ActiveValue::Set(request.title),
-            artist: ActiveValue::Set(request.artist),
-            album: ActiveValue::Set(request.album),
-            release_date: ActiveValue::Set(request.release_date),
-            genre: ActiveValue::Set(request.genre),
-
-            ..Default::default()
+            Some(music_model) => {
+                // Musics imported before these fields were captured get them on the next import
+                let mut active_model = music_model.clone().into_active_model();
+                let filled = [
+                    fill_missing(&mut active_model.album_id, album_id),
+                    fill_missing(&mut active_model.isrc, request.isrc),
+                    fill_missing(&mut active_model.duration_ms, request.duration_ms),
+                    fill_missing(&mut active_model.explicit, request.explicit),
+                    fill_missing(&mut active_model.artwork_url, request.artwork_url),
+                    fill_missing(&mut active_model.disc_number, request.disc_number),
+                    fill_missing(&mut active_model.track_number, request.track_number),
+                    fill_missing(&mut active_model.preview_url, request.preview_url),
+                ];
+                if filled.contains(&true) {
+                    self.music_repository.update(active_model).await?
+                } else {
+                    music_model
+                }
+            }
+            None => {
+                let new_music = MusicActiveModel {
+                    title: ActiveValue::Set(request.title),
+                    artist: ActiveValue::Set(request.artist),
+                    album: ActiveValue::Set(request.album),
+                    album_id: ActiveValue::Set(album_id),
+                    release_date: ActiveValue::Set(request.release_date),
+                    genre: ActiveValue::Set(request.genre),
+                    isrc: ActiveValue::Set(request.isrc),
+                    duration_ms: ActiveValue::Set(request.duration_ms),
+                    explicit: ActiveValue::Set(request.explicit),
+                    artwork_url: ActiveValue::Set(request.artwork_url),
+                    disc_number: ActiveValue::Set(request.disc_number),
+                    track_number: ActiveValue::Set(request.track_number),
+                    preview_url: ActiveValue::Set(request.preview_url),
+
+                    ..Default::default()
+                };
+                self.music_repository.create(new_music).await?
+            }
         };
 
-        self.music_repository.create(new_music).await
+        self.artist_service
+            .set_music_artists(&music, &artists)
+            .await?;
+        Ok(music)
     }
 
     pub async fn update(&self, request: UpdateMusicRequest) -> Result<MusicModel, DbErr> {
-        let mut existing_music = self
+        let existing_music = self
             .music_repository
-            .find_by_id(request.title, request.artist, request.album)
+            .find_by_id(request.id)
             .await?
             .ok_or(DbErr::Custom("Music not found".to_string()))?;
 
+        let album_changed = request
+            .album
+            .as_ref()
+            .is_some_and(|album| *album != existing_music.album);
+        let artist_changed = request
+            .artist
+            .as_ref()
+            .is_some_and(|artist| *artist != existing_music.artist);
+
+        // Fields must be `Set` on the active model, a model turned active as a whole is unchanged
+        let mut active_model = existing_music.into_active_model();
+        if let Some(title) = request.title {
+            active_model.title = ActiveValue::Set(title);
+        }
+        if let Some(artist) = request.artist {
+            active_model.artist = ActiveValue::Set(artist);
+        }
+        if let Some(album) = request.album {
+            active_model.album = ActiveValue::Set(album);
+        }
         if let Some(release_date) = request.release_date {
-            existing_music.release_date = release_date;
+            active_model.release_date = ActiveValue::Set(release_date);
         }
         if let Some(genre) = request.genre {
-            existing_music.genre = Some(genre);
+            active_model.genre = ActiveValue::Set(Some(genre));
         }
+        let mut music = self.music_repository.update(active_model).await?;
+
+        if artist_changed || album_changed {
+            let artists = self
+                .artist_service
+                .resolve_credits(&ArtistCredit::parse(&music.artist))
+                .await?;
+            if artist_changed {
+                self.artist_service
+                    .set_music_artists(&music, &artists)
+                    .await?;
+            }
+            let album_id = self
+                .artist_service
+                .resolve_album(&music.album, &artists)
+                .await?
+                .map(|album| album.id);
+            if album_id != music.album_id {
+                let mut active_model = music.into_active_model();
+                active_model.album_id = ActiveValue::Set(album_id);
+                music = self.music_repository.update(active_model).await?;
+            }
+        }
+
+        Ok(music)
+    }
+
+    /// Returns the track id of the music on the platform if an earlier sync discovered it
+    pub async fn find_external_id(
+        &self,
+        music: &MusicModel,
+        origin: PlaylistOrigin,
+    ) -> Result<Option<String>, DbErr> {
+        Ok(self
+            .music_external_id_repository
+            .find(music.id, origin)
+            .await?
+            .map(|model| model.external_id))
+    }
+
+    pub async fn save_external_id(
+        &self,
+        music_id: i32,
+        origin: PlaylistOrigin,
+        external_id: &str,
+    ) -> Result<(), DbErr> {
+        let existing = self
+            .music_external_id_repository
+            .find(music_id, origin.clone())
+            .await?;
+
+        let model = match existing {
+            Some(model) if model.external_id == external_id => return Ok(()),
+            Some(model) => {
+                let mut active_model = model.into_active_model();
+                active_model.external_id = ActiveValue::Set(external_id.to_string());
+                active_model.updated_on =
+                    ActiveValue::Set(Utc::now().with_timezone(&FixedOffset::east_opt(0).unwrap()));
+                active_model
+            }
+            None => MusicExternalIdActiveModel {
+                music_id: ActiveValue::Set(music_id),
+                origin: ActiveValue::Set(origin),
+                external_id: ActiveValue::Set(external_id.to_string()),
+                ..Default::default()
+            },
+        };
+        self.music_external_id_repository.save(model).await?;
+        Ok(())
+    }
+
+    /// Remembers the platform tracks found by the matcher so the next export skips the search.
+    /// Only exact matches such as ISRC lookups are kept, a cached track is reported with full
+    /// confidence and skips the ISRC search. The cache is shared by every user, so only
+    /// reports built by the matcher and sent by the user may be saved, never previews or
+    /// matches sent by a client.
+    pub async fn save_matched_external_ids(
+        &self,
+        report: &[TrackMatchEntry],
+        origin: PlaylistOrigin,
+    ) -> Result<(), DbErr> {
+        for entry in report {
+            if entry.status != TrackMatchStatus::Matched || entry.confidence != Some(1.0) {
+                continue;
+            }
+            if let Some(platform_track_id) = &entry.platform_track_id {
+                self.save_external_id(entry.music_id, origin.clone(), platform_track_id)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
 
-        self.music_repository
-            .update(existing_music.into_active_model())
-            .await
+/// Sets `field` to `value` when it is still empty, returns whether it changed
+fn fill_missing<T>(field: &mut ActiveValue<Option<T>>, value: Option<T>) -> bool
+where
+    Option<T>: Into<sea_orm::Value>,
+{
+    if value.is_none() || field.as_ref().is_some() {
+        return false;
     }
+    *field = ActiveValue::Set(value);
+    true
 }
@@ -0,0 +1,2 @@
+pub mod musicbrainz;
+pub use musicbrainz::*;
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+
+/// Recording found on MusicBrainz, `score` is the search relevance between 0 and 100
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackInfo {
+    pub title: String,
+    pub artist: String,
+    pub musicbrainz_id: String,
+    pub release_date: Option<String>,
+    pub genre: Option<String>,
+    pub score: u8,
+}
+
+/// Body of `GET /ws/2/recording?query=...`
+#[derive(Debug, Deserialize)]
+pub struct MusicBrainzRecordingSearch {
+    #[serde(default)]
+    pub recordings: Vec<MusicBrainzRecording>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MusicBrainzRecording {
+    pub id: String,
+    #[serde(default)]
+    pub score: u8,
+    pub title: String,
+    #[serde(rename = "artist-credit", default)]
+    pub artist_credit: Vec<MusicBrainzArtistCredit>,
+    #[serde(rename = "first-release-date")]
+    pub first_release_date: Option<String>,
+    #[serde(default)]
+    pub releases: Vec<MusicBrainzEntity>,
+    #[serde(default)]
+    pub tags: Vec<MusicBrainzTag>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MusicBrainzArtistCredit {
+    pub name: String,
+    #[serde(default)]
+    pub joinphrase: String,
+    pub artist: MusicBrainzEntity,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MusicBrainzEntity {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MusicBrainzTag {
+    pub name: String,
+    #[serde(default)]
+    pub count: i32,
+}
+
+/// Release or artist looked up with `inc=tags`, only the tags are read
+#[derive(Debug, Deserialize)]
+pub struct MusicBrainzTagged {
+    #[serde(default)]
+    pub tags: Vec<MusicBrainzTag>,
+}
+
+impl MusicBrainzRecording {
+    /// Credited artists as MusicBrainz displays them, e.g. "SDM feat. Werenoi"
+    pub fn artist_name(&self) -> String {
+        self.artist_credit
+            .iter()
+            .map(|credit| format!("{}{}", credit.name, credit.joinphrase))
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+
+    pub fn to_track_info(&self) -> TrackInfo {
+        TrackInfo {
+            title: self.title.clone(),
+            artist: self.artist_name(),
+            musicbrainz_id: self.id.clone(),
+            release_date: self.first_release_date.clone(),
+            genre: MusicBrainzTag::most_voted(&self.tags),
+            score: self.score,
+        }
+    }
+}
+
+impl MusicBrainzTag {
+    /// Name of the tag with the most votes, the first one wins a tie
+    pub fn most_voted(tags: &[MusicBrainzTag]) -> Option<String> {
+        tags.iter()
+            .fold(None, |best: Option<&MusicBrainzTag>, tag| match best {
+                Some(best) if best.count >= tag.count => Some(best),
+                _ => Some(tag),
+            })
+            .map(|tag| tag.name.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_search_deserializes_musicbrainz_payload() {
+        let json = r#"{
+            "created": "2025-10-27T10:00:00.000Z",
+            "count": 1,
+            "recordings": [{
+                "id": "b1a9c0e9-d987-4042-ae91-78d6a3267d69",
+                "score": 100,
+                "title": "Bolide allemand",
+                "artist-credit": [
+                    {"name": "SDM", "joinphrase": " feat. ", "artist": {"id": "a1", "name": "SDM"}},
+                    {"name": "Werenoi", "artist": {"id": "a2", "name": "Werenoi"}}
+                ],
+                "first-release-date": "2023-09-15",
+                "releases": [{"id": "r1", "title": "Liens du 100"}],
+                "tags": [{"count": 1, "name": "pop"}, {"count": 3, "name": "french hip hop"}]
+            }]
+        }"#;
+
+        let search: MusicBrainzRecordingSearch = serde_json::from_str(json).unwrap();
+        let track = search.recordings[0].to_track_info();
+
+        assert_eq!(track.title, "Bolide allemand");
+        assert_eq!(track.artist, "SDM feat. Werenoi");
+        assert_eq!(track.release_date.as_deref(), Some("2023-09-15"));
+        assert_eq!(track.genre.as_deref(), Some("french hip hop"));
+        assert_eq!(track.score, 100);
+        assert_eq!(search.recordings[0].releases[0].id, "r1");
+    }
+
+    #[test]
+    fn test_recording_without_optional_fields() {
+        let json = r#"{"id": "x", "title": "Untitled"}"#;
+
+        let recording: MusicBrainzRecording = serde_json::from_str(json).unwrap();
+        let track = recording.to_track_info();
+
+        assert_eq!(track.artist, "");
+        assert_eq!(track.genre, None);
+        assert_eq!(track.release_date, None);
+        assert_eq!(track.score, 0);
+    }
+}
@@ -2,3 +2,6 @@
 mod musicbrainz;
 #[cfg(feature = "full")]
 pub use musicbrainz::*;
+
+pub mod dto;
+pub use dto::*;
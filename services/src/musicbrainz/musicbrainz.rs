@@ -1,123 +1,241 @@
+use std::sync::Arc;
+
 use crate::error::AppError;
-use serde_json::Value;
-use ureq::Agent;
-
-pub struct MusicBrainzService;
-
-#[derive(Debug, serde::Serialize)]
-pub struct TrackInfo {
-    pub title: String,
-    pub artist: String,
-    pub musicbrainz_id: String,
-    pub release_date: Option<String>,
-    pub genre: Option<String>,
+use crate::{
+    MusicBrainzRecording, MusicBrainzRecordingSearch, MusicBrainzTag, MusicBrainzTagged, TrackInfo,
+};
+use log::{debug, error, warn};
+use once_cell::sync::Lazy;
+use sea_orm::{DatabaseConnection, Set};
+use serde::de::DeserializeOwned;
+use swaptun_models::MusicbrainzCacheActiveModel;
+use swaptun_repositories::MusicbrainzCacheRepository;
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration, Instant};
+
+const MUSICBRAINZ_API_URL: &str = "https://musicbrainz.org/ws/2";
+const USER_AGENT: &str = "Swaptun/1.0 (contact@swaptun.local)";
+const SEARCH_LIMIT: usize = 5;
+/// MusicBrainz rejects clients sending more than one request per second
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+const LUCENE_SPECIAL_CHARACTERS: &str = "+-&|!(){}[]^\"~*?:\\/";
+
+/// Time of the last request sent to MusicBrainz by this process, shared by every service instance
+static LAST_REQUEST: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Clone)]
+pub struct MusicBrainzService {
+    musicbrainz_cache_repository: MusicbrainzCacheRepository,
+    http_client: reqwest::Client,
+    base_url: String,
 }
 
 impl MusicBrainzService {
-    pub fn new() -> Self {
-        MusicBrainzService
+    /// Uses `MUSICBRAINZ_BASE_URL` when set, the public MusicBrainz API otherwise
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        let base_url = std::env::var("MUSICBRAINZ_BASE_URL")
+            .unwrap_or_else(|_| MUSICBRAINZ_API_URL.to_string());
+        Self::with_base_url(db, base_url)
+    }
+
+    pub fn with_base_url(db: Arc<DatabaseConnection>, base_url: impl Into<String>) -> Self {
+        MusicBrainzService {
+            musicbrainz_cache_repository: MusicbrainzCacheRepository::new(db),
+            http_client: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
     }
 
-    pub fn search_track(&self, title: &str, artist: &str) -> Result<TrackInfo, AppError> {
-        let agent = Agent::new();
-        let query = format!("recording:\"{}\" AND artist:\"{}\"", title, artist);
-        let url = format!(
-            "https://musicbrainz.org/ws/2/recording/?query={}&fmt=json&limit=1&inc=artist-credits+releases+tags",
-            query
+    /// Best recording for the given title and artist
+    pub async fn search_track(
+        &self,
+        title: &str,
+        artist: &str,
+    ) -> Result<Option<TrackInfo>, AppError> {
+        Ok(self
+            .search_recordings(title, artist)
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    /// Recordings matching the title and artist, best score first.
+    /// Results are cached so the same search never reaches MusicBrainz twice.
+    pub async fn search_recordings(
+        &self,
+        title: &str,
+        artist: &str,
+    ) -> Result<Vec<TrackInfo>, AppError> {
+        let title_key = title.trim().to_lowercase();
+        let artist_key = artist.trim().to_lowercase();
+
+        if let Some(cached) = self
+            .musicbrainz_cache_repository
+            .find(&title_key, &artist_key)
+            .await?
+        {
+            debug!("MusicBrainz cache hit for {} - {}", artist_key, title_key);
+            return serde_json::from_value(cached.candidates).map_err(|e| {
+                error!("Invalid MusicBrainz cache entry {}: {:?}", cached.id, e);
+                AppError::InternalServerError
+            });
+        }
+
+        let query = format!(
+            "recording:\"{}\" AND artist:\"{}\"",
+            escape_lucene(title.trim()),
+            escape_lucene(artist.trim())
         );
+        let limit = SEARCH_LIMIT.to_string();
+        let search: MusicBrainzRecordingSearch = self
+            .get(
+                "recording",
+                &[
+                    ("query", query.as_str()),
+                    ("limit", limit.as_str()),
+                    ("inc", "artist-credits+releases+tags"),
+                ],
+            )
+            .await?
+            .ok_or_else(|| AppError::InternalServerError)?;
+
+        let mut recordings = search.recordings;
+        recordings.sort_by(|a, b| b.score.cmp(&a.score));
+
+        let mut candidates: Vec<TrackInfo> = recordings
+            .iter()
+            .map(MusicBrainzRecording::to_track_info)
+            .collect();
+        if let (Some(best), Some(recording)) = (candidates.first_mut(), recordings.first()) {
+            if best.genre.is_none() {
+                best.genre = self.fallback_genre(recording).await?;
+            }
+        }
 
-        let res = agent
-            .get(&url)
-            .set("User-Agent", "Swaptun/1.0 (contact@swaptun.local)")
-            .call()
-            .map_err(|_| AppError::InternalServerError)?;
-
-        let reader = res.into_reader();
-        let data: Value =
-            serde_json::from_reader(reader).map_err(|_| AppError::InternalServerError)?;
-
-        let recording = &data["recordings"][0];
-
-        let title = recording["title"].as_str().unwrap_or_default().to_string();
-        let id = recording["id"].as_str().unwrap_or_default().to_string();
-        let artist = recording["artist-credit"][0]["artist"]["name"]
-            .as_str()
-            .unwrap_or_default()
-            .to_string();
-        let release_date = recording["first-release-date"]
-            .as_str()
-            .map(|s| s.to_string());
-
-        // 1. From recording
-        let mut genre = Self::extract_genre_from_tags(&recording["tags"]);
-        println!("le genre: {:?}", genre);
-
-        // 2. From release
-        if genre.is_none() {
-            println!("on teste release");
-            if let Some(release_id) = recording["releases"].get(0).and_then(|r| r["id"].as_str()) {
-                let release_url = format!(
-                    "https://musicbrainz.org/ws/2/release/{}?fmt=json&inc=tags",
-                    release_id
-                );
-                let release_res = agent
-                    .get(&release_url)
-                    .set("User-Agent", "Swaptun/1.0 (contact@swaptun.local)")
-                    .call()
-                    .map_err(|_| AppError::InternalServerError)?;
-                let release_data: Value = serde_json::from_reader(release_res.into_reader())
-                    .map_err(|_| AppError::InternalServerError)?;
-
-                genre = Self::extract_genre_from_tags(&release_data["tags"]);
+        let cache_entry = MusicbrainzCacheActiveModel {
+            title: Set(title_key),
+            artist: Set(artist_key),
+            candidates: Set(serde_json::to_value(&candidates).map_err(|e| {
+                error!("Failed to serialize MusicBrainz candidates: {:?}", e);
+                AppError::InternalServerError
+            })?),
+            ..Default::default()
+        };
+        // A concurrent search may have cached the same key already, the results are still valid
+        if let Err(e) = self.musicbrainz_cache_repository.save(cache_entry).await {
+            warn!("Failed to cache MusicBrainz search: {:?}", e);
+        }
+
+        Ok(candidates)
+    }
+
+    /// Genre of the first release, or of the main artist when the release has no tags
+    async fn fallback_genre(
+        &self,
+        recording: &MusicBrainzRecording,
+    ) -> Result<Option<String>, AppError> {
+        if let Some(release) = recording.releases.first() {
+            let path = format!("release/{}", release.id);
+            if let Some(release) = self
+                .get::<MusicBrainzTagged>(&path, &[("inc", "tags")])
+                .await?
+            {
+                if let Some(genre) = MusicBrainzTag::most_voted(&release.tags) {
+                    return Ok(Some(genre));
+                }
             }
-            println!("le genre: {:?}", genre);
         }
 
-        // 3. From artist
-        if genre.is_none() {
-            println!("on teste artist");
-            if let Some(artist_id) = recording["artist-credit"][0]["artist"]["id"].as_str() {
-                let artist_url = format!(
-                    "https://musicbrainz.org/ws/2/artist/{}?fmt=json&inc=tags",
-                    artist_id
-                );
-                let artist_res = agent
-                    .get(&artist_url)
-                    .set("User-Agent", "Swaptun/1.0 (contact@swaptun.local)")
-                    .call()
-                    .map_err(|_| AppError::InternalServerError)?;
-                let artist_data: Value = serde_json::from_reader(artist_res.into_reader())
-                    .map_err(|_| AppError::InternalServerError)?;
-
-                genre = Self::extract_genre_from_tags(&artist_data["tags"]);
+        if let Some(credit) = recording.artist_credit.first() {
+            let path = format!("artist/{}", credit.artist.id);
+            if let Some(artist) = self
+                .get::<MusicBrainzTagged>(&path, &[("inc", "tags")])
+                .await?
+            {
+                return Ok(MusicBrainzTag::most_voted(&artist.tags));
             }
-            println!("genre :{:?}", genre);
         }
 
-        Ok(TrackInfo {
-            title,
-            artist,
-            musicbrainz_id: id,
-            release_date,
-            genre,
+        Ok(None)
+    }
+
+    /// Sends a rate limited request to the API, `None` when the resource does not exist
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+    ) -> Result<Option<T>, AppError> {
+        wait_for_rate_limit().await;
+
+        let url = format!("{}/{}", self.base_url, path);
+        let response = self
+            .http_client
+            .get(&url)
+            .header(reqwest::header::USER_AGENT, USER_AGENT)
+            .query(params)
+            .query(&[("fmt", "json")])
+            .send()
+            .await
+            .map_err(|e| {
+                error!("MusicBrainz request to {} failed: {:?}", url, e);
+                AppError::InternalServerError
+            })?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            error!("MusicBrainz answered {} for {}", response.status(), url);
+            return Err(AppError::InternalServerError);
+        }
+
+        response.json::<T>().await.map(Some).map_err(|e| {
+            error!("Invalid MusicBrainz response for {}: {:?}", url, e);
+            AppError::InternalServerError
         })
     }
+}
 
-    fn extract_genre_from_tags(tags: &Value) -> Option<String> {
-        tags.as_array()?
-            .iter()
-            .filter_map(|tag| tag.get("name").and_then(|n| n.as_str()))
-            .next()
-            .map(|s| s.to_string())
+/// Holds the lock while sleeping so concurrent callers are released one per interval
+async fn wait_for_rate_limit() {
+    let mut last_request = LAST_REQUEST.lock().await;
+    if let Some(last) = *last_request {
+        let elapsed = last.elapsed();
+        if elapsed < MIN_REQUEST_INTERVAL {
+            sleep(MIN_REQUEST_INTERVAL - elapsed).await;
+        }
+    }
+    *last_request = Some(Instant::now());
+}
+
+/// Escapes the characters Lucene gives a meaning to, so user input is searched literally
+pub fn escape_lucene(term: &str) -> String {
+    let mut escaped = String::with_capacity(term.len());
+    for c in term.chars() {
+        if LUCENE_SPECIAL_CHARACTERS.contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
     }
+    escaped
 }
 
-pub async fn get_track_metadata(title: &str, artist: &str) -> Result<Option<TrackInfo>, AppError> {
-    let service = MusicBrainzService::new();
-    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-    match service.search_track(title, artist) {
-        Ok(track_info) => Ok(Some(track_info)),
-        Err(AppError::InternalServerError) => Ok(None), // Pas trouvé
-        Err(e) => Err(e),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_lucene_escapes_special_characters() {
+        assert_eq!(escape_lucene("AC/DC"), "AC\\/DC");
+        assert_eq!(escape_lucene("Say \"Hi\"!"), "Say \\\"Hi\\\"\\!");
+        assert_eq!(escape_lucene("(What's) up?"), "\\(What's\\) up\\?");
+        assert_eq!(escape_lucene("a\\b"), "a\\\\b");
+    }
+
+    #[test]
+    fn test_escape_lucene_keeps_plain_text() {
+        assert_eq!(escape_lucene("Bolide allemand"), "Bolide allemand");
+        assert_eq!(escape_lucene("Beyoncé"), "Beyoncé");
     }
 }
@@ -1,3 +1,9 @@
+pub mod playlist_diff;
+pub mod playlist_mirror;
 pub mod playlist_request;
+pub mod playlist_snapshot;
 
+pub use playlist_diff::*;
+pub use playlist_mirror::*;
 pub use playlist_request::*;
+pub use playlist_snapshot::*;
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+/// Changes bringing the tracks of a platform playlist from `current` to `desired`.
+/// Repeated tracks are counted, so a track listed twice but wanted once is removed once.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlaylistDiff {
+    /// Tracks to append, in the order of the desired playlist
+    pub to_add: Vec<String>,
+    /// Tracks to drop, once per extra occurrence
+    pub to_remove: Vec<String>,
+    /// Whether the tracks are still out of order once added and removed
+    pub reorder: bool,
+}
+
+impl PlaylistDiff {
+    pub fn between(current: &[String], desired: &[String]) -> Self {
+        let mut wanted = count(desired);
+        let mut kept = Vec::new();
+        let mut to_remove = Vec::new();
+        for track in current {
+            match wanted.get_mut(track.as_str()) {
+                Some(remaining) if *remaining > 0 => {
+                    *remaining -= 1;
+                    kept.push(track.clone());
+                }
+                _ => to_remove.push(track.clone()),
+            }
+        }
+
+        let mut present = count(&kept);
+        let to_add: Vec<String> = desired
+            .iter()
+            .filter(|track| match present.get_mut(track.as_str()) {
+                Some(remaining) if *remaining > 0 => {
+                    *remaining -= 1;
+                    false
+                }
+                _ => true,
+            })
+            .cloned()
+            .collect();
+
+        kept.extend(to_add.iter().cloned());
+        PlaylistDiff {
+            to_add,
+            to_remove,
+            reorder: kept != desired,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.to_add.is_empty() && self.to_remove.is_empty() && !self.reorder
+    }
+}
+
+fn count(tracks: &[String]) -> HashMap<&str, usize> {
+    let mut counts = HashMap::new();
+    for track in tracks {
+        *counts.entry(track.as_str()).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(tracks: &[&str]) -> Vec<String> {
+        tracks.iter().map(|track| track.to_string()).collect()
+    }
+
+    #[test]
+    fn test_same_playlist_has_no_changes() {
+        let tracks = ids(&["a", "b", "c"]);
+        assert!(PlaylistDiff::between(&tracks, &tracks).is_empty());
+    }
+
+    #[test]
+    fn test_missing_tracks_are_appended() {
+        let diff = PlaylistDiff::between(&ids(&["a", "b"]), &ids(&["a", "b", "c", "d"]));
+        assert_eq!(diff.to_add, ids(&["c", "d"]));
+        assert!(diff.to_remove.is_empty());
+        assert!(!diff.reorder);
+    }
+
+    #[test]
+    fn test_dropped_tracks_are_removed() {
+        let diff = PlaylistDiff::between(&ids(&["a", "b", "c"]), &ids(&["a", "c"]));
+        assert!(diff.to_add.is_empty());
+        assert_eq!(diff.to_remove, ids(&["b"]));
+        assert!(!diff.reorder);
+    }
+
+    #[test]
+    fn test_moved_tracks_need_a_reorder() {
+        let diff = PlaylistDiff::between(&ids(&["a", "b", "c"]), &ids(&["c", "a", "b"]));
+        assert!(diff.to_add.is_empty());
+        assert!(diff.to_remove.is_empty());
+        assert!(diff.reorder);
+    }
+
+    #[test]
+    fn test_added_track_in_the_middle_needs_a_reorder() {
+        let diff = PlaylistDiff::between(&ids(&["a", "c"]), &ids(&["a", "b", "c"]));
+        assert_eq!(diff.to_add, ids(&["b"]));
+        assert!(diff.reorder);
+    }
+
+    #[test]
+    fn test_repeated_tracks_are_counted() {
+        let diff = PlaylistDiff::between(&ids(&["a", "a", "b"]), &ids(&["a", "b", "b"]));
+        assert_eq!(diff.to_remove, ids(&["a"]));
+        assert_eq!(diff.to_add, ids(&["b"]));
+        assert!(!diff.reorder);
+    }
+}
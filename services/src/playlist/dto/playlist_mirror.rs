@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use swaptun_models::{PlaylistModel, PlaylistOrigin};
+use validator::Validate;
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct CreatePlaylistMirrorRequest {
+    pub playlist_id: i32,
+    pub destination: PlaylistOrigin,
+    /// Playlist already on the destination to keep in sync. When absent the playlist of the
+    /// previous send is used, or a new one is created.
+    #[serde(default)]
+    #[validate(length(min = 1, message = "Platform playlist id cannot be empty"))]
+    pub platform_playlist_id: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PlaylistMirror {
+    pub id: i32,
+    pub playlist: PlaylistModel,
+    pub destination: PlaylistOrigin,
+    /// Playlist kept in sync on the destination, unknown until the first push is done
+    pub platform_playlist_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_mirror_request_without_platform_playlist() {
+        let json = r#"{"playlist_id":7,"destination":"AppleMusic"}"#;
+        let request: CreatePlaylistMirrorRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.playlist_id, 7);
+        assert_eq!(request.destination, PlaylistOrigin::AppleMusic);
+        assert_eq!(request.platform_playlist_id, None);
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_create_mirror_request_rejects_empty_platform_playlist() {
+        let request = CreatePlaylistMirrorRequest {
+            playlist_id: 7,
+            destination: PlaylistOrigin::Spotify,
+            platform_playlist_id: Some(String::new()),
+        };
+
+        assert!(request.validate().is_err());
+    }
+}
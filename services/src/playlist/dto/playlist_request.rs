@@ -1,8 +1,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use swaptun_models::{PlaylistModel, PlaylistOrigin, UserModel};
+use swaptun_models::{MusicModel, PlaylistModel, PlaylistOrigin, UserModel};
 use validator::Validate;
 
+/// `origin_id` of the playlist holding the tracks the user liked on the platform
+pub const LIKED_SONGS_ORIGIN_ID: &str = "liked_songs";
+pub const LIKED_SONGS_NAME: &str = "Liked Songs";
+
 #[derive(Deserialize, Serialize, Validate)]
 pub struct CreatePlaylistRequest {
     pub name: String,
@@ -12,6 +16,20 @@ pub struct CreatePlaylistRequest {
     pub image_url: Option<String>,
 }
 
+impl CreatePlaylistRequest {
+    /// Playlist holding the tracks the user liked or saved to their library on `origin`,
+    /// there is one per platform
+    pub fn liked_songs(origin: PlaylistOrigin) -> Self {
+        CreatePlaylistRequest {
+            name: LIKED_SONGS_NAME.to_string(),
+            description: None,
+            origin,
+            origin_id: LIKED_SONGS_ORIGIN_ID.to_string(),
+            image_url: None,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Validate)]
 pub struct UpdatePlaylistRequest {
     pub name: Option<String>,
@@ -54,15 +72,116 @@ pub struct GetPlaylistMusicsResponse {
     pub musics: Vec<swaptun_models::MusicModel>,
 }
 
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct MoveMusicRequest {
+    /// Zero-based position the music should end up at
+    #[validate(range(min = 0, message = "Position cannot be negative"))]
+    pub position: i32,
+    /// Which copy of the music to move when it appears several times, the first by default
+    #[serde(default)]
+    #[validate(range(min = 0, message = "Occurrence cannot be negative"))]
+    pub occurrence: i32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct RemoveMusicParams {
+    /// Which copy of the music to remove when it appears several times, the first by default
+    #[serde(default)]
+    pub occurrence: usize,
+}
+
 #[derive(Deserialize, Serialize, Validate, Debug)]
 pub struct SendPlaylistRequest {
     pub destination: PlaylistOrigin,
+    /// Matches confirmed from a preview. When present no search is done and only the
+    /// entries with a platform track id are added to the new playlist.
+    #[serde(default)]
+    pub matches: Option<Vec<TrackMatchEntry>>,
+    /// Creates another playlist on the destination instead of updating the one created by
+    /// the previous send
+    #[serde(default)]
+    pub create_new: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SendPlaylistResponse {
     pub platform: PlaylistOrigin,
     pub playlist_id: String,
+    #[serde(default)]
+    pub report: Vec<TrackMatchEntry>,
+    /// Whether the playlist of a previous send was updated rather than a new one created
+    #[serde(default)]
+    pub updated: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SendPlaylistPreviewResponse {
+    pub platform: PlaylistOrigin,
+    pub report: Vec<TrackMatchEntry>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum TrackMatchStatus {
+    Matched,
+    NotFound,
+    Ambiguous,
+    /// The user picked this platform track themselves
+    Overridden,
+}
+
+/// Outcome of looking for one of our tracks on the destination platform
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct TrackMatchEntry {
+    pub music_id: i32,
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub status: TrackMatchStatus,
+    pub platform_track_id: Option<String>,
+    pub platform_title: Option<String>,
+    pub platform_artist: Option<String>,
+    pub confidence: Option<f64>,
+}
+
+impl TrackMatchEntry {
+    pub fn not_found(music: &MusicModel) -> Self {
+        TrackMatchEntry {
+            music_id: music.id,
+            title: music.title.clone(),
+            artist: music.artist.clone(),
+            album: music.album.clone(),
+            status: TrackMatchStatus::NotFound,
+            platform_track_id: None,
+            platform_title: None,
+            platform_artist: None,
+            confidence: None,
+        }
+    }
+
+    pub fn overridden(music: &MusicModel, platform_track_id: &str) -> Self {
+        TrackMatchEntry {
+            status: TrackMatchStatus::Overridden,
+            platform_track_id: Some(platform_track_id.to_string()),
+            confidence: Some(1.0),
+            ..TrackMatchEntry::not_found(music)
+        }
+    }
+
+    /// Entry for a music whose platform track id is already known from an earlier sync
+    pub fn known(music: &MusicModel, platform_track_id: &str) -> Self {
+        TrackMatchEntry {
+            status: TrackMatchStatus::Matched,
+            platform_track_id: Some(platform_track_id.to_string()),
+            platform_title: Some(music.title.clone()),
+            platform_artist: Some(music.artist.clone()),
+            confidence: Some(1.0),
+            ..TrackMatchEntry::not_found(music)
+        }
+    }
+
+    pub fn is_found(&self) -> bool {
+        self.platform_track_id.is_some()
+    }
 }
 
 #[derive(Deserialize, Serialize, Validate, Debug)]
@@ -102,11 +221,24 @@ impl From<UserModel> for UserInfo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_liked_songs_playlist_is_one_per_origin() {
+        let spotify = CreatePlaylistRequest::liked_songs(PlaylistOrigin::Spotify);
+        let apple = CreatePlaylistRequest::liked_songs(PlaylistOrigin::AppleMusic);
+
+        assert_eq!(spotify.origin_id, LIKED_SONGS_ORIGIN_ID);
+        assert_eq!(spotify.origin_id, apple.origin_id);
+        assert_eq!(spotify.name, LIKED_SONGS_NAME);
+        assert_eq!(apple.origin, PlaylistOrigin::AppleMusic);
+    }
+
     #[test]
     fn test_send_playlist_response_serialization() {
         let response = SendPlaylistResponse {
             platform: PlaylistOrigin::Spotify,
             playlist_id: "test_id_123".to_string(),
+            report: vec![],
+            updated: false,
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -121,6 +253,7 @@ mod tests {
 
         assert_eq!(response.platform, PlaylistOrigin::YoutubeMusic);
         assert_eq!(response.playlist_id, "yt_playlist_456");
+        assert!(response.report.is_empty());
     }
 
     #[test]
@@ -129,12 +262,15 @@ mod tests {
             PlaylistOrigin::Spotify,
             PlaylistOrigin::AppleMusic,
             PlaylistOrigin::YoutubeMusic,
+            PlaylistOrigin::Deezer,
         ];
 
         for platform in platforms {
             let response = SendPlaylistResponse {
                 platform: platform.clone(),
                 playlist_id: format!("{:?}_test_id", platform),
+                report: vec![],
+                updated: false,
             };
 
             let json = serde_json::to_string(&response).unwrap();
@@ -145,6 +281,116 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_send_playlist_response_with_report() {
+        use chrono::NaiveDate;
+
+        let music = MusicModel {
+            id: 1,
+            title: "Test Song".to_string(),
+            artist: "Test Artist".to_string(),
+            album: "Test Album".to_string(),
+            album_id: None,
+            release_date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            genre: None,
+            isrc: None,
+            duration_ms: None,
+            explicit: None,
+            artwork_url: None,
+            disc_number: None,
+            track_number: None,
+            preview_url: None,
+            musicbrainz_id: None,
+            enriched_on: None,
+            enrichment_attempts: 0,
+            enrichment_failed_on: None,
+        };
+        let matched = TrackMatchEntry {
+            status: TrackMatchStatus::Matched,
+            platform_track_id: Some("track_1".to_string()),
+            platform_title: Some("Test Song".to_string()),
+            platform_artist: Some("Test Artist".to_string()),
+            confidence: Some(0.98),
+            ..TrackMatchEntry::not_found(&music)
+        };
+
+        let response = SendPlaylistResponse {
+            platform: PlaylistOrigin::Deezer,
+            playlist_id: "deezer_1".to_string(),
+            report: vec![matched, TrackMatchEntry::not_found(&music)],
+            updated: false,
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        let deserialized: SendPlaylistResponse = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized.report.len(), 2);
+        assert_eq!(deserialized.report[0].status, TrackMatchStatus::Matched);
+        assert!(deserialized.report[0].is_found());
+        assert_eq!(deserialized.report[1].status, TrackMatchStatus::NotFound);
+        assert!(!deserialized.report[1].is_found());
+        assert!(json.contains("\"status\":\"NotFound\""));
+    }
+
+    #[test]
+    fn test_send_playlist_request_without_matches() {
+        let json = r#"{"destination":"Spotify"}"#;
+        let request: SendPlaylistRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.destination, PlaylistOrigin::Spotify);
+        assert!(request.matches.is_none());
+        assert!(!request.create_new);
+    }
+
+    #[test]
+    fn test_send_playlist_request_with_confirmed_matches() {
+        let json = r#"{
+            "destination":"Deezer",
+            "matches":[{
+                "music_id":1,"title":"Song","artist":"Artist","album":"Album","status":"Matched",
+                "platform_track_id":"3135556","platform_title":"Song",
+                "platform_artist":"Artist","confidence":0.97
+            }]
+        }"#;
+        let request: SendPlaylistRequest = serde_json::from_str(json).unwrap();
+
+        let matches = request.matches.unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].platform_track_id.as_deref(), Some("3135556"));
+    }
+
+    #[test]
+    fn test_track_match_entry_known() {
+        use chrono::NaiveDate;
+
+        let music = MusicModel {
+            id: 1,
+            title: "Test Song".to_string(),
+            artist: "Test Artist".to_string(),
+            album: "Test Album".to_string(),
+            album_id: None,
+            release_date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            genre: None,
+            isrc: None,
+            duration_ms: None,
+            explicit: None,
+            artwork_url: None,
+            disc_number: None,
+            track_number: None,
+            preview_url: None,
+            musicbrainz_id: None,
+            enriched_on: None,
+            enrichment_attempts: 0,
+            enrichment_failed_on: None,
+        };
+        let entry = TrackMatchEntry::known(&music, "track_1");
+
+        assert_eq!(entry.status, TrackMatchStatus::Matched);
+        assert_eq!(entry.platform_track_id.as_deref(), Some("track_1"));
+        assert_eq!(entry.platform_title.as_deref(), Some("Test Song"));
+        assert_eq!(entry.confidence, Some(1.0));
+    }
+
     #[test]
     fn test_get_playlists_params_default_include_musics() {
         let json = r#"{"origin":null}"#;
@@ -169,11 +415,24 @@ mod tests {
         use swaptun_models::MusicModel;
 
         let music = MusicModel {
+            id: 1,
             title: "Test Song".to_string(),
             artist: "Test Artist".to_string(),
             album: "Test Album".to_string(),
+            album_id: None,
             release_date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
             genre: Some("Rock".to_string()),
+            isrc: None,
+            duration_ms: None,
+            explicit: None,
+            artwork_url: None,
+            disc_number: None,
+            track_number: None,
+            preview_url: None,
+            musicbrainz_id: None,
+            enriched_on: None,
+            enrichment_attempts: 0,
+            enrichment_failed_on: None,
         };
 
         let response = GetPlaylistMusicsResponse {
@@ -202,6 +461,7 @@ mod tests {
             created_on: Utc::now().with_timezone(&FixedOffset::east_opt(0).unwrap()),
             updated_on: Utc::now().with_timezone(&FixedOffset::east_opt(0).unwrap()),
             image_url: None,
+            last_synced_on: None,
         };
 
         let playlist_with_musics = Playlist {
@@ -229,14 +489,28 @@ mod tests {
             created_on: Utc::now().with_timezone(&FixedOffset::east_opt(0).unwrap()),
             updated_on: Utc::now().with_timezone(&FixedOffset::east_opt(0).unwrap()),
             image_url: None,
+            last_synced_on: None,
         };
 
         let music = MusicModel {
+            id: 1,
             title: "Test Song".to_string(),
             artist: "Test Artist".to_string(),
             album: "Test Album".to_string(),
+            album_id: None,
             release_date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
             genre: Some("Rock".to_string()),
+            isrc: None,
+            duration_ms: None,
+            explicit: None,
+            artwork_url: None,
+            disc_number: None,
+            track_number: None,
+            preview_url: None,
+            musicbrainz_id: None,
+            enriched_on: None,
+            enrichment_attempts: 0,
+            enrichment_failed_on: None,
         };
 
         let playlist_with_musics = Playlist {
@@ -266,6 +540,7 @@ mod tests {
             created_on: Utc::now().with_timezone(&FixedOffset::east_opt(0).unwrap()),
             updated_on: Utc::now().with_timezone(&FixedOffset::east_opt(0).unwrap()),
             image_url: None,
+            last_synced_on: None,
         };
 
         let response = GetPlaylistResponse {
@@ -279,4 +554,21 @@ mod tests {
         assert!(json.contains("playlists"));
         assert!(json.contains("Test Playlist"));
     }
+
+    #[test]
+    fn test_move_music_request_rejects_negative_position() {
+        let request: MoveMusicRequest = serde_json::from_str(r#"{"position":-1}"#).unwrap();
+        assert!(request.validate().is_err());
+
+        let request: MoveMusicRequest = serde_json::from_str(r#"{"position":0}"#).unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.occurrence, 0);
+    }
+
+    #[test]
+    fn test_move_music_request_rejects_negative_occurrence() {
+        let request: MoveMusicRequest =
+            serde_json::from_str(r#"{"position":2,"occurrence":-1}"#).unwrap();
+        assert!(request.validate().is_err());
+    }
 }
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use swaptun_models::{MusicModel, SnapshotReason};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PlaylistSnapshot {
+    pub id: i32,
+    pub playlist_id: i32,
+    pub reason: SnapshotReason,
+    pub name: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    /// Ids of the musics of the playlist, in order
+    pub music_ids: Vec<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SnapshotDiffParams {
+    pub from: i32,
+    pub to: i32,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SnapshotDiffResponse {
+    pub from: i32,
+    pub to: i32,
+    pub added: Vec<MusicModel>,
+    pub removed: Vec<MusicModel>,
+    pub moved: Vec<MusicModel>,
+}
+
+/// Musics added, removed and moved between two versions of a playlist, by id.
+/// Repeated musics are counted, so a music listed once more is added once.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SnapshotDiff {
+    /// In the order of the newer version
+    pub added: Vec<i32>,
+    /// In the order of the older version
+    pub removed: Vec<i32>,
+    /// Fewest musics that have to move to get the order of the newer version, in its order
+    pub moved: Vec<i32>,
+}
+
+impl SnapshotDiff {
+    pub fn between(from: &[i32], to: &[i32]) -> Self {
+        let (kept_from, removed) = split_extra(from, to);
+        let (kept_to, added) = split_extra(to, &kept_from);
+
+        // Occurrences are told apart, so each entry of `kept_to` has one place in `kept_from`
+        let mut places: HashMap<(i32, usize), usize> = HashMap::new();
+        let mut seen: HashMap<i32, usize> = HashMap::new();
+        for (index, id) in kept_from.iter().enumerate() {
+            let occurrence = seen.entry(*id).or_insert(0);
+            places.insert((*id, *occurrence), index);
+            *occurrence += 1;
+        }
+        seen.clear();
+        let order: Vec<usize> = kept_to
+            .iter()
+            .map(|id| {
+                let occurrence = seen.entry(*id).or_insert(0);
+                *occurrence += 1;
+                places[&(*id, *occurrence - 1)]
+            })
+            .collect();
+
+        let in_place = longest_increasing(&order);
+        let moved = kept_to
+            .iter()
+            .zip(in_place)
+            .filter(|(_, in_place)| !in_place)
+            .map(|(id, _)| *id)
+            .collect();
+
+        SnapshotDiff {
+            added,
+            removed,
+            moved,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.moved.is_empty()
+    }
+}
+
+/// Splits `ids` into the entries also found in `other` and the extra ones
+fn split_extra(ids: &[i32], other: &[i32]) -> (Vec<i32>, Vec<i32>) {
+    let mut available: HashMap<i32, usize> = HashMap::new();
+    for id in other {
+        *available.entry(*id).or_insert(0) += 1;
+    }
+
+    let mut kept = Vec::new();
+    let mut extra = Vec::new();
+    for id in ids {
+        match available.get_mut(id) {
+            Some(remaining) if *remaining > 0 => {
+                *remaining -= 1;
+                kept.push(*id);
+            }
+            _ => extra.push(*id),
+        }
+    }
+    (kept, extra)
+}
+
+/// Flags the values belonging to one of the longest increasing subsequences
+fn longest_increasing(values: &[usize]) -> Vec<bool> {
+    // `tails[length]` is the index of the smallest value ending a subsequence of `length + 1`
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; values.len()];
+    for (index, value) in values.iter().enumerate() {
+        let length = tails.partition_point(|tail| values[*tail] < *value);
+        previous[index] = length.checked_sub(1).map(|length| tails[length]);
+        if length == tails.len() {
+            tails.push(index);
+        } else {
+            tails[length] = index;
+        }
+    }
+
+    let mut flags = vec![false; values.len()];
+    let mut current = tails.last().copied();
+    while let Some(index) = current {
+        flags[index] = true;
+        current = previous[index];
+    }
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_version_has_no_changes() {
+        assert!(SnapshotDiff::between(&[1, 2, 3], &[1, 2, 3]).is_empty());
+    }
+
+    #[test]
+    fn test_added_and_removed_musics() {
+        let diff = SnapshotDiff::between(&[1, 2, 3], &[1, 3, 4]);
+
+        assert_eq!(diff.added, vec![4]);
+        assert_eq!(diff.removed, vec![2]);
+        assert!(diff.moved.is_empty());
+    }
+
+    #[test]
+    fn test_only_the_moved_music_is_reported() {
+        let diff = SnapshotDiff::between(&[1, 2, 3, 4, 5], &[5, 1, 2, 3, 4]);
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.moved, vec![5]);
+    }
+
+    #[test]
+    fn test_repeated_musics_are_counted() {
+        let diff = SnapshotDiff::between(&[1, 2, 1], &[1, 2]);
+
+        assert_eq!(diff.removed, vec![1]);
+        assert!(diff.added.is_empty());
+        assert!(diff.moved.is_empty());
+
+        let diff = SnapshotDiff::between(&[1], &[1, 1]);
+        assert_eq!(diff.added, vec![1]);
+    }
+
+    #[test]
+    fn test_reversed_playlist_moves_all_but_one() {
+        let diff = SnapshotDiff::between(&[1, 2, 3], &[3, 2, 1]);
+
+        assert_eq!(diff.moved.len(), 2);
+    }
+}
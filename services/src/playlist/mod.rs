@@ -1,6 +1,18 @@
 pub mod dto;
 pub use dto::*;
 #[cfg(feature = "full")]
+pub mod playlist_export_service;
+#[cfg(feature = "full")]
+pub use playlist_export_service::*;
+#[cfg(feature = "full")]
+pub mod playlist_mirror_service;
+#[cfg(feature = "full")]
+pub use playlist_mirror_service::*;
+#[cfg(feature = "full")]
 pub mod playlist_service;
 #[cfg(feature = "full")]
 pub use playlist_service::*;
+#[cfg(feature = "full")]
+pub mod playlist_snapshot_service;
+#[cfg(feature = "full")]
+pub use playlist_snapshot_service::*;
@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use log::{error, info};
+use sea_orm::{ActiveValue::Set, DatabaseConnection, IntoActiveModel};
+use swaptun_models::{PlaylistExportActiveModel, PlaylistExportModel, PlaylistOrigin, UserModel};
+use swaptun_repositories::{PlaylistExportRepository, PlaylistRepository};
+
+use crate::error::AppError;
+use crate::{JobProgress, MusicProvider, MusicService, SendPlaylistResponse, TrackMatchEntry};
+
+/// Remembers the platform playlist each of our playlists was sent to, so that sending it
+/// again updates that playlist instead of creating a duplicate
+#[derive(Clone)]
+pub struct PlaylistExportService {
+    playlist_export_repository: PlaylistExportRepository,
+    playlist_repository: PlaylistRepository,
+    music_service: MusicService,
+}
+
+impl PlaylistExportService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        PlaylistExportService {
+            playlist_export_repository: PlaylistExportRepository::new(db.clone()),
+            playlist_repository: PlaylistRepository::new(db.clone()),
+            music_service: MusicService::new(db),
+        }
+    }
+
+    pub async fn get_export(
+        &self,
+        playlist_id: i32,
+        user_id: i32,
+        destination: PlaylistOrigin,
+    ) -> Result<Option<PlaylistExportModel>, AppError> {
+        Ok(self
+            .playlist_export_repository
+            .find(playlist_id, user_id, destination)
+            .await?)
+    }
+
+    /// Makes the next send of the playlist update `platform_playlist_id`, a playlist the user
+    /// already has on the destination
+    pub async fn link(
+        &self,
+        playlist_id: i32,
+        user_id: i32,
+        destination: PlaylistOrigin,
+        platform_playlist_id: &str,
+    ) -> Result<(), AppError> {
+        let previous = self
+            .get_export(playlist_id, user_id, destination.clone())
+            .await?;
+        self.record(
+            previous,
+            playlist_id,
+            user_id,
+            destination,
+            platform_playlist_id,
+        )
+        .await
+    }
+
+    /// Sends the matched tracks of one of our playlists to the provider's platform. The
+    /// playlist created by the previous send is updated unless `create_new` is set or it was
+    /// deleted on the platform since. With `cache_matches` the matched tracks are saved for the
+    /// next exports of every user, only for reports built by the matcher.
+    pub async fn export(
+        &self,
+        provider: &dyn MusicProvider,
+        user: &UserModel,
+        playlist_id: i32,
+        report: Vec<TrackMatchEntry>,
+        create_new: bool,
+        cache_matches: bool,
+        progress: &JobProgress,
+    ) -> Result<SendPlaylistResponse, AppError> {
+        let destination = provider.origin();
+        let playlist = self
+            .playlist_repository
+            .find_by_id(playlist_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Playlist with id {} not found", playlist_id))
+            })?;
+        let found = report.iter().filter(|entry| entry.is_found()).count();
+        progress.start_playlist(&playlist.name, found).await;
+
+        let previous = self
+            .get_export(playlist_id, user.id, destination.clone())
+            .await?;
+
+        let updated_export = match previous.as_ref().filter(|_| !create_new) {
+            Some(previous) => {
+                match provider
+                    .update_playlist_from_matches(
+                        user,
+                        &previous.platform_playlist_id,
+                        report.clone(),
+                        progress,
+                    )
+                    .await
+                {
+                    Ok(export) => Some(export),
+                    Err(AppError::NotFound(_)) => {
+                        info!(
+                            "Playlist {} was deleted from {:?}, creating a new one",
+                            previous.platform_playlist_id, destination
+                        );
+                        None
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            None => None,
+        };
+        let (export, updated) = match updated_export {
+            Some(export) => (export, true),
+            None => (
+                provider
+                    .create_playlist_from_matches(user, playlist_id, report, progress)
+                    .await?,
+                false,
+            ),
+        };
+
+        progress.finish_playlist().await;
+
+        // Nothing is created on the platform for a playlist without any matched track
+        if !export.playlist_id.is_empty() {
+            self.record(
+                previous,
+                playlist_id,
+                user.id,
+                destination.clone(),
+                &export.playlist_id,
+            )
+            .await?;
+        }
+
+        if cache_matches {
+            if let Err(e) = self
+                .music_service
+                .save_matched_external_ids(&export.report, destination.clone())
+                .await
+            {
+                error!("Error saving {:?} track ids: {:?}", destination, e);
+            }
+        }
+
+        Ok(SendPlaylistResponse {
+            platform: destination,
+            playlist_id: export.playlist_id,
+            report: export.report,
+            updated,
+        })
+    }
+
+    async fn record(
+        &self,
+        previous: Option<PlaylistExportModel>,
+        playlist_id: i32,
+        user_id: i32,
+        destination: PlaylistOrigin,
+        platform_playlist_id: &str,
+    ) -> Result<(), AppError> {
+        let now = Utc::now();
+        let active_model = match previous {
+            Some(previous) => {
+                let mut active_model = previous.into_active_model();
+                active_model.platform_playlist_id = Set(platform_playlist_id.to_string());
+                active_model.updated_on = Set(now.into());
+                active_model
+            }
+            None => PlaylistExportActiveModel {
+                playlist_id: Set(playlist_id),
+                user_id: Set(user_id),
+                destination: Set(destination),
+                platform_playlist_id: Set(platform_playlist_id.to_string()),
+                created_on: Set(now.into()),
+                updated_on: Set(now.into()),
+                ..Default::default()
+            },
+        };
+        self.playlist_export_repository.save(active_model).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use log::info;
+use sea_orm::{ActiveValue::Set, DatabaseConnection};
+use swaptun_models::{PlaylistMirrorActiveModel, PlaylistMirrorModel, PlaylistModel, UserModel};
+use swaptun_repositories::{PlaylistMirrorRepository, PlaylistRepository};
+
+use crate::error::AppError;
+use crate::{
+    CreatePlaylistMirrorRequest, JobPayload, JobService, PlaylistExportService, PlaylistMirror,
+};
+
+/// Keeps playlists sent to another platform up to date with their source. Each change found
+/// when the source is imported again queues an export updating the destination playlist.
+#[derive(Clone)]
+pub struct PlaylistMirrorService {
+    playlist_mirror_repository: PlaylistMirrorRepository,
+    playlist_repository: PlaylistRepository,
+    playlist_export_service: PlaylistExportService,
+    job_service: JobService,
+}
+
+impl PlaylistMirrorService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        PlaylistMirrorService {
+            playlist_mirror_repository: PlaylistMirrorRepository::new(db.clone()),
+            playlist_repository: PlaylistRepository::new(db.clone()),
+            playlist_export_service: PlaylistExportService::new(db.clone()),
+            job_service: JobService::new(db),
+        }
+    }
+
+    /// Mirrors one of the user's playlists and queues the first push to the destination
+    pub async fn create(
+        &self,
+        user: &UserModel,
+        request: CreatePlaylistMirrorRequest,
+    ) -> Result<PlaylistMirror, AppError> {
+        let playlist = self.get_playlist(request.playlist_id).await?;
+        if playlist.user_id != user.id {
+            return Err(AppError::Unauthorized(
+                "You can only mirror your own playlists".to_string(),
+            ));
+        }
+        if playlist.origin == request.destination {
+            return Err(AppError::Validation(format!(
+                "Playlist already comes from {:?}",
+                request.destination
+            )));
+        }
+        if self
+            .playlist_mirror_repository
+            .find(playlist.id, user.id, request.destination.clone())
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Validation(format!(
+                "Playlist is already mirrored to {:?}",
+                request.destination
+            )));
+        }
+
+        if let Some(platform_playlist_id) = &request.platform_playlist_id {
+            self.playlist_export_service
+                .link(
+                    playlist.id,
+                    user.id,
+                    request.destination.clone(),
+                    platform_playlist_id,
+                )
+                .await?;
+        }
+        let mirror = self
+            .playlist_mirror_repository
+            .create(PlaylistMirrorActiveModel {
+                playlist_id: Set(playlist.id),
+                user_id: Set(user.id),
+                destination: Set(request.destination),
+                created_on: Set(Utc::now().into()),
+                ..Default::default()
+            })
+            .await?;
+        self.push(&mirror).await?;
+
+        info!(
+            "Playlist {} of user {} mirrored to {:?}",
+            playlist.id, user.id, mirror.destination
+        );
+        self.to_response(mirror, playlist).await
+    }
+
+    pub async fn get_user_mirrors(&self, user_id: i32) -> Result<Vec<PlaylistMirror>, AppError> {
+        let mut mirrors = Vec::new();
+        for mirror in self
+            .playlist_mirror_repository
+            .find_by_user(user_id)
+            .await?
+        {
+            let playlist = self.get_playlist(mirror.playlist_id).await?;
+            mirrors.push(self.to_response(mirror, playlist).await?);
+        }
+        Ok(mirrors)
+    }
+
+    /// Stops mirroring, the playlist on the destination is left as is
+    pub async fn delete(&self, user_id: i32, id: i32) -> Result<(), AppError> {
+        let mirror = self
+            .playlist_mirror_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Mirror not found".to_string()))?;
+        if mirror.user_id != user_id {
+            return Err(AppError::Unauthorized(
+                "You do not have permission to delete this mirror".to_string(),
+            ));
+        }
+
+        self.playlist_mirror_repository.delete(id).await?;
+        Ok(())
+    }
+
+    /// Queues a push for each mirror of the playlist, except those whose push is still waiting
+    /// for a worker since it will read the playlist as it is now. Returns how many were queued.
+    pub async fn push_changes(&self, playlist_id: i32) -> Result<usize, AppError> {
+        let mirrors = self
+            .playlist_mirror_repository
+            .find_by_playlist(playlist_id)
+            .await?;
+        if mirrors.is_empty() {
+            return Ok(0);
+        }
+
+        let queued = self.job_service.queued_exports().await?;
+        let mut pushed = 0;
+        for mirror in mirrors {
+            let key = (
+                mirror.user_id,
+                mirror.playlist_id,
+                mirror.destination.clone(),
+            );
+            if !queued.contains(&key) {
+                self.push(&mirror).await?;
+                pushed += 1;
+            }
+        }
+        Ok(pushed)
+    }
+
+    async fn push(&self, mirror: &PlaylistMirrorModel) -> Result<(), AppError> {
+        self.job_service
+            .enqueue(
+                mirror.user_id,
+                JobPayload::ExportPlaylist {
+                    playlist_id: mirror.playlist_id,
+                    destination: mirror.destination.clone(),
+                    matches: None,
+                    create_new: false,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn get_playlist(&self, id: i32) -> Result<PlaylistModel, AppError> {
+        self.playlist_repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Playlist not found".to_string()))
+    }
+
+    async fn to_response(
+        &self,
+        mirror: PlaylistMirrorModel,
+        playlist: PlaylistModel,
+    ) -> Result<PlaylistMirror, AppError> {
+        let export = self
+            .playlist_export_service
+            .get_export(
+                mirror.playlist_id,
+                mirror.user_id,
+                mirror.destination.clone(),
+            )
+            .await?;
+
+        Ok(PlaylistMirror {
+            id: mirror.id,
+            playlist,
+            destination: mirror.destination,
+            platform_playlist_id: export.map(|export| export.platform_playlist_id),
+            created_at: mirror.created_on.into(),
+        })
+    }
+}
@@ -1,16 +1,19 @@
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
 use super::{
     CreatePlaylistRequest, DeletePlaylistRequest, GetPlaylistMusicsResponse, GetPlaylistResponse,
-    GetPlaylistsParams, Playlist, UpdatePlaylistRequest,
+    GetPlaylistsParams, Playlist, PlaylistMirrorService, PlaylistSnapshot, PlaylistSnapshotService,
+    SnapshotDiffParams, SnapshotDiffResponse, TrackMatchEntry, UpdatePlaylistRequest,
 };
 use crate::{error::AppError, SharedPlaylist, SharedPlaylistsResponse, UserInfo};
 
+use chrono::Utc;
 use log::error;
 use sea_orm::{DatabaseConnection, DbErr, DeleteResult, IntoActiveModel};
 use swaptun_models::{
-    music_playlist, playlist::PlaylistOrigin, MusicModel, PlaylistActiveModel, PlaylistModel,
-    UserModel,
+    music_playlist, playlist::PlaylistOrigin, MusicModel, MusicPlaylistModel, PlaylistActiveModel,
+    PlaylistModel, SnapshotReason, UserModel,
 };
 use swaptun_repositories::{MusicPlaylistRepository, MusicRepository, PlaylistRepository};
 
@@ -19,6 +22,8 @@ pub struct PlaylistService {
     pub playlist_repository: PlaylistRepository,
     pub music_playlist_repository: MusicPlaylistRepository,
     pub music_repository: MusicRepository,
+    playlist_mirror_service: PlaylistMirrorService,
+    playlist_snapshot_service: PlaylistSnapshotService,
 }
 
 impl PlaylistService {
@@ -26,7 +31,9 @@ impl PlaylistService {
         Self {
             playlist_repository: PlaylistRepository::new(db.clone()),
             music_playlist_repository: MusicPlaylistRepository::new(db.clone()),
-            music_repository: MusicRepository::new(db),
+            music_repository: MusicRepository::new(db.clone()),
+            playlist_mirror_service: PlaylistMirrorService::new(db.clone()),
+            playlist_snapshot_service: PlaylistSnapshotService::new(db),
         }
     }
 
@@ -94,6 +101,37 @@ impl PlaylistService {
         })
     }
 
+    /// Rejects confirmed matches for musics that are not part of the playlist, so a send
+    /// can only ever export the playlist's own tracks
+    pub async fn check_matches(
+        &self,
+        playlist_id: i32,
+        matches: &[TrackMatchEntry],
+    ) -> Result<(), AppError> {
+        let playlist = self.get_playlist(playlist_id).await?;
+        let music_ids: HashSet<i32> = self
+            .music_playlist_repository
+            .find_by_playlist_id(playlist.id)
+            .await?
+            .into_iter()
+            .map(|entry| entry.music_id)
+            .collect();
+
+        let foreign: Vec<String> = matches
+            .iter()
+            .filter(|entry| !music_ids.contains(&entry.music_id))
+            .map(|entry| entry.music_id.to_string())
+            .collect();
+        if !foreign.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Musics {} are not part of playlist with id {}",
+                foreign.join(", "),
+                playlist_id
+            )));
+        }
+        Ok(())
+    }
+
     pub async fn get_shared_playlists(
         &self,
         user: UserModel,
@@ -213,7 +251,7 @@ impl PlaylistService {
     ) -> Result<PlaylistModel, AppError> {
         if let Some(playlist) = self
             .playlist_repository
-            .find_by_user(&user, None)
+            .find_by_user(&user, Some(request.origin.clone()))
             .await?
             .into_iter()
             .find(|p| p.origin_id == request.origin_id)
@@ -277,7 +315,7 @@ impl PlaylistService {
         }
 
         match self._update(playlist).await {
-            Ok(_) => Ok(()),
+            Ok(playlist) => self.snapshot_edit(&playlist).await,
             Err(_) => Err(AppError::InternalServerError),
         }
     }
@@ -312,56 +350,265 @@ impl PlaylistService {
         playlist: &PlaylistModel,
         music: MusicModel,
     ) -> Result<(), AppError> {
-        if let Some(_) = self
+        let position = self
             .music_playlist_repository
-            .find_relation(playlist.clone(), music.clone())
-            .await?
-        {
-            return Ok(());
-        }
+            .next_position(playlist.id)
+            .await?;
         let music_playlist = music_playlist::ActiveModel {
             playlist_id: sea_orm::ActiveValue::Set(playlist.id),
-            music_album: sea_orm::ActiveValue::Set(music.album),
-            music_artist: sea_orm::ActiveValue::Set(music.artist),
-            music_title: sea_orm::ActiveValue::Set(music.title),
+            music_id: sea_orm::ActiveValue::Set(music.id),
+            position: sea_orm::ActiveValue::Set(position),
             ..Default::default()
         };
 
         match self.music_playlist_repository.create(music_playlist).await {
-            Ok(_) => Ok(()),
+            Ok(_) => self.snapshot_edit(playlist).await,
             Err(e) => {
                 error!("Error adding music to playlist {}", e);
                 Err(AppError::InternalServerError)
             }
         }
     }
+
+    /// Removes every copy of the music from the playlist
+    /// Removes one copy of a music from the playlist, `occurrence` picks which one when the
+    /// music appears several times
     pub async fn remove_music(
         &self,
         playlist: &PlaylistModel,
         music: &MusicModel,
+        occurrence: usize,
+    ) -> Result<(), AppError> {
+        let entry = self
+            .music_playlist_repository
+            .find_by_playlist_id(playlist.id)
+            .await?
+            .into_iter()
+            .filter(|entry| entry.music_id == music.id)
+            .nth(occurrence)
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Music {} not found in playlist with id {}",
+                    music.title, playlist.id
+                ))
+            })?;
+
+        match self.music_playlist_repository.delete(entry.id).await {
+            Ok(_) => self.snapshot_edit(playlist).await,
+            Err(e) => {
+                error!("Error removing music from playlist: {:?}", e);
+                Err(AppError::InternalServerError)
+            }
+        }
+    }
+
+    /// Moves a music of the playlist to `position`, shifting the musics in between.
+    /// `occurrence` picks which copy to move when the music appears several times.
+    pub async fn move_music(
+        &self,
+        playlist: &PlaylistModel,
+        music_id: i32,
+        occurrence: usize,
+        position: usize,
     ) -> Result<(), AppError> {
-        if let Some(_) = self
+        let mut entries = self
             .music_playlist_repository
-            .find_relation(playlist.clone(), music.clone())
+            .find_by_playlist_id(playlist.id)
+            .await?;
+
+        let from = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.music_id == music_id)
+            .nth(occurrence)
+            .map(|(index, _)| index)
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Music {} not found in playlist with id {}",
+                    music_id, playlist.id
+                ))
+            })?;
+        let entry = entries.remove(from);
+        entries.insert(position.min(entries.len()), entry);
+
+        self.save_positions(entries).await?;
+        self.snapshot_edit(playlist).await
+    }
+
+    /// Makes the playlist entries match the imported `music_ids` and records when the
+    /// playlist was synced
+    pub async fn sync_musics(
+        &self,
+        playlist: &PlaylistModel,
+        music_ids: &[i32],
+    ) -> Result<(), AppError> {
+        self.replace_musics(playlist, music_ids, SnapshotReason::Import)
+            .await?;
+
+        let mut active_model = playlist.clone().into_active_model();
+        active_model.last_synced_on = sea_orm::ActiveValue::Set(Some(Utc::now().into()));
+        self.playlist_repository.update(active_model).await?;
+        Ok(())
+    }
+
+    /// Makes the playlist entries match `music_ids`, in order and duplicates included.
+    /// Existing entries are reused for the musics they hold, the others are created
+    /// or deleted, so an unchanged playlist is not written. Any change is recorded in a
+    /// snapshot and pushed to the mirrors of the playlist.
+    async fn replace_musics(
+        &self,
+        playlist: &PlaylistModel,
+        music_ids: &[i32],
+        reason: SnapshotReason,
+    ) -> Result<(), AppError> {
+        let mut existing: HashMap<i32, VecDeque<MusicPlaylistModel>> = HashMap::new();
+        for entry in self
+            .music_playlist_repository
+            .find_by_playlist_id(playlist.id)
             .await?
         {
-            match self
-                .music_playlist_repository
-                .delete_relation(playlist, music)
-                .await
-            {
-                Ok(_) => Ok(()),
-                Err(e) => {
-                    error!("Error removing music from playlist: {:?}", e);
-                    Err(AppError::InternalServerError)
+            existing.entry(entry.music_id).or_default().push_back(entry);
+        }
+
+        let mut updated = Vec::new();
+        let mut created = Vec::new();
+        for (position, music_id) in music_ids.iter().enumerate() {
+            let position = position as i32;
+            match existing.get_mut(music_id).and_then(VecDeque::pop_front) {
+                Some(entry) if entry.position == position => {}
+                Some(entry) => {
+                    let mut active_model = entry.into_active_model();
+                    active_model.position = sea_orm::ActiveValue::Set(position);
+                    updated.push(active_model);
                 }
+                None => created.push(music_playlist::ActiveModel {
+                    playlist_id: sea_orm::ActiveValue::Set(playlist.id),
+                    music_id: sea_orm::ActiveValue::Set(*music_id),
+                    position: sea_orm::ActiveValue::Set(position),
+                    ..Default::default()
+                }),
             }
-        } else {
-            Err(AppError::NotFound(format!(
-                "Music {} not found in playlist with id {}",
-                music.title, playlist.id
-            )))
         }
+        let deleted: Vec<i32> = existing
+            .into_values()
+            .flatten()
+            .map(|entry| entry.id)
+            .collect();
+
+        if updated.is_empty() && created.is_empty() && deleted.is_empty() {
+            return Ok(());
+        }
+        self.music_playlist_repository
+            .replace(deleted, updated, created)
+            .await?;
+
+        self.playlist_snapshot_service
+            .record(playlist, music_ids, reason)
+            .await?;
+        // The playlist itself is up to date, the mirrors catch up on the next change
+        if let Err(e) = self.playlist_mirror_service.push_changes(playlist.id).await {
+            error!(
+                "Failed to push playlist {} to its mirrors: {:?}",
+                playlist.id, e
+            );
+        }
+        Ok(())
+    }
+
+    async fn save_positions(&self, entries: Vec<MusicPlaylistModel>) -> Result<(), AppError> {
+        let moved = entries
+            .into_iter()
+            .enumerate()
+            .filter(|(position, entry)| entry.position != *position as i32)
+            .map(|(position, entry)| {
+                let mut active_model = entry.into_active_model();
+                active_model.position = sea_orm::ActiveValue::Set(position as i32);
+                active_model
+            })
+            .collect();
+        self.music_playlist_repository.update_all(moved).await?;
+        Ok(())
+    }
+
+    /// Records the playlist as a user edit left it
+    async fn snapshot_edit(&self, playlist: &PlaylistModel) -> Result<(), AppError> {
+        let music_ids: Vec<i32> = self
+            .music_playlist_repository
+            .find_by_playlist_id(playlist.id)
+            .await?
+            .into_iter()
+            .map(|entry| entry.music_id)
+            .collect();
+        self.playlist_snapshot_service
+            .record(playlist, &music_ids, SnapshotReason::Edit)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_own_playlist(&self, id: i32, user_id: i32) -> Result<PlaylistModel, AppError> {
+        let playlist = self.get_playlist(id).await?;
+        if playlist.user_id != user_id {
+            return Err(AppError::Unauthorized(
+                "You do not have permission to access the history of this playlist".to_string(),
+            ));
+        }
+        Ok(playlist)
+    }
+
+    /// Past versions of one of the user's playlists, most recent first
+    pub async fn get_snapshots(
+        &self,
+        playlist_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<PlaylistSnapshot>, AppError> {
+        let playlist = self.get_own_playlist(playlist_id, user_id).await?;
+        self.playlist_snapshot_service
+            .get_snapshots(playlist.id)
+            .await
+    }
+
+    pub async fn diff_snapshots(
+        &self,
+        playlist_id: i32,
+        user_id: i32,
+        params: SnapshotDiffParams,
+    ) -> Result<SnapshotDiffResponse, AppError> {
+        let playlist = self.get_own_playlist(playlist_id, user_id).await?;
+        self.playlist_snapshot_service
+            .diff(playlist.id, params.from, params.to)
+            .await
+    }
+
+    /// Brings back the musics of a snapshot as the contents of the playlist. The restore is
+    /// itself recorded, so it can be undone by restoring an older snapshot.
+    pub async fn restore_snapshot(
+        &self,
+        playlist_id: i32,
+        snapshot_id: i32,
+        user_id: i32,
+    ) -> Result<(), AppError> {
+        let playlist = self.get_own_playlist(playlist_id, user_id).await?;
+        let snapshot = self
+            .playlist_snapshot_service
+            .get_snapshot(playlist.id, snapshot_id)
+            .await?;
+
+        // Musics deleted since the snapshot cannot come back
+        let existing: HashSet<i32> = self
+            .music_repository
+            .find_by_ids(&snapshot.music_ids)
+            .await?
+            .into_iter()
+            .map(|music| music.id)
+            .collect();
+        let music_ids: Vec<i32> = snapshot
+            .music_ids
+            .into_iter()
+            .filter(|id| existing.contains(id))
+            .collect();
+
+        self.replace_musics(&playlist, &music_ids, SnapshotReason::Restore)
+            .await
     }
 
     pub async fn share_playlist(
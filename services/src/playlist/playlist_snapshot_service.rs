@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use log::error;
+use sea_orm::{ActiveValue::Set, DatabaseConnection};
+use swaptun_models::{
+    MusicModel, PlaylistModel, PlaylistSnapshotActiveModel, PlaylistSnapshotModel, SnapshotReason,
+};
+use swaptun_repositories::{MusicRepository, PlaylistSnapshotRepository};
+
+use crate::error::AppError;
+use crate::{PlaylistSnapshot, SnapshotDiff, SnapshotDiffResponse};
+
+/// Snapshots kept per playlist, the oldest ones are dropped first
+const MAX_SNAPSHOTS_PER_PLAYLIST: u64 = 50;
+
+/// History of the contents of each playlist, recorded after every import or edit changing it
+#[derive(Clone)]
+pub struct PlaylistSnapshotService {
+    playlist_snapshot_repository: PlaylistSnapshotRepository,
+    music_repository: MusicRepository,
+}
+
+impl PlaylistSnapshotService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        PlaylistSnapshotService {
+            playlist_snapshot_repository: PlaylistSnapshotRepository::new(db.clone()),
+            music_repository: MusicRepository::new(db),
+        }
+    }
+
+    pub async fn record(
+        &self,
+        playlist: &PlaylistModel,
+        music_ids: &[i32],
+        reason: SnapshotReason,
+    ) -> Result<PlaylistSnapshot, AppError> {
+        let snapshot = self
+            .playlist_snapshot_repository
+            .create(PlaylistSnapshotActiveModel {
+                playlist_id: Set(playlist.id),
+                reason: Set(reason),
+                name: Set(playlist.name.clone()),
+                description: Set(playlist.description.clone()),
+                image_url: Set(playlist.image_url.clone()),
+                music_ids: Set(serde_json::json!(music_ids)),
+                created_on: Set(Utc::now().into()),
+                ..Default::default()
+            })
+            .await?;
+        self.playlist_snapshot_repository
+            .delete_oldest(playlist.id, MAX_SNAPSHOTS_PER_PLAYLIST)
+            .await?;
+
+        to_snapshot(snapshot)
+    }
+
+    /// Snapshots of the playlist, most recent first
+    pub async fn get_snapshots(&self, playlist_id: i32) -> Result<Vec<PlaylistSnapshot>, AppError> {
+        self.playlist_snapshot_repository
+            .find_by_playlist(playlist_id)
+            .await?
+            .into_iter()
+            .map(to_snapshot)
+            .collect()
+    }
+
+    pub async fn get_snapshot(
+        &self,
+        playlist_id: i32,
+        id: i32,
+    ) -> Result<PlaylistSnapshot, AppError> {
+        let snapshot = self
+            .playlist_snapshot_repository
+            .find_by_id(id)
+            .await?
+            .filter(|snapshot| snapshot.playlist_id == playlist_id)
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Snapshot {} not found for playlist with id {}",
+                    id, playlist_id
+                ))
+            })?;
+        to_snapshot(snapshot)
+    }
+
+    /// Musics added, removed and moved from snapshot `from` to snapshot `to`
+    pub async fn diff(
+        &self,
+        playlist_id: i32,
+        from: i32,
+        to: i32,
+    ) -> Result<SnapshotDiffResponse, AppError> {
+        let older = self.get_snapshot(playlist_id, from).await?;
+        let newer = self.get_snapshot(playlist_id, to).await?;
+        let diff = SnapshotDiff::between(&older.music_ids, &newer.music_ids);
+
+        let ids: Vec<i32> = diff
+            .added
+            .iter()
+            .chain(&diff.removed)
+            .chain(&diff.moved)
+            .copied()
+            .collect();
+        let musics: HashMap<i32, MusicModel> = self
+            .music_repository
+            .find_by_ids(&ids)
+            .await?
+            .into_iter()
+            .map(|music| (music.id, music))
+            .collect();
+        let load = |ids: &[i32]| -> Vec<MusicModel> {
+            ids.iter()
+                .filter_map(|id| musics.get(id).cloned())
+                .collect()
+        };
+
+        Ok(SnapshotDiffResponse {
+            from,
+            to,
+            added: load(&diff.added),
+            removed: load(&diff.removed),
+            moved: load(&diff.moved),
+        })
+    }
+}
+
+fn to_snapshot(snapshot: PlaylistSnapshotModel) -> Result<PlaylistSnapshot, AppError> {
+    let music_ids = serde_json::from_value(snapshot.music_ids).map_err(|e| {
+        error!("Invalid musics in snapshot {}: {:?}", snapshot.id, e);
+        AppError::InternalServerError
+    })?;
+
+    Ok(PlaylistSnapshot {
+        id: snapshot.id,
+        playlist_id: snapshot.playlist_id,
+        reason: snapshot.reason,
+        name: snapshot.name,
+        description: snapshot.description,
+        image_url: snapshot.image_url,
+        music_ids,
+        created_at: snapshot.created_on.into(),
+    })
+}
@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Playlist of the user's library on a platform, listed without being imported
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RemotePlaylist {
+    /// Platform id, the `origin_id` of the playlist once imported
+    pub id: String,
+    pub name: String,
+    /// Unknown on platforms that only count tracks when the playlist is read
+    pub track_count: Option<u32>,
+    /// Whether the user created the playlist rather than followed it, when the platform tells
+    pub owned: Option<bool>,
+    pub image_url: Option<String>,
+    /// Whether the playlist is part of the imports
+    pub selected: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RemotePlaylistsResponse {
+    /// False until the user picks playlists, every playlist is imported meanwhile
+    pub has_selection: bool,
+    pub playlists: Vec<RemotePlaylist>,
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct UpdateImportSelectionRequest {
+    /// Platform ids of the playlists to import, the others are left out of the next imports
+    #[validate(length(max = 1000, message = "Too many playlists selected"))]
+    pub playlist_ids: Vec<String>,
+}
+
+/// Playlists an import is limited to
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportSelection {
+    /// `None` when the user never picked playlists
+    playlist_ids: Option<HashSet<String>>,
+}
+
+impl ImportSelection {
+    /// Imports every playlist of the library
+    pub fn all() -> Self {
+        ImportSelection { playlist_ids: None }
+    }
+
+    pub fn only<I: IntoIterator<Item = String>>(playlist_ids: I) -> Self {
+        ImportSelection {
+            playlist_ids: Some(playlist_ids.into_iter().collect()),
+        }
+    }
+
+    pub fn is_all(&self) -> bool {
+        self.playlist_ids.is_none()
+    }
+
+    pub fn includes(&self, playlist_id: &str) -> bool {
+        self.playlist_ids
+            .as_ref()
+            .is_none_or(|playlist_ids| playlist_ids.contains(playlist_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_selection_all_includes_everything() {
+        let selection = ImportSelection::all();
+
+        assert!(selection.is_all());
+        assert!(selection.includes("37i9dQZF1DXcBWIGoYBM5M"));
+    }
+
+    #[test]
+    fn test_import_selection_only_selected() {
+        let selection = ImportSelection::only(vec!["mine".to_string()]);
+
+        assert!(!selection.is_all());
+        assert!(selection.includes("mine"));
+        assert!(!selection.includes("editorial"));
+        assert!(!ImportSelection::only(Vec::new()).includes("mine"));
+    }
+
+    #[test]
+    fn test_update_import_selection_request_deserialization() {
+        let json = r#"{"playlist_ids":["mine","liked_songs"]}"#;
+        let request: UpdateImportSelectionRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.playlist_ids, vec!["mine", "liked_songs"]);
+        assert!(request.validate().is_ok());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod import_selection;
+
+pub use import_selection::*;
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use log::error;
+use sea_orm::{ActiveValue::Set, DatabaseConnection, IntoActiveModel};
+use swaptun_models::{ImportSelectionActiveModel, ImportSelectionModel, PlaylistOrigin, UserModel};
+use swaptun_repositories::ImportSelectionRepository;
+
+use crate::error::AppError;
+use crate::validators::user_validators::process_validation_errors;
+use crate::{
+    ImportSelection, MusicProvider, RemotePlaylistsResponse, UpdateImportSelectionRequest,
+};
+
+/// Remembers which playlists of each platform the user wants imported, scheduled re-syncs
+/// included
+#[derive(Clone)]
+pub struct ImportSelectionService {
+    import_selection_repository: ImportSelectionRepository,
+}
+
+impl ImportSelectionService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        ImportSelectionService {
+            import_selection_repository: ImportSelectionRepository::new(db),
+        }
+    }
+
+    pub async fn get(
+        &self,
+        user_id: i32,
+        origin: PlaylistOrigin,
+    ) -> Result<ImportSelection, AppError> {
+        match self
+            .import_selection_repository
+            .find(user_id, origin)
+            .await?
+        {
+            Some(selection) => to_selection(selection),
+            None => Ok(ImportSelection::all()),
+        }
+    }
+
+    /// Limits the next imports from `origin` to the given playlists
+    pub async fn save(
+        &self,
+        user_id: i32,
+        origin: PlaylistOrigin,
+        request: UpdateImportSelectionRequest,
+    ) -> Result<ImportSelection, AppError> {
+        process_validation_errors(&request)?;
+        let mut playlist_ids = request.playlist_ids;
+        playlist_ids.sort();
+        playlist_ids.dedup();
+
+        let selection = match self
+            .import_selection_repository
+            .find(user_id, origin.clone())
+            .await?
+        {
+            Some(selection) => {
+                let mut selection = selection.into_active_model();
+                selection.playlist_ids = Set(serde_json::json!(playlist_ids));
+                selection.updated_on = Set(Utc::now().into());
+                self.import_selection_repository.update(selection).await?
+            }
+            None => {
+                self.import_selection_repository
+                    .create(ImportSelectionActiveModel {
+                        user_id: Set(user_id),
+                        origin: Set(origin),
+                        playlist_ids: Set(serde_json::json!(playlist_ids)),
+                        updated_on: Set(Utc::now().into()),
+                        ..Default::default()
+                    })
+                    .await?
+            }
+        };
+
+        to_selection(selection)
+    }
+
+    /// Goes back to importing every playlist from `origin`
+    pub async fn clear(&self, user_id: i32, origin: PlaylistOrigin) -> Result<(), AppError> {
+        self.import_selection_repository
+            .delete(user_id, origin)
+            .await?;
+        Ok(())
+    }
+
+    /// Lists the playlists of the user's library on the provider's platform without importing
+    /// them, flagging the ones the imports are limited to
+    pub async fn get_remote_playlists(
+        &self,
+        provider: &dyn MusicProvider,
+        user: &UserModel,
+    ) -> Result<RemotePlaylistsResponse, AppError> {
+        let selection = self.get(user.id, provider.origin()).await?;
+        let mut playlists = provider.list_playlists(user).await?;
+        for playlist in &mut playlists {
+            playlist.selected = selection.includes(&playlist.id);
+        }
+
+        Ok(RemotePlaylistsResponse {
+            has_selection: !selection.is_all(),
+            playlists,
+        })
+    }
+}
+
+fn to_selection(selection: ImportSelectionModel) -> Result<ImportSelection, AppError> {
+    let playlist_ids: Vec<String> =
+        serde_json::from_value(selection.playlist_ids).map_err(|e| {
+            error!(
+                "Invalid playlists in import selection {}: {:?}",
+                selection.id, e
+            );
+            AppError::InternalServerError
+        })?;
+
+    Ok(ImportSelection::only(playlist_ids))
+}
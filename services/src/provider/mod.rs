@@ -0,0 +1,14 @@
+pub mod dto;
+pub use dto::*;
+#[cfg(feature = "full")]
+pub mod import_selection_service;
+#[cfg(feature = "full")]
+pub use import_selection_service::*;
+#[cfg(feature = "full")]
+pub mod provider;
+#[cfg(feature = "full")]
+pub use provider::*;
+#[cfg(feature = "full")]
+pub mod registry;
+#[cfg(feature = "full")]
+pub use registry::*;
@@ -0,0 +1,119 @@
+use std::future::Future;
+
+use async_trait::async_trait;
+use log::{error, info};
+use swaptun_models::{MusicModel, PlaylistOrigin, UserModel};
+
+use crate::{
+    error::AppError, AddTokenRequest, ImportSelection, JobProgress, RemotePlaylist, TrackMatchEntry,
+};
+
+/// Result of sending one of our playlists to a platform
+#[derive(Debug, Clone)]
+pub struct PlaylistExport {
+    pub playlist_id: String,
+    pub report: Vec<TrackMatchEntry>,
+}
+
+/// Common interface of the streaming platforms playlists can be imported from and sent to
+#[async_trait]
+pub trait MusicProvider: Send + Sync {
+    /// Platform handled by this provider
+    fn origin(&self) -> PlaylistOrigin;
+
+    /// Completes the authorization flow with the token or code received by the client,
+    /// then starts the import of the user's playlists
+    async fn connect(&self, user: &UserModel, request: AddTokenRequest) -> Result<(), AppError>;
+
+    /// Lists the playlists of the user's library, liked songs included, without importing them
+    async fn list_playlists(&self, user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError>;
+
+    /// Imports the playlists of the user's library that are part of `selection` into our
+    /// database
+    async fn import_playlists(
+        &self,
+        user: &UserModel,
+        selection: &ImportSelection,
+        progress: &JobProgress,
+    ) -> Result<(), AppError>;
+
+    /// Looks for one of our tracks on the platform and reports the best match, if any
+    async fn search_track(
+        &self,
+        user: &UserModel,
+        music: &MusicModel,
+    ) -> Result<TrackMatchEntry, AppError>;
+
+    /// Matches every track of one of our playlists on the platform without creating anything
+    async fn preview_playlist(
+        &self,
+        user: &UserModel,
+        playlist_id: i32,
+        progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError>;
+
+    /// Creates a playlist on the platform from one of our playlists with the tracks of an
+    /// already computed match report, such as a preview confirmed by the user
+    async fn create_playlist_from_matches(
+        &self,
+        user: &UserModel,
+        playlist_id: i32,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError>;
+
+    /// Brings a playlist previously created on the platform in line with a match report: adds
+    /// the missing tracks, removes the dropped ones and fixes the order where the platform
+    /// allows it. Fails with `AppError::NotFound` when the platform playlist no longer exists.
+    async fn update_playlist_from_matches(
+        &self,
+        user: &UserModel,
+        platform_playlist_id: &str,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError>;
+
+    /// Ids of the users who connected their account on the platform
+    async fn connected_users(&self) -> Result<Vec<i32>, AppError>;
+
+    /// Removes the user's credentials and the playlists imported from the platform
+    async fn disconnect(&self, user: &UserModel) -> Result<(), AppError>;
+}
+
+/// Imports every playlist with `import`, carrying on after a failure so one broken playlist
+/// does not block the others. Each failure is reported to `progress`, the first is returned.
+pub async fn import_each<T, F, Fut>(
+    playlists: impl IntoIterator<Item = T>,
+    name: impl Fn(&T) -> String,
+    progress: &JobProgress,
+    mut import: F,
+) -> Result<(), AppError>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<(), AppError>>,
+{
+    let mut first_error = None;
+    for playlist in playlists {
+        let name = name(&playlist);
+        if let Err(e) = report_import(&name, import(playlist).await, progress).await {
+            first_error.get_or_insert(e);
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Logs the outcome of importing the playlist `name`, a failure is also reported to `progress`
+pub async fn report_import(
+    name: &str,
+    result: Result<(), AppError>,
+    progress: &JobProgress,
+) -> Result<(), AppError> {
+    match &result {
+        Ok(()) => info!("Successfully imported playlist: {}", name),
+        Err(e) => {
+            error!("Error importing playlist {}: {:?}", name, e);
+            progress.playlist_failed(name, e).await;
+        }
+    }
+    result
+}
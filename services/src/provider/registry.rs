@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sea_orm::DatabaseConnection;
+use swaptun_models::PlaylistOrigin;
+
+use crate::{
+    error::AppError, AppleMusicService, DeezerService, MusicProvider, SpotifyService,
+    YoutubeMusicService,
+};
+
+/// Providers available for each `PlaylistOrigin`
+#[derive(Clone)]
+pub struct ProviderRegistry {
+    providers: HashMap<PlaylistOrigin, Arc<dyn MusicProvider>>,
+}
+
+impl ProviderRegistry {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        let mut registry = Self {
+            providers: HashMap::new(),
+        };
+        registry.register(Arc::new(SpotifyService::new(db.clone())));
+        registry.register(Arc::new(YoutubeMusicService::new(db.clone())));
+        registry.register(Arc::new(AppleMusicService::new(db.clone())));
+        registry.register(Arc::new(DeezerService::new(db)));
+        registry
+    }
+
+    pub fn register(&mut self, provider: Arc<dyn MusicProvider>) {
+        self.providers.insert(provider.origin(), provider);
+    }
+
+    pub fn get(&self, origin: &PlaylistOrigin) -> Result<Arc<dyn MusicProvider>, AppError> {
+        self.providers
+            .get(origin)
+            .cloned()
+            .ok_or_else(|| AppError::Validation(format!("No provider available for {:?}", origin)))
+    }
+
+    pub fn providers(&self) -> impl Iterator<Item = &Arc<dyn MusicProvider>> {
+        self.providers.values()
+    }
+}
@@ -0,0 +1,4 @@
+#[cfg(feature = "full")]
+pub mod sync_scheduler;
+#[cfg(feature = "full")]
+pub use sync_scheduler::*;
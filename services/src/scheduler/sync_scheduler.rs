@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use log::{error, info};
+use sea_orm::DatabaseConnection;
+use tokio::task::JoinHandle;
+
+use crate::error::AppError;
+use crate::{JobPayload, JobService, ProviderRegistry};
+
+const DEFAULT_SYNC_INTERVAL_SECS: u64 = 6 * 3600;
+
+/// Re-imports the playlists of every connected account on a regular basis
+#[derive(Clone)]
+pub struct SyncScheduler {
+    job_service: JobService,
+    registry: ProviderRegistry,
+}
+
+impl SyncScheduler {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        let registry = ProviderRegistry::new(db.clone());
+        Self::with_registry(db, registry)
+    }
+
+    pub fn with_registry(db: Arc<DatabaseConnection>, registry: ProviderRegistry) -> Self {
+        SyncScheduler {
+            job_service: JobService::new(db),
+            registry,
+        }
+    }
+
+    /// Queues the imports every `SYNC_INTERVAL_SECS` seconds, starting one interval after
+    /// startup. A zero interval disables the scheduler.
+    pub fn start(self) -> Option<JoinHandle<()>> {
+        let interval = std::env::var("SYNC_INTERVAL_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(DEFAULT_SYNC_INTERVAL_SECS));
+        if interval.is_zero() {
+            info!("Playlist sync scheduler disabled");
+            return None;
+        }
+
+        Some(tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                match self.schedule_imports(interval).await {
+                    Ok(count) => info!("Scheduled the sync of {} accounts", count),
+                    Err(e) => error!("Failed to schedule playlist syncs: {:?}", e),
+                }
+            }
+        }))
+    }
+
+    /// Queues an import for each connected account that has none pending yet. The imports
+    /// are spread evenly over `spread` so the platforms are not all called at once.
+    pub async fn schedule_imports(&self, spread: Duration) -> Result<usize, AppError> {
+        let pending = self.job_service.pending_imports().await?;
+
+        let mut accounts = Vec::new();
+        for provider in self.registry.providers() {
+            let origin = provider.origin();
+            for user_id in provider.connected_users().await? {
+                if !pending.contains(&(user_id, origin.clone())) {
+                    accounts.push((user_id, origin.clone()));
+                }
+            }
+        }
+        // Keeps the accounts of a user next to each other
+        accounts.sort_by_key(|(user_id, _)| *user_id);
+
+        let step = spread / accounts.len().max(1) as u32;
+        let now = Utc::now();
+        for (index, (user_id, origin)) in accounts.iter().enumerate() {
+            let delay = chrono::Duration::from_std(step * index as u32)
+                .unwrap_or_else(|_| chrono::Duration::zero());
+            self.job_service
+                .enqueue_at(
+                    *user_id,
+                    JobPayload::ImportPlaylists {
+                        origin: origin.clone(),
+                    },
+                    now + delay,
+                )
+                .await?;
+        }
+
+        Ok(accounts.len())
+    }
+}
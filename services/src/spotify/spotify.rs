@@ -1,107 +1,45 @@
 use crate::error::AppError;
 use crate::{
-    music::dto::CreateMusicRequest, CreatePlaylistRequest, MusicService, NotificationService,
-    PlaylistService, SpotifyUrlResponse,
+    find_override, import_each, music::dto::CreateMusicRequest, report_import, ArtistCredit,
+    CreatePlaylistRequest, ImportSelection, JobProgress, MusicProvider, MusicService,
+    NotificationService, PlaylistExport, PlaylistService, RemotePlaylist, ScoredMatch,
+    SpotifyUrlResponse, TrackCandidate, TrackMatchEntry, TrackMatchOverrideService, TrackMatcher,
+    TrackQuery, LIKED_SONGS_NAME, LIKED_SONGS_ORIGIN_ID,
+};
+use crate::{
+    AddTokenRequest, DeleteTokenRequest, EventHub, PlaylistDiff, UpdateTokenRequest, UserEvent,
 };
-use crate::{AddTokenRequest, DeleteTokenRequest, UpdateTokenRequest};
 use futures::StreamExt;
 use log::{error, info};
-use rspotify::model::{PlayableId, PlayableItem, SearchResult, SimplifiedPlaylist};
-use rspotify::prelude::{BaseClient, OAuthClient};
+use rspotify::model::{
+    FullTrack, PlayableId, PlayableItem, PlaylistId, SearchResult, SimplifiedPlaylist, TrackId,
+};
+use rspotify::prelude::{BaseClient, Id, OAuthClient};
 use sea_orm::IntoActiveModel;
 use std::sync::Arc;
 
 use sea_orm::{ActiveValue::Set, DatabaseConnection};
 use swaptun_models::{
-    PlaylistOrigin, SpotifyCodeActiveModel, SpotifyCodeModel, SpotifyTokenActiveModel,
-    SpotifyTokenModel, UserModel,
+    MusicModel, PlaylistModel, PlaylistOrigin, SpotifyCodeActiveModel, SpotifyCodeModel,
+    SpotifyTokenActiveModel, SpotifyTokenModel, UserModel,
 };
 use swaptun_repositories::{
     spotify_code_repository::SpotifyCodeRepository,
     spotify_token_repository::SpotifyTokenRepository,
 };
 
+use async_trait::async_trait;
 use chrono::{NaiveDate, Utc};
 use rspotify::{scopes, AuthCodeSpotify, Credentials, OAuth, Token};
 
-/// Normalize a string for fuzzy matching
-fn normalize_string(s: &str) -> String {
-    s.to_lowercase()
-        .chars()
-        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-        .collect::<String>()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
-/// Check if two strings match with fuzzy logic
-/// Returns true if:
-/// - They are exact matches
-/// - One contains the other
-/// - They match after normalization
-fn fuzzy_match(str1: &str, str2: &str) -> bool {
-    let norm1 = normalize_string(str1);
-    let norm2 = normalize_string(str2);
-
-    // Exact match after normalization
-    if norm1 == norm2 {
-        return true;
-    }
-
-    // One contains the other
-    if norm1.contains(&norm2) || norm2.contains(&norm1) {
-        return true;
-    }
-
-    false
-}
-
-/// Check if artists match with fuzzy logic
-/// For "SDM & Werenoi", should match "SDM" or "Werenoi" individually
-fn artists_match(spotify_artists: &[rspotify::model::SimplifiedArtist], db_artist: &str) -> bool {
-    let db_artist_norm = normalize_string(db_artist);
-
-    // Check if any Spotify artist matches
-    for spotify_artist in spotify_artists {
-        let spotify_artist_norm = normalize_string(&spotify_artist.name);
-
-        if fuzzy_match(&spotify_artist_norm, &db_artist_norm) {
-            return true;
-        }
-    }
-
-    // Check if db_artist contains multiple artists (split by &, feat, ft, with, etc.)
-    let db_artist_parts: Vec<&str> = db_artist
-        .split(&['&', ','][..])
-        .chain(db_artist.split(" feat "))
-        .chain(db_artist.split(" feat. "))
-        .chain(db_artist.split(" ft "))
-        .chain(db_artist.split(" ft. "))
-        .chain(db_artist.split(" with "))
-        .map(|s| s.trim())
-        .collect();
-
-    // Check if any part matches any Spotify artist
-    for db_part in &db_artist_parts {
-        let db_part_norm = normalize_string(db_part);
-        for spotify_artist in spotify_artists {
-            let spotify_artist_norm = normalize_string(&spotify_artist.name);
-            if fuzzy_match(&spotify_artist_norm, &db_part_norm) {
-                return true;
-            }
-        }
-    }
-
-    false
-}
-
 #[derive(Clone)]
 pub struct SpotifyService {
     spotify_code_repository: SpotifyCodeRepository,
     spotify_token_repository: SpotifyTokenRepository,
     playlist_service: PlaylistService,
     music_service: MusicService,
+    matcher: TrackMatcher,
+    track_match_override_service: TrackMatchOverrideService,
     db: Arc<DatabaseConnection>,
 }
 
@@ -112,6 +50,8 @@ impl SpotifyService {
             spotify_token_repository: SpotifyTokenRepository::new(db.clone()),
             playlist_service: PlaylistService::new(db.clone()),
             music_service: MusicService::new(db.clone()),
+            matcher: TrackMatcher::from_env(),
+            track_match_override_service: TrackMatchOverrideService::new(db.clone()),
             db,
         }
     }
@@ -298,6 +238,8 @@ impl SpotifyService {
     pub async fn get_user_playlists(
         &self,
         user: UserModel,
+        selection: &ImportSelection,
+        progress: &JobProgress,
     ) -> Result<Vec<SimplifiedPlaylist>, AppError> {
         let spotify = self.get_spotify_client_connected(&user).await?;
         match spotify.me().await {
@@ -308,43 +250,33 @@ impl SpotifyService {
                 // Collecter les playlists
                 while let Some(playlist_result) = playlists.next().await {
                     if let Ok(playlist) = playlist_result {
-                        playlist_models.push(playlist.clone());
-
-                        // Spawn background task to import playlists
-                        let service = self.clone();
-                        let user_clone = user.clone();
-
-                        tokio::spawn(async move {
-                            match service.get_spotify_client_connected(&user_clone).await {
-                                Ok(spotify_client) => {
-                                    if let Err(e) = service
-                                        .import_playlist(
-                                            playlist.clone(),
-                                            &user_clone,
-                                            &spotify_client,
-                                        )
-                                        .await
-                                    {
-                                        error!(
-                                            "Error importing playlist {}: {:?}",
-                                            playlist.name, e
-                                        );
-                                    } else {
-                                        info!("Successfully imported playlist: {}", playlist.name);
-                                    }
-                                }
-                                Err(e) => {
-                                    error!(
-                                        "Failed to get Spotify client for background import: {:?}",
-                                        e
-                                    );
-                                }
-                            }
-                        });
+                        playlist_models.push(playlist);
                     }
                 }
+                playlist_models.retain(|playlist| selection.includes(&playlist.id.to_string()));
+
+                let (user, spotify) = (&user, &spotify);
+                let result = import_each(
+                    &playlist_models,
+                    |playlist| playlist.name.clone(),
+                    progress,
+                    |playlist| async move {
+                        self.import_playlist(playlist.clone(), user, spotify, progress)
+                            .await
+                    },
+                )
+                .await;
+
+                // Accounts connected before the library was requested cannot read it until they
+                // connect again, their playlists are still imported
+                if selection.includes(LIKED_SONGS_ORIGIN_ID) {
+                    let saved_tracks = self.import_saved_tracks(user, spotify, progress).await;
+                    report_import(LIKED_SONGS_NAME, saved_tracks, progress)
+                        .await
+                        .ok();
+                }
 
-                Ok(playlist_models)
+                result.map(|_| playlist_models)
             }
             Err(e) => {
                 error!("Error getting user playlists: {:?}", e);
@@ -353,13 +285,63 @@ impl SpotifyService {
         }
     }
 
+    /// Lists the saved tracks and the playlists of the user's library without importing them
+    pub async fn list_playlists(&self, user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError> {
+        let spotify = self.get_spotify_client_connected(user).await?;
+        let spotify_user = spotify.me().await.map_err(|e| {
+            error!("Error getting Spotify user: {:?}", e);
+            AppError::InternalServerError
+        })?;
+
+        let mut remote_playlists = Vec::new();
+        // Only the total is needed, accounts without the library scope don't list it
+        match spotify
+            .current_user_saved_tracks_manual(None, Some(1), None)
+            .await
+        {
+            Ok(saved_tracks) => remote_playlists.push(RemotePlaylist {
+                id: LIKED_SONGS_ORIGIN_ID.to_string(),
+                name: LIKED_SONGS_NAME.to_string(),
+                track_count: Some(saved_tracks.total),
+                owned: Some(true),
+                image_url: None,
+                selected: false,
+            }),
+            Err(e) => error!("Error getting saved tracks: {:?}", e),
+        }
+
+        let mut playlists = spotify.user_playlists(spotify_user.id.clone());
+        while let Some(playlist) = playlists.next().await {
+            match playlist {
+                Ok(playlist) => remote_playlists.push(RemotePlaylist {
+                    id: playlist.id.to_string(),
+                    name: playlist.name,
+                    track_count: Some(playlist.tracks.total),
+                    owned: Some(playlist.owner.id == spotify_user.id),
+                    image_url: playlist.images.first().map(|image| image.url.clone()),
+                    selected: false,
+                }),
+                Err(e) => {
+                    error!("Error getting user playlists: {:?}", e);
+                    return Err(AppError::InternalServerError);
+                }
+            }
+        }
+
+        Ok(remote_playlists)
+    }
+
     pub async fn import_playlist(
         &self,
         playlist: SimplifiedPlaylist,
         user: &UserModel,
         spotify: &AuthCodeSpotify,
+        progress: &JobProgress,
     ) -> Result<(), AppError> {
         let mut tracks = spotify.playlist_items(playlist.id.clone(), None, None);
+        progress
+            .start_playlist(&playlist.name, playlist.tracks.total as usize)
+            .await;
 
         // Extract image URL from playlist (use first/largest image if available)
         let image_url = playlist.images.first().map(|img| img.url.clone());
@@ -373,67 +355,117 @@ impl SpotifyService {
         };
         let playlist = self.playlist_service.create_or_get(request, &user).await?;
 
-        let mut local_tracks = self.music_service.find_by_playlist(&playlist).await?;
+        let mut music_ids = Vec::new();
         while let Some(track) = tracks.next().await {
+            // Counted up front, items that are not tracks are skipped below
+            progress.track_done().await;
             if let Ok(track) = track {
-                if let Some(track) = track.track {
-                    match track {
-                        PlayableItem::Track(track) => {
-                            // Vérifier si la musique existe déjà dans la playlist
-                            if let Some(index) = local_tracks.iter().position(|t| {
-                                t.title == track.name
-                                    && t.artist
-                                        == track.artists.first().map_or("", |a| a.name.as_str())
-                                    && t.album == track.album.name
-                            }) {
-                                info!(
-                                    "La musique {} - {} existe déjà dans la playlist",
-                                    track.artists.first().map_or("", |a| a.name.as_str()),
-                                    track.name
-                                );
-                                local_tracks.remove(index);
-                                continue;
-                            };
-                            let artist_name = track
-                                .artists
-                                .first()
-                                .map(|a| a.name.clone())
-                                .unwrap_or_default();
-                            let track_title = track.name.clone();
-
-                            let genre = None;
-
-                            let create_music_request = CreateMusicRequest {
-                                title: track_title,
-                                release_date: track
-                                    .album
-                                    .release_date
-                                    .unwrap_or_default()
-                                    .parse::<NaiveDate>()
-                                    .unwrap_or_default(),
-                                genre,
-                                artist: artist_name,
-                                album: track.album.name,
-                                description: None,
-                            };
-                            let music = self.music_service.create(create_music_request).await?;
-                            self.playlist_service.add_music(&playlist, music).await?;
-                        }
-
-                        _ => {}
-                    }
+                if let Some(PlayableItem::Track(track)) = track.track {
+                    music_ids.push(self.save_track(track).await?);
                 }
             }
         }
-        for local_track in local_tracks {
-            info!(
-                "La musique {} n'existe plus dans la nouvelle playlist",
-                local_track.title
-            );
-            self.playlist_service
-                .remove_music(&playlist, &local_track)
+        self.playlist_service
+            .sync_musics(&playlist, &music_ids)
+            .await?;
+        progress.finish_playlist().await;
+        self.notify_playlist_imported(user, &playlist).await;
+
+        Ok(())
+    }
+
+    /// Imports the tracks saved in the user's library as their liked songs playlist, most
+    /// recently saved first
+    pub async fn import_saved_tracks(
+        &self,
+        user: &UserModel,
+        spotify: &AuthCodeSpotify,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        let mut saved_tracks = Vec::new();
+        let mut pages = spotify.current_user_saved_tracks(None);
+        while let Some(saved_track) = pages.next().await {
+            match saved_track {
+                Ok(saved_track) => saved_tracks.push(saved_track.track),
+                Err(e) => {
+                    error!("Error getting saved tracks: {:?}", e);
+                    return Err(AppError::InternalServerError);
+                }
+            }
+        }
+
+        let request = CreatePlaylistRequest::liked_songs(PlaylistOrigin::Spotify);
+        progress
+            .start_playlist(&request.name, saved_tracks.len())
+            .await;
+        let playlist = self.playlist_service.create_or_get(request, user).await?;
+
+        let mut music_ids = Vec::new();
+        for track in saved_tracks {
+            progress.track_done().await;
+            music_ids.push(self.save_track(track).await?);
+        }
+        self.playlist_service
+            .sync_musics(&playlist, &music_ids)
+            .await?;
+        progress.finish_playlist().await;
+        self.notify_playlist_imported(user, &playlist).await;
+
+        Ok(())
+    }
+
+    /// Stores a Spotify track as one of our musics and returns its id
+    async fn save_track(&self, track: FullTrack) -> Result<i32, AppError> {
+        let artist_name = track
+            .artists
+            .first()
+            .map(|a| a.name.clone())
+            .unwrap_or_default();
+        let track_title = track.name.clone();
+
+        let genre = None;
+
+        let create_music_request = CreateMusicRequest {
+            title: track_title,
+            release_date: track
+                .album
+                .release_date
+                .unwrap_or_default()
+                .parse::<NaiveDate>()
+                .unwrap_or_default(),
+            genre,
+            artist: artist_name,
+            album: track.album.name,
+            description: None,
+            isrc: track.external_ids.get("isrc").cloned(),
+            duration_ms: i32::try_from(track.duration.num_milliseconds()).ok(),
+            explicit: Some(track.explicit),
+            // Spotify lists album images from the largest to the smallest
+            artwork_url: track.album.images.first().map(|i| i.url.clone()),
+            disc_number: Some(track.disc_number),
+            track_number: i32::try_from(track.track_number).ok(),
+            preview_url: track.preview_url.clone(),
+            artists: ArtistCredit::from_names(
+                track.artists.iter().map(|artist| artist.name.clone()),
+            ),
+        };
+        let music = self.music_service.create(create_music_request).await?;
+        if let Some(track_id) = &track.id {
+            self.music_service
+                .save_external_id(music.id, PlaylistOrigin::Spotify, track_id.id())
                 .await?;
         }
+        Ok(music.id)
+    }
+
+    async fn notify_playlist_imported(&self, user: &UserModel, playlist: &PlaylistModel) {
+        EventHub::global().publish(
+            user.id,
+            UserEvent::PlaylistImported {
+                playlist_id: playlist.id,
+                origin: PlaylistOrigin::Spotify,
+            },
+        );
 
         // Send silent notification with only playlist ID (lightweight)
         let notification_data = serde_json::json!({
@@ -456,8 +488,6 @@ impl SpotifyService {
                 info!("Silent notification sent for playlist: {}", playlist.name);
             }
         }
-
-        Ok(())
     }
 
     pub async fn get_spotify_client(&self) -> Result<AuthCodeSpotify, AppError> {
@@ -481,7 +511,7 @@ impl SpotifyService {
                 expires_at: Some(token.expires_at.into()),
                 expires_in: expires_in.clone(),
                 scopes: scopes!(
-                    "playlist-read-private playlist-modify-public playlist-modify-private user-read-email"
+                    "playlist-read-private playlist-modify-public playlist-modify-private user-read-email user-library-read"
                 ),
             })));
             if expires_in.num_seconds() < 0 {
@@ -515,17 +545,71 @@ impl SpotifyService {
     pub fn get_oauth(&self) -> OAuth {
         OAuth {
             redirect_uri: "https://swaptun.com/open/spotify".to_string(),
-            scopes: scopes!("playlist-read-private playlist-modify-public playlist-modify-private"),
+            scopes: scopes!(
+                "playlist-read-private playlist-modify-public playlist-modify-private user-library-read"
+            ),
             ..Default::default()
         }
     }
 
-    /// Creates a playlist on Spotify from a database playlist and adds matching tracks
+    /// Searches Spotify for every track of a database playlist without writing anything
+    pub async fn match_playlist_tracks(
+        &self,
+        playlist_id: i32,
+        user: &UserModel,
+        progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError> {
+        let playlist = self.playlist_service.get_playlist(playlist_id).await?;
+        let spotify = self.get_spotify_client_connected(user).await?;
+
+        let tracks = self
+            .music_service
+            .find_by_playlist(&playlist)
+            .await
+            .map_err(|e| {
+                error!("Error getting playlist tracks: {:?}", e);
+                AppError::InternalServerError
+            })?;
+
+        let overrides = self
+            .track_match_override_service
+            .get_overrides(user, PlaylistOrigin::Spotify)
+            .await?;
+
+        progress.start_playlist(&playlist.name, tracks.len()).await;
+
+        let mut report = Vec::new();
+        for track in tracks {
+            let entry = if let Some(platform_track_id) = find_override(&overrides, &track) {
+                TrackMatchEntry::overridden(&track, platform_track_id)
+            } else if let Some(external_id) = self
+                .music_service
+                .find_external_id(&track, PlaylistOrigin::Spotify)
+                .await?
+            {
+                TrackMatchEntry::known(&track, &external_id)
+            } else {
+                match self.find_spotify_track(&spotify, &track).await {
+                    Some(best) => best.report_entry(&track, best.item.id().to_string()),
+                    None => TrackMatchEntry::not_found(&track),
+                }
+            };
+            progress.track_matched(&entry).await;
+            report.push(entry);
+        }
+
+        progress.finish_playlist().await;
+        Ok(report)
+    }
+
+    /// Creates a playlist on Spotify from a database playlist and adds the matched tracks
     pub async fn create_spotify_playlist_from_db(
         &self,
         playlist_id: i32,
         user: &UserModel,
-    ) -> Result<String, AppError> {
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
         // Get the database playlist
         let playlist = self.playlist_service.get_playlist(playlist_id).await?;
 
@@ -555,106 +639,7 @@ impl SpotifyService {
 
         info!("Created Spotify playlist with ID: {}", new_playlist.id);
 
-        // Get tracks from the database playlist
-        let tracks = self
-            .music_service
-            .find_by_playlist(&playlist)
-            .await
-            .map_err(|e| {
-                error!("Error getting playlist tracks: {:?}", e);
-                AppError::InternalServerError
-            })?;
-
-        if tracks.is_empty() {
-            info!("No tracks in playlist, returning early");
-            return Ok(new_playlist.id.to_string());
-        }
-
-        // Search for tracks on Spotify and collect their IDs
-        let mut spotify_track_ids = Vec::new();
-        let mut not_found_tracks = Vec::new();
-
-        for track in tracks {
-            // Search for the track on Spotify
-            let query = format!("track:{} artist:{}", track.title, track.artist);
-
-            match spotify
-                .search(
-                    &query,
-                    rspotify::model::SearchType::Track,
-                    None,
-                    None,
-                    Some(5),
-                    None,
-                )
-                .await
-            {
-                Ok(SearchResult::Tracks(track_result)) => {
-                    for spotify_track in track_result.items.iter().take(5) {
-                        if let Some(track_id) = &spotify_track.id {
-                            // Use fuzzy matching for both title and artist
-                            let title_matches = fuzzy_match(&spotify_track.name, &track.title);
-                            let artist_matches = artists_match(&spotify_track.artists, &track.artist);
-
-                            if title_matches && artist_matches {
-                                spotify_track_ids.push(track_id.clone());
-                                info!(
-                                    "Found track on Spotify: {} - {} (matched with: {} - {})",
-                                    track.artist,
-                                    track.title,
-                                    spotify_track.artists.first().map_or("", |a| a.name.as_str()),
-                                    spotify_track.name
-                                );
-                                break;
-                            } else {
-                                info!(
-                                    "Track found but does not match - Title match: {}, Artist match: {} | Spotify: {} - {}, DB: {} - {}",
-                                    title_matches,
-                                    artist_matches,
-                                    spotify_track.artists.first().map_or("", |a| a.name.as_str()),
-                                    spotify_track.name,
-                                    track.artist,
-                                    track.title
-                                );
-                            }
-                        } else {
-                            info!(
-                                "Track found but has no ID: {} - {}",
-                                track.artist, track.title
-                            );
-                        }
-                    }
-                    // Si aucun des 5 premiers ne correspond, on ajoute à not_found_tracks
-                    if !spotify_track_ids.iter().any(|id| {
-                        track_result
-                            .items
-                            .iter()
-                            .take(5)
-                            .any(|t| t.id.as_ref() == Some(id))
-                    }) {
-                        not_found_tracks.push(format!("{} - {}", track.artist, track.title));
-                        info!(
-                            "Track not found on Spotify: {} - {}",
-                            track.artist, track.title
-                        );
-                    }
-                }
-                Ok(_) => {
-                    not_found_tracks.push(format!("{} - {}", track.artist, track.title));
-                    info!(
-                        "Unexpected search result for track: {} - {}",
-                        track.artist, track.title
-                    );
-                }
-                Err(e) => {
-                    error!(
-                        "Error searching for track {} - {}: {:?}",
-                        track.artist, track.title, e
-                    );
-                    not_found_tracks.push(format!("{} - {}", track.artist, track.title));
-                }
-            }
-        }
+        let spotify_track_ids = spotify_track_ids(&report);
 
         // Add tracks to the Spotify playlist in batches of 100 (Spotify's limit)
         if !spotify_track_ids.is_empty() {
@@ -670,6 +655,7 @@ impl SpotifyService {
                 {
                     Ok(_) => {
                         info!("Added {} tracks to Spotify playlist", chunk.len());
+                        progress.tracks_done(chunk.len()).await;
                     }
                     Err(e) => {
                         error!("Error adding tracks to Spotify playlist: {:?}", e);
@@ -679,15 +665,244 @@ impl SpotifyService {
             }
         }
 
-        // Log tracks that weren't found
-        if !not_found_tracks.is_empty() {
-            info!("The following tracks were not found on Spotify:");
-            for track in &not_found_tracks {
-                info!("  - {}", track);
+        info!(
+            "Exported {} of {} tracks to Spotify",
+            spotify_track_ids.len(),
+            report.len()
+        );
+
+        Ok(PlaylistExport {
+            playlist_id: new_playlist.id.to_string(),
+            report,
+        })
+    }
+
+    /// Replaces the tracks of a Spotify playlist created by a previous send with the matched
+    /// ones, leaving it untouched when it already holds them in order
+    pub async fn update_spotify_playlist(
+        &self,
+        platform_playlist_id: &str,
+        user: &UserModel,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        let spotify = self.get_spotify_client_connected(user).await?;
+        let playlist_id = PlaylistId::from_id(platform_playlist_id.to_string()).map_err(|e| {
+            error!(
+                "Invalid Spotify playlist id {}: {:?}",
+                platform_playlist_id, e
+            );
+            AppError::NotFound(format!(
+                "Spotify playlist {} not found",
+                platform_playlist_id
+            ))
+        })?;
+
+        // Deleting a Spotify playlist only unfollows it, it is then gone for the user
+        let spotify_user = spotify.me().await.map_err(|e| {
+            error!("Error getting Spotify user: {:?}", e);
+            AppError::InternalServerError
+        })?;
+        let followed = spotify
+            .playlist_check_follow(playlist_id.clone(), &[spotify_user.id])
+            .await
+            .map_err(|e| {
+                error!("Error checking Spotify playlist {}: {:?}", playlist_id, e);
+                AppError::InternalServerError
+            })?;
+        if !followed.first().copied().unwrap_or(false) {
+            return Err(AppError::NotFound(format!(
+                "Spotify playlist {} not found",
+                platform_playlist_id
+            )));
+        }
+
+        let mut current = Vec::new();
+        let mut items = spotify.playlist_items(playlist_id.clone(), None, None);
+        while let Some(item) = items.next().await {
+            let item = item.map_err(|e| {
+                error!("Error getting Spotify playlist items: {:?}", e);
+                AppError::InternalServerError
+            })?;
+            if let Some(PlayableItem::Track(track)) = item.track {
+                if let Some(id) = track.id {
+                    current.push(id.id().to_string());
+                }
+            }
+        }
+
+        let spotify_track_ids = spotify_track_ids(&report);
+        let desired: Vec<String> = spotify_track_ids
+            .iter()
+            .map(|id| id.id().to_string())
+            .collect();
+
+        if PlaylistDiff::between(&current, &desired).is_empty() {
+            info!("Spotify playlist {} is already up to date", playlist_id);
+            progress.tracks_done(desired.len()).await;
+        } else {
+            // Spotify replaces at most 100 items at once, the others are appended
+            let mut chunks = spotify_track_ids.chunks(100);
+            let first: Vec<PlayableId> = chunks
+                .next()
+                .unwrap_or_default()
+                .iter()
+                .map(|id| PlayableId::from(id.clone()))
+                .collect();
+            let replaced = first.len();
+            spotify
+                .playlist_replace_items(playlist_id.clone(), first)
+                .await
+                .map_err(|e| {
+                    error!("Error replacing Spotify playlist items: {:?}", e);
+                    AppError::InternalServerError
+                })?;
+            progress.tracks_done(replaced).await;
+            for chunk in chunks {
+                let track_ids: Vec<PlayableId> = chunk
+                    .iter()
+                    .map(|id| PlayableId::from(id.clone()))
+                    .collect();
+                spotify
+                    .playlist_add_items(playlist_id.clone(), track_ids, None)
+                    .await
+                    .map_err(|e| {
+                        error!("Error adding tracks to Spotify playlist: {:?}", e);
+                        AppError::InternalServerError
+                    })?;
+                progress.tracks_done(chunk.len()).await;
+            }
+            info!(
+                "Updated Spotify playlist {} with {} tracks",
+                playlist_id,
+                spotify_track_ids.len()
+            );
+        }
+
+        Ok(PlaylistExport {
+            playlist_id: platform_playlist_id.to_string(),
+            report,
+        })
+    }
+
+    /// Searches Spotify for a track of our database and returns the id of the best matching result
+    pub async fn find_spotify_track(
+        &self,
+        spotify: &AuthCodeSpotify,
+        track: &MusicModel,
+    ) -> Option<ScoredMatch<TrackId<'static>>> {
+        if let Some(isrc) = &track.isrc {
+            if let Some(found) = self.find_spotify_track_by_isrc(spotify, isrc).await {
+                info!(
+                    "Found track on Spotify by ISRC {}: {} - {}",
+                    isrc, track.artist, track.title
+                );
+                return Some(found);
+            }
+        }
+
+        let query = format!("track:{} artist:{}", track.title, track.artist);
+
+        match spotify
+            .search(
+                &query,
+                rspotify::model::SearchType::Track,
+                None,
+                None,
+                Some(5),
+                None,
+            )
+            .await
+        {
+            Ok(SearchResult::Tracks(track_result)) => {
+                let candidates = track_result.items.into_iter().filter_map(|spotify_track| {
+                    Some(TrackCandidate {
+                        item: spotify_track.id?,
+                        title: spotify_track.name,
+                        artists: spotify_track.artists.into_iter().map(|a| a.name).collect(),
+                        album: Some(spotify_track.album.name),
+                        duration_ms: u32::try_from(spotify_track.duration.num_milliseconds()).ok(),
+                    })
+                });
+
+                match self
+                    .matcher
+                    .best_match(&TrackQuery::from(track), candidates)
+                {
+                    Some(best) => {
+                        info!(
+                            "Found track on Spotify: {} - {} (id: {}, confidence: {:.2})",
+                            track.artist,
+                            track.title,
+                            best.item.id(),
+                            best.score
+                        );
+                        Some(best)
+                    }
+                    None => {
+                        info!(
+                            "Track not found on Spotify: {} - {}",
+                            track.artist, track.title
+                        );
+                        None
+                    }
+                }
+            }
+            Ok(_) => {
+                info!(
+                    "Unexpected search result for track: {} - {}",
+                    track.artist, track.title
+                );
+                None
+            }
+            Err(e) => {
+                error!(
+                    "Error searching for track {} - {}: {:?}",
+                    track.artist, track.title, e
+                );
+                None
             }
         }
+    }
 
-        Ok(new_playlist.id.to_string())
+    /// Looks a track up by its ISRC, which identifies the exact recording
+    async fn find_spotify_track_by_isrc(
+        &self,
+        spotify: &AuthCodeSpotify,
+        isrc: &str,
+    ) -> Option<ScoredMatch<TrackId<'static>>> {
+        let query = format!("isrc:{}", isrc);
+
+        match spotify
+            .search(
+                &query,
+                rspotify::model::SearchType::Track,
+                None,
+                None,
+                Some(1),
+                None,
+            )
+            .await
+        {
+            Ok(SearchResult::Tracks(track_result)) => {
+                track_result.items.into_iter().find_map(|spotify_track| {
+                    Some(ScoredMatch::exact(
+                        spotify_track.id?,
+                        spotify_track.name,
+                        spotify_track
+                            .artists
+                            .first()
+                            .map(|a| a.name.clone())
+                            .unwrap_or_default(),
+                    ))
+                })
+            }
+            Ok(_) => None,
+            Err(e) => {
+                error!("Error searching Spotify for ISRC {}: {:?}", isrc, e);
+                None
+            }
+        }
     }
 
     pub async fn disconnect(&self, user: &UserModel) -> Result<(), AppError> {
@@ -714,3 +929,102 @@ impl SpotifyService {
         Ok(())
     }
 }
+
+#[async_trait]
+impl MusicProvider for SpotifyService {
+    fn origin(&self) -> PlaylistOrigin {
+        PlaylistOrigin::Spotify
+    }
+
+    async fn connect(&self, user: &UserModel, request: AddTokenRequest) -> Result<(), AppError> {
+        self.add_token(request, user.clone()).await
+    }
+
+    async fn list_playlists(&self, user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError> {
+        SpotifyService::list_playlists(self, user).await
+    }
+
+    async fn import_playlists(
+        &self,
+        user: &UserModel,
+        selection: &ImportSelection,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        self.get_user_playlists(user.clone(), selection, progress)
+            .await
+            .map(|_| ())
+    }
+
+    async fn search_track(
+        &self,
+        user: &UserModel,
+        music: &MusicModel,
+    ) -> Result<TrackMatchEntry, AppError> {
+        let spotify = self.get_spotify_client_connected(user).await?;
+        Ok(match self.find_spotify_track(&spotify, music).await {
+            Some(best) => best.report_entry(music, best.item.id().to_string()),
+            None => TrackMatchEntry::not_found(music),
+        })
+    }
+
+    async fn preview_playlist(
+        &self,
+        user: &UserModel,
+        playlist_id: i32,
+        progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError> {
+        self.match_playlist_tracks(playlist_id, user, progress)
+            .await
+    }
+
+    async fn create_playlist_from_matches(
+        &self,
+        user: &UserModel,
+        playlist_id: i32,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        self.create_spotify_playlist_from_db(playlist_id, user, report, progress)
+            .await
+    }
+
+    async fn update_playlist_from_matches(
+        &self,
+        user: &UserModel,
+        platform_playlist_id: &str,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        self.update_spotify_playlist(platform_playlist_id, user, report, progress)
+            .await
+    }
+
+    async fn connected_users(&self) -> Result<Vec<i32>, AppError> {
+        Ok(self
+            .spotify_token_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|token| token.user_id)
+            .collect())
+    }
+
+    async fn disconnect(&self, user: &UserModel) -> Result<(), AppError> {
+        SpotifyService::disconnect(self, user).await
+    }
+}
+
+/// Spotify ids of the matched tracks of a report, in playlist order
+fn spotify_track_ids(report: &[TrackMatchEntry]) -> Vec<TrackId<'static>> {
+    report
+        .iter()
+        .filter_map(|entry| entry.platform_track_id.clone())
+        .filter_map(|id| match TrackId::from_id(id) {
+            Ok(track_id) => Some(track_id),
+            Err(e) => {
+                error!("Invalid Spotify track id: {:?}", e);
+                None
+            }
+        })
+        .collect()
+}
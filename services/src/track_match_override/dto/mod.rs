@@ -0,0 +1,2 @@
+pub mod track_match_override_request;
+pub use track_match_override_request::*;
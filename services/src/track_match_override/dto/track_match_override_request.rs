@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use swaptun_models::PlaylistOrigin;
+use validator::Validate;
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct SetTrackMatchOverrideRequest {
+    pub music_id: i32,
+    pub origin: PlaylistOrigin,
+    #[validate(length(min = 1, message = "Platform track id cannot be empty"))]
+    pub platform_track_id: String,
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct ClearTrackMatchOverrideRequest {
+    pub music_id: i32,
+    pub origin: PlaylistOrigin,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_override_request_deserialization() {
+        let json = r#"{"music_id":42,"origin":"Deezer","platform_track_id":"3135556"}"#;
+        let request: SetTrackMatchOverrideRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.music_id, 42);
+        assert_eq!(request.origin, PlaylistOrigin::Deezer);
+        assert_eq!(request.platform_track_id, "3135556");
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_set_override_request_rejects_empty_track_id() {
+        let request = SetTrackMatchOverrideRequest {
+            music_id: 42,
+            origin: PlaylistOrigin::Spotify,
+            platform_track_id: String::new(),
+        };
+
+        assert!(request.validate().is_err());
+    }
+}
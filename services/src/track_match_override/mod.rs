@@ -0,0 +1,6 @@
+pub mod dto;
+pub use dto::*;
+#[cfg(feature = "full")]
+pub mod track_match_override_service;
+#[cfg(feature = "full")]
+pub use track_match_override_service::*;
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use log::info;
+use sea_orm::{ActiveValue::Set, DatabaseConnection, IntoActiveModel};
+use swaptun_models::{MusicModel, PlaylistOrigin, TrackMatchOverrideActiveModel, UserModel};
+use swaptun_repositories::TrackMatchOverrideRepository;
+
+use crate::error::AppError;
+use crate::{ClearTrackMatchOverrideRequest, MusicService, SetTrackMatchOverrideRequest};
+
+/// Platform track ids chosen by a user, keyed by the id of our music
+pub type TrackMatchOverrides = HashMap<i32, String>;
+
+#[derive(Clone)]
+pub struct TrackMatchOverrideService {
+    track_match_override_repository: TrackMatchOverrideRepository,
+    music_service: MusicService,
+}
+
+impl TrackMatchOverrideService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            track_match_override_repository: TrackMatchOverrideRepository::new(db.clone()),
+            music_service: MusicService::new(db),
+        }
+    }
+
+    /// Stores the platform track the user picked for one of our musics, replacing any previous one
+    pub async fn set_override(
+        &self,
+        user: &UserModel,
+        request: SetTrackMatchOverrideRequest,
+    ) -> Result<(), AppError> {
+        self.music_service
+            .find_by_id(request.music_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Music not found".to_string()))?;
+
+        let existing = self
+            .track_match_override_repository
+            .find(user.id, request.music_id, request.origin.clone())
+            .await?;
+
+        let model = match existing {
+            Some(model) => {
+                let mut active_model = model.into_active_model();
+                active_model.platform_track_id = Set(request.platform_track_id);
+                active_model
+            }
+            None => TrackMatchOverrideActiveModel {
+                user_id: Set(user.id),
+                music_id: Set(request.music_id),
+                origin: Set(request.origin),
+                platform_track_id: Set(request.platform_track_id),
+                ..Default::default()
+            },
+        };
+        self.track_match_override_repository.save(model).await?;
+
+        info!("Track match override saved for user {}", user.id);
+        Ok(())
+    }
+
+    pub async fn clear_override(
+        &self,
+        user: &UserModel,
+        request: ClearTrackMatchOverrideRequest,
+    ) -> Result<(), AppError> {
+        let result = self
+            .track_match_override_repository
+            .delete(user.id, request.music_id, request.origin)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound(
+                "Track match override not found".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Loads every override of the user for a platform, to be looked up before searching
+    pub async fn get_overrides(
+        &self,
+        user: &UserModel,
+        origin: PlaylistOrigin,
+    ) -> Result<TrackMatchOverrides, AppError> {
+        let overrides = self
+            .track_match_override_repository
+            .find_by_user_and_origin(user.id, origin)
+            .await?;
+
+        Ok(overrides
+            .into_iter()
+            .map(|model| (model.music_id, model.platform_track_id))
+            .collect())
+    }
+}
+
+/// Returns the platform track id the user picked for `music`, if any
+pub fn find_override<'a>(
+    overrides: &'a TrackMatchOverrides,
+    music: &MusicModel,
+) -> Option<&'a str> {
+    overrides.get(&music.id).map(String::as_str)
+}
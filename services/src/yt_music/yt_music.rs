@@ -2,24 +2,33 @@ use std::{collections::HashMap, sync::Arc};
 
 use once_cell::sync::Lazy;
 use sea_orm::{DatabaseConnection, IntoActiveModel, Set};
-use swaptun_models::{PlaylistOrigin, UserModel, YoutubeTokenActiveModel, YoutubeTokenModel};
+use swaptun_models::{
+    MusicModel, PlaylistModel, PlaylistOrigin, UserModel, YoutubeTokenActiveModel,
+    YoutubeTokenModel,
+};
 use swaptun_repositories::YoutubeTokenRepository;
 use tokio::sync::Mutex;
 use ytmapi_rs::{
     auth::OAuthToken,
-    common::{AlbumID, VideoID, YoutubeID},
+    common::{AlbumID, Explicit, PlaylistID, SetVideoID, VideoID, YoutubeID},
     parse::{GetAlbum, LibraryPlaylist, PlaylistItem, PlaylistSong},
     query::{playlist::PrivacyStatus, CreatePlaylistQuery},
     YtMusic,
 };
 
-use crate::AddTokenRequest;
 use crate::{
     error::AppError, music::dto::CreateMusicRequest, music::music_service::MusicService,
     notification::notification::NotificationService, playlist::playlist_service::PlaylistService,
     CreatePlaylistRequest, YoutubeUrlResponse,
 };
-use apple_music_api::catalog::Artist;
+use crate::{
+    find_override, import_each, report_import, AddTokenRequest, ArtistCredit, EventHub,
+    ImportSelection, JobPayload, JobProgress, JobService, MusicProvider, PlaylistDiff,
+    PlaylistExport, RemotePlaylist, ScoredMatch, TrackCandidate, TrackMatchEntry,
+    TrackMatchOverrideService, TrackMatcher, TrackQuery, UserEvent, LIKED_SONGS_NAME,
+    LIKED_SONGS_ORIGIN_ID,
+};
+use async_trait::async_trait;
 use log::{error, info};
 use oauth2::{
     basic::{BasicClient, BasicErrorResponseType, BasicTokenType},
@@ -29,35 +38,22 @@ use oauth2::{
     StandardTokenIntrospectionResponse, StandardTokenResponse, TokenResponse, TokenUrl,
 };
 use std::env::var;
+
+/// Auto playlist of the songs the user liked on YouTube Music
+const LIKED_MUSIC_PLAYLIST_ID: &str = "LM";
+
 static VERIFIER_STORE: Lazy<Mutex<HashMap<i32, PkceCodeVerifier>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 use ytmapi_rs::query::playlist::GetWatchPlaylistQueryID;
 
-/// Normalize a string for simple comparison (lowercase + alphanumeric only)
-fn normalize_string(s: &str) -> String {
-    s.to_lowercase()
-        .chars()
-        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-        .collect::<String>()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
-/// Simple permissive match: check if one string contains the other
-fn simple_match(str1: &str, str2: &str) -> bool {
-    let norm1 = normalize_string(str1);
-    let norm2 = normalize_string(str2);
-
-    // Check if one contains the other
-    norm1.contains(&norm2) || norm2.contains(&norm1)
-}
-
 #[derive(Clone)]
 pub struct YoutubeMusicService {
     youtube_token_repository: YoutubeTokenRepository,
     playlist_service: PlaylistService,
     music_service: MusicService,
+    matcher: TrackMatcher,
+    track_match_override_service: TrackMatchOverrideService,
+    job_service: JobService,
     db: Arc<DatabaseConnection>,
 }
 
@@ -71,6 +67,9 @@ impl YoutubeMusicService {
             youtube_token_repository,
             playlist_service,
             music_service,
+            matcher: TrackMatcher::from_env(),
+            track_match_override_service: TrackMatchOverrideService::new(db.clone()),
+            job_service: JobService::new(db.clone()),
             db,
         }
     }
@@ -173,7 +172,14 @@ impl YoutubeMusicService {
 
         info!("Successfully saved token for user {}", user.id);
 
-        self.get_user_playlists(&user).await?;
+        self.job_service
+            .enqueue(
+                user.id,
+                JobPayload::ImportPlaylists {
+                    origin: PlaylistOrigin::YoutubeMusic,
+                },
+            )
+            .await?;
 
         Ok(())
     }
@@ -334,7 +340,12 @@ impl YoutubeMusicService {
         Ok(client)
     }
 
-    pub async fn get_user_playlists(&self, user: &UserModel) -> Result<(), AppError> {
+    pub async fn get_user_playlists(
+        &self,
+        user: &UserModel,
+        selection: &ImportSelection,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
         let client = self.get_ytmusic_client(user).await?;
         let playlists = client.get_library_playlists().await.map_err(|e| {
             error!("Failed to get library playlists: {:?}", e);
@@ -343,47 +354,62 @@ impl YoutubeMusicService {
 
         info!("Found {} YouTube Music playlists", playlists.len());
 
-        // Spawn background task to import playlists
-        let service = self.clone();
-        let user_clone = user.clone();
-        let playlists_clone = playlists.clone();
+        // The liked songs playlist is imported below on its own
+        let playlists = playlists.into_iter().filter(|playlist| {
+            playlist.playlist_id.get_raw() != LIKED_MUSIC_PLAYLIST_ID
+                && selection.includes(&playlist.playlist_id.get_playlist_id().to_string())
+        });
+        let client = &client;
+        let mut result = import_each(
+            playlists,
+            |playlist| playlist.title.clone(),
+            progress,
+            |playlist| async move {
+                self.import_playlist(&playlist, user, client, progress)
+                    .await
+            },
+        )
+        .await;
+        if selection.includes(LIKED_SONGS_ORIGIN_ID) {
+            let liked_songs = self.import_liked_songs(user, client, progress).await;
+            result = result.and(report_import(LIKED_SONGS_NAME, liked_songs, progress).await);
+        }
 
-        tokio::spawn(async move {
-            info!(
-                "Starting background import of {} YouTube Music playlists",
-                playlists_clone.len()
-            );
+        result
+    }
 
-            match service.get_ytmusic_client(&user_clone).await {
-                Ok(client) => {
-                    for playlist in playlists_clone {
-                        if let Err(e) = service
-                            .import_playlist(&playlist, &user_clone, &client)
-                            .await
-                        {
-                            error!(
-                                "Error importing YouTube Music playlist {:?}: {:?}",
-                                playlist.title, e
-                            );
-                        } else {
-                            info!(
-                                "Successfully imported YouTube Music playlist: {:?}",
-                                playlist.title
-                            );
-                        }
-                    }
-                    info!("Background import of YouTube Music playlists completed");
-                }
-                Err(e) => {
-                    error!(
-                        "Failed to get YouTube Music client for background import: {:?}",
-                        e
-                    );
-                }
-            }
-        });
+    /// Lists the liked songs and the playlists of the user's library without importing them.
+    /// The library listing neither counts the tracks nor tells followed playlists apart.
+    pub async fn list_playlists(&self, user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError> {
+        let client = self.get_ytmusic_client(user).await?;
+        let playlists = client.get_library_playlists().await.map_err(|e| {
+            error!("Failed to get library playlists: {:?}", e);
+            AppError::InternalServerError
+        })?;
 
-        Ok(())
+        let liked_songs = RemotePlaylist {
+            id: LIKED_SONGS_ORIGIN_ID.to_string(),
+            name: LIKED_SONGS_NAME.to_string(),
+            track_count: None,
+            owned: Some(true),
+            image_url: None,
+            selected: false,
+        };
+        Ok(std::iter::once(liked_songs)
+            .chain(
+                playlists
+                    .into_iter()
+                    .filter(|playlist| playlist.playlist_id.get_raw() != LIKED_MUSIC_PLAYLIST_ID)
+                    .map(|playlist| RemotePlaylist {
+                        id: playlist.playlist_id.get_playlist_id().to_string(),
+                        name: playlist.title,
+                        track_count: None,
+                        owned: None,
+                        image_url: None,
+                        selected: false,
+                    }),
+            )
+            .collect())
     }
 
     pub async fn get_playlist_track(
@@ -391,13 +417,20 @@ impl YoutubeMusicService {
         playlist: &LibraryPlaylist,
         client: &YtMusic<OAuthToken>,
     ) -> Result<Vec<PlaylistSong>, AppError> {
-        let tracks: Vec<PlaylistItem> = match client
-            .get_playlist_tracks(playlist.playlist_id.clone())
+        self.get_playlist_songs(playlist.playlist_id.clone(), client)
             .await
+    }
+
+    async fn get_playlist_songs(
+        &self,
+        playlist_id: PlaylistID<'_>,
+        client: &YtMusic<OAuthToken>,
+    ) -> Result<Vec<PlaylistSong>, AppError> {
+        let tracks: Vec<PlaylistItem> = match client.get_playlist_tracks(playlist_id.clone()).await
         {
             Ok(tracks) => tracks,
             Err(e) => {
-                error!("Failed to load playlist {:?}: {}", playlist.playlist_id, e);
+                error!("Failed to load playlist {:?}: {}", playlist_id, e);
                 return Err(AppError::InternalServerError);
             }
         };
@@ -421,6 +454,7 @@ impl YoutubeMusicService {
         playlist: &LibraryPlaylist,
         user: &UserModel,
         client: &YtMusic<OAuthToken>,
+        progress: &JobProgress,
     ) -> Result<(), AppError> {
         let request = CreatePlaylistRequest {
             name: playlist.title.clone(),
@@ -429,14 +463,46 @@ impl YoutubeMusicService {
             origin_id: playlist.playlist_id.get_playlist_id().to_string(),
             image_url: None, // YouTube Music LibraryPlaylist doesn't expose image URLs
         };
+        // Synced even when empty so tracks removed on YouTube Music are removed here too
         let tracks = self.get_playlist_track(playlist, client).await?;
-        if tracks.is_empty() {
-            info!("No tracks found in playlist: {}", playlist.title);
-            return Ok(());
-        }
+        progress.start_playlist(&playlist.title, tracks.len()).await;
         let playlist_model = self.playlist_service.create_or_get(request, &user).await?;
 
-        let mut local_tracks = self.music_service.find_by_playlist(&playlist_model).await?;
+        self.sync_songs(user, &playlist_model, tracks, client, progress)
+            .await
+    }
+
+    /// Imports the songs liked on YouTube Music as the user's liked songs playlist
+    pub async fn import_liked_songs(
+        &self,
+        user: &UserModel,
+        client: &YtMusic<OAuthToken>,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        let request = CreatePlaylistRequest::liked_songs(PlaylistOrigin::YoutubeMusic);
+        let tracks = self
+            .get_playlist_songs(
+                PlaylistID::from_raw(LIKED_MUSIC_PLAYLIST_ID.to_string()),
+                client,
+            )
+            .await?;
+        progress.start_playlist(&request.name, tracks.len()).await;
+        let playlist_model = self.playlist_service.create_or_get(request, user).await?;
+
+        self.sync_songs(user, &playlist_model, tracks, client, progress)
+            .await
+    }
+
+    /// Stores the songs as our musics and makes them the contents of `playlist_model`
+    async fn sync_songs(
+        &self,
+        user: &UserModel,
+        playlist_model: &PlaylistModel,
+        tracks: Vec<PlaylistSong>,
+        client: &YtMusic<OAuthToken>,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        let mut music_ids = Vec::new();
 
         for track in tracks {
             let artist = match track.artists.clone().first() {
@@ -445,13 +511,6 @@ impl YoutubeMusicService {
                     continue; // Skip if no artist is found
                 }
             };
-            if let Some(pos) = local_tracks.iter().position(|local_track| {
-                local_track.title == track.title
-                    && local_track.artist == artist
-                    && local_track.album == track.album.name.clone()
-            }) {
-                local_tracks.remove(pos);
-            }
             let album_info = self.get_album_info(&client, &track.album.id).await?;
 
             let create_music_request = CreateMusicRequest {
@@ -466,20 +525,45 @@ impl YoutubeMusicService {
                 artist: artist,
                 album: track.album.name,
                 description: None,
+                isrc: None,
+                duration_ms: parse_duration_ms(&track.duration),
+                explicit: Some(matches!(track.explicit, Explicit::IsExplicit)),
+                artwork_url: track
+                    .thumbnails
+                    .iter()
+                    .max_by_key(|thumbnail| thumbnail.width)
+                    .map(|thumbnail| thumbnail.url.clone()),
+                disc_number: None,
+                track_number: None,
+                preview_url: None,
+                artists: ArtistCredit::from_names(
+                    track.artists.iter().map(|artist| artist.name.clone()),
+                ),
             };
             let music = self.music_service.create(create_music_request).await?;
-            self.playlist_service
-                .add_music(&playlist_model, music)
+            self.music_service
+                .save_external_id(
+                    music.id,
+                    PlaylistOrigin::YoutubeMusic,
+                    track.video_id.get_raw(),
+                )
                 .await?;
+            music_ids.push(music.id);
+            progress.track_done().await;
         }
-        info!("Tracks to remove: {:?}", local_tracks);
 
-        for local_track in local_tracks {
-            info!("Removing track from playlist: {:?}", local_track);
-            self.playlist_service
-                .remove_music(&playlist_model, &local_track)
-                .await?;
-        }
+        self.playlist_service
+            .sync_musics(playlist_model, &music_ids)
+            .await?;
+        progress.finish_playlist().await;
+
+        EventHub::global().publish(
+            user.id,
+            UserEvent::PlaylistImported {
+                playlist_id: playlist_model.id,
+                origin: PlaylistOrigin::YoutubeMusic,
+            },
+        );
 
         // Send silent notification with only playlist ID (lightweight)
         let notification_data = serde_json::json!({
@@ -496,10 +580,13 @@ impl YoutubeMusicService {
             {
                 error!(
                     "Failed to send silent notification for playlist {}: {:?}",
-                    playlist.title, e
+                    playlist_model.name, e
                 );
             } else {
-                info!("Silent notification sent for playlist: {}", playlist.title);
+                info!(
+                    "Silent notification sent for playlist: {}",
+                    playlist_model.name
+                );
             }
         }
 
@@ -526,11 +613,13 @@ impl YoutubeMusicService {
             }
         }
     }
-    pub async fn import_playlist_in_yt(
+    /// Searches YouTube Music for every track of a database playlist without writing anything
+    pub async fn match_playlist_tracks(
         &self,
         user: &UserModel,
         playlist_id: i32,
-    ) -> Result<String, AppError> {
+        progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError> {
         let client = match self.get_ytmusic_client(user).await {
             Ok(client) => client,
             Err(e) => {
@@ -542,49 +631,69 @@ impl YoutubeMusicService {
         let playlist = self.playlist_service.get_playlist(playlist_id).await?;
         let tracks = self.music_service.find_by_playlist(&playlist).await?;
 
-        if tracks.is_empty() {
-            info!("No tracks in playlist, returning early");
-            return Ok(String::new());
-        }
+        let overrides = self
+            .track_match_override_service
+            .get_overrides(user, PlaylistOrigin::YoutubeMusic)
+            .await?;
 
-        let mut youtube_tracks_id = Vec::new();
+        progress.start_playlist(&playlist.name, tracks.len()).await;
 
+        let mut report = Vec::new();
         for track in tracks {
-            match client.search_songs(track.title.clone()).await {
-                Ok(youtube_tracks) => {
-                    let mut found = false;
-                    for youtube_track in youtube_tracks {
-                        // Simple permissive matching: check if DB name is included in YouTube name or vice versa
-                        let title_matches = simple_match(&youtube_track.title, &track.title);
-                        let artist_matches = simple_match(&youtube_track.artist, &track.artist);
-
-                        if title_matches && artist_matches {
-                            info!(
-                                "Found track on YouTube Music: {} - {} (matched with: {} - {})",
-                                track.artist,
-                                track.title,
-                                youtube_track.artist,
-                                youtube_track.title
-                            );
-                            youtube_tracks_id.push(youtube_track);
-                            found = true;
-                            break;
-                        }
-                    }
-                    if !found {
-                        info!(
-                            "Track not found on YouTube Music: {} - {}",
-                            track.artist, track.title
-                        );
-                    }
-                }
-                Err(e) => {
-                    error!("Failed to search for track {}: {:?}", track.title, e);
-                    continue;
+            let entry = if let Some(platform_track_id) = find_override(&overrides, &track) {
+                TrackMatchEntry::overridden(&track, platform_track_id)
+            } else if let Some(external_id) = self
+                .music_service
+                .find_external_id(&track, PlaylistOrigin::YoutubeMusic)
+                .await?
+            {
+                TrackMatchEntry::known(&track, &external_id)
+            } else {
+                match self.find_youtube_song(&client, &track).await {
+                    Some(best) => best.report_entry(&track, best.item.get_raw().to_string()),
+                    None => TrackMatchEntry::not_found(&track),
                 }
+            };
+            progress.track_matched(&entry).await;
+            report.push(entry);
+        }
+
+        progress.finish_playlist().await;
+        Ok(report)
+    }
+
+    pub async fn import_playlist_in_yt(
+        &self,
+        user: &UserModel,
+        playlist_id: i32,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        let client = match self.get_ytmusic_client(user).await {
+            Ok(client) => client,
+            Err(e) => {
+                error!("Failed to get YouTube Music client: {:?}", e);
+                return Err(AppError::InternalServerError);
             }
+        };
+
+        let playlist = self.playlist_service.get_playlist(playlist_id).await?;
+
+        let video_ids: Vec<VideoID> = report
+            .iter()
+            .filter_map(|entry| entry.platform_track_id.clone())
+            .map(VideoID::from_raw)
+            .collect();
+
+        if video_ids.is_empty() {
+            info!("No tracks in playlist, returning early");
+            return Ok(PlaylistExport {
+                playlist_id: String::new(),
+                report,
+            });
         }
 
+        let added = video_ids.len();
         let query = CreatePlaylistQuery::new(&playlist.name, None, PrivacyStatus::Public);
         let yt_playlist_id = match client.create_playlist(query).await {
             Ok(playlist_id) => {
@@ -596,17 +705,17 @@ impl YoutubeMusicService {
                 return Err(AppError::InternalServerError);
             }
         };
-        let video_ids: Vec<VideoID> = youtube_tracks_id
-            .into_iter()
-            .map(|track| track.video_id)
-            .collect();
         match client
             .add_video_items_to_playlist(yt_playlist_id.clone(), video_ids)
             .await
         {
             Ok(_) => {
                 info!("Successfully added tracks to YouTube playlist: {:?}", yt_playlist_id);
-                Ok(yt_playlist_id.get_raw().to_string())
+                progress.tracks_done(added).await;
+                Ok(PlaylistExport {
+                    playlist_id: yt_playlist_id.get_raw().to_string(),
+                    report,
+                })
             },
             Err(e) => {
                 error!("Failed to add video items to playlist: {:?}", e);
@@ -615,6 +724,166 @@ impl YoutubeMusicService {
         }
     }
 
+    /// Brings a YouTube Music playlist created by a previous send in line with the matched
+    /// tracks. Items can't be moved through the API, so a playlist out of order is refilled.
+    pub async fn update_playlist_in_yt(
+        &self,
+        user: &UserModel,
+        platform_playlist_id: &str,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        let client = self.get_ytmusic_client(user).await.map_err(|e| {
+            error!("Failed to get YouTube Music client: {:?}", e);
+            AppError::InternalServerError
+        })?;
+        let yt_playlist_id = PlaylistID::from_raw(platform_playlist_id.to_string());
+
+        // Loading a deleted playlist fails like any other request, but it leaves the library
+        let library = client.get_library_playlists().await.map_err(|e| {
+            error!("Failed to get library playlists: {:?}", e);
+            AppError::InternalServerError
+        })?;
+        if !library_has_playlist(
+            library
+                .iter()
+                .map(|playlist| playlist.playlist_id.get_raw()),
+            platform_playlist_id,
+        ) {
+            return Err(AppError::NotFound(format!(
+                "YouTube Music playlist {} not found",
+                platform_playlist_id
+            )));
+        }
+
+        let songs: Vec<PlaylistSong> = client
+            .get_playlist_tracks(yt_playlist_id.clone())
+            .await
+            .map_err(|e| {
+                error!("Failed to load playlist {}: {}", platform_playlist_id, e);
+                AppError::InternalServerError
+            })?
+            .into_iter()
+            .filter_map(|item| match item {
+                PlaylistItem::Song(song) => Some(song),
+                _ => None,
+            })
+            .collect();
+
+        let current: Vec<String> = songs
+            .iter()
+            .map(|song| song.video_id.get_raw().to_string())
+            .collect();
+        let desired: Vec<String> = report
+            .iter()
+            .filter_map(|entry| entry.platform_track_id.clone())
+            .collect();
+        let diff = PlaylistDiff::between(&current, &desired);
+        let desired_count = desired.len();
+
+        let (to_remove, to_add) = if diff.reorder {
+            (current.clone(), desired)
+        } else {
+            (diff.to_remove, diff.to_add)
+        };
+        // Tracks already in the playlist are done
+        progress
+            .tracks_done(desired_count.saturating_sub(to_add.len()))
+            .await;
+
+        // Removing an item needs the id of its slot in the playlist
+        let mut slots = songs.into_iter();
+        let set_video_ids: Vec<SetVideoID> = to_remove
+            .iter()
+            .filter_map(|video_id| {
+                slots
+                    .by_ref()
+                    .find(|song| song.video_id.get_raw() == video_id.as_str())
+                    .map(|song| song.set_video_id)
+            })
+            .collect();
+        if !set_video_ids.is_empty() {
+            client
+                .remove_playlist_items(yt_playlist_id.clone(), set_video_ids)
+                .await
+                .map_err(|e| {
+                    error!("Failed to remove items from YouTube playlist: {:?}", e);
+                    AppError::InternalServerError
+                })?;
+        }
+
+        if !to_add.is_empty() {
+            let added = to_add.len();
+            let video_ids: Vec<VideoID> = to_add.into_iter().map(VideoID::from_raw).collect();
+            client
+                .add_video_items_to_playlist(yt_playlist_id.clone(), video_ids)
+                .await
+                .map_err(|e| {
+                    error!("Failed to add video items to playlist: {:?}", e);
+                    AppError::InternalServerError
+                })?;
+            progress.tracks_done(added).await;
+        }
+        info!("Updated YouTube playlist {}", platform_playlist_id);
+
+        Ok(PlaylistExport {
+            playlist_id: platform_playlist_id.to_string(),
+            report,
+        })
+    }
+
+    /// Searches YouTube Music for a track of our database and returns the best matching video id
+    pub async fn find_youtube_song(
+        &self,
+        client: &YtMusic<OAuthToken>,
+        track: &MusicModel,
+    ) -> Option<ScoredMatch<VideoID<'static>>> {
+        match client
+            .search_songs(format!("{} {}", track.title, track.artist))
+            .await
+        {
+            Ok(youtube_tracks) => {
+                let candidates = youtube_tracks
+                    .into_iter()
+                    .map(|youtube_track| TrackCandidate {
+                        title: youtube_track.title,
+                        artists: vec![youtube_track.artist],
+                        album: None,
+                        duration_ms: parse_duration_ms(&youtube_track.duration)
+                            .and_then(|duration| u32::try_from(duration).ok()),
+                        item: youtube_track.video_id,
+                    });
+
+                match self
+                    .matcher
+                    .best_match(&TrackQuery::from(track), candidates)
+                {
+                    Some(best) => {
+                        info!(
+                            "Found track on YouTube Music: {} - {} (id: {}, confidence: {:.2})",
+                            track.artist,
+                            track.title,
+                            best.item.get_raw(),
+                            best.score
+                        );
+                        Some(best)
+                    }
+                    None => {
+                        info!(
+                            "Track not found on YouTube Music: {} - {}",
+                            track.artist, track.title
+                        );
+                        None
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Failed to search for track {}: {:?}", track.title, e);
+                None
+            }
+        }
+    }
+
     pub async fn disconnect(&self, user: &UserModel) -> Result<(), AppError> {
         info!("Disconnecting YouTube Music for user {}", user.id);
 
@@ -639,3 +908,128 @@ impl YoutubeMusicService {
         Ok(())
     }
 }
+
+#[async_trait]
+impl MusicProvider for YoutubeMusicService {
+    fn origin(&self) -> PlaylistOrigin {
+        PlaylistOrigin::YoutubeMusic
+    }
+
+    async fn connect(&self, user: &UserModel, request: AddTokenRequest) -> Result<(), AppError> {
+        self.auth_callback(user, request).await
+    }
+
+    async fn list_playlists(&self, user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError> {
+        YoutubeMusicService::list_playlists(self, user).await
+    }
+
+    async fn import_playlists(
+        &self,
+        user: &UserModel,
+        selection: &ImportSelection,
+        progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        self.get_user_playlists(user, selection, progress).await
+    }
+
+    async fn search_track(
+        &self,
+        user: &UserModel,
+        music: &MusicModel,
+    ) -> Result<TrackMatchEntry, AppError> {
+        let client = self.get_ytmusic_client(user).await?;
+        Ok(match self.find_youtube_song(&client, music).await {
+            Some(best) => best.report_entry(music, best.item.get_raw().to_string()),
+            None => TrackMatchEntry::not_found(music),
+        })
+    }
+
+    async fn preview_playlist(
+        &self,
+        user: &UserModel,
+        playlist_id: i32,
+        progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError> {
+        self.match_playlist_tracks(user, playlist_id, progress)
+            .await
+    }
+
+    async fn create_playlist_from_matches(
+        &self,
+        user: &UserModel,
+        playlist_id: i32,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        self.import_playlist_in_yt(user, playlist_id, report, progress)
+            .await
+    }
+
+    async fn update_playlist_from_matches(
+        &self,
+        user: &UserModel,
+        platform_playlist_id: &str,
+        report: Vec<TrackMatchEntry>,
+        progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        self.update_playlist_in_yt(user, platform_playlist_id, report, progress)
+            .await
+    }
+
+    async fn connected_users(&self) -> Result<Vec<i32>, AppError> {
+        Ok(self
+            .youtube_token_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|token| token.user_id)
+            .collect())
+    }
+
+    async fn disconnect(&self, user: &UserModel) -> Result<(), AppError> {
+        YoutubeMusicService::disconnect(self, user).await
+    }
+}
+
+/// YouTube Music shows durations as `3:45` or `1:02:03`
+fn parse_duration_ms(duration: &str) -> Option<i32> {
+    let mut seconds: i32 = 0;
+    for part in duration.trim().split(':') {
+        seconds = seconds.checked_mul(60)?.checked_add(part.parse().ok()?)?;
+    }
+    seconds.checked_mul(1000)
+}
+
+/// Library ids carry a `VL` prefix the ids returned on creation do not have
+fn library_has_playlist<'a>(
+    library_ids: impl IntoIterator<Item = &'a str>,
+    platform_playlist_id: &str,
+) -> bool {
+    let wanted = platform_playlist_id
+        .strip_prefix("VL")
+        .unwrap_or(platform_playlist_id);
+    library_ids
+        .into_iter()
+        .any(|id| id.strip_prefix("VL").unwrap_or(id) == wanted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_ms() {
+        assert_eq!(parse_duration_ms("3:45"), Some(225_000));
+        assert_eq!(parse_duration_ms("1:02:03"), Some(3_723_000));
+        assert_eq!(parse_duration_ms(""), None);
+        assert_eq!(parse_duration_ms("live"), None);
+    }
+
+    #[test]
+    fn test_deleted_playlist_is_not_in_library() {
+        let library = ["VLPLkept", "LM"];
+        assert!(library_has_playlist(library, "PLkept"));
+        assert!(library_has_playlist(library, "VLPLkept"));
+        assert!(!library_has_playlist(library, "PLdeleted"));
+    }
+}
@@ -1,3 +1,11 @@
+// Each test file includes this module on its own and uses only part of the fixtures
+#![allow(dead_code)]
+
+use swaptun_models::{MusicModel, PlaylistModel, PlaylistOrigin};
+use swaptun_services::{
+    CreateMusicRequest, CreatePlaylistRequest, MusicService, PlaylistService, TestDatabase,
+};
+
 #[cfg(test)]
 #[ctor::ctor]
 fn init() {
@@ -7,3 +15,37 @@ fn init() {
         .is_test(true)
         .try_init();
 }
+
+/// Playlist of the test user, its origin id derived from the name
+pub async fn create_playlist(
+    test_db: &TestDatabase,
+    name: &str,
+    origin: PlaylistOrigin,
+) -> PlaylistModel {
+    PlaylistService::new(test_db.get_db())
+        .create(
+            CreatePlaylistRequest {
+                name: name.to_string(),
+                description: None,
+                origin,
+                origin_id: name.to_lowercase().replace(' ', "_"),
+                image_url: None,
+            },
+            test_db.get_user().id,
+        )
+        .await
+        .unwrap()
+}
+
+pub async fn create_music(test_db: &TestDatabase, title: &str) -> MusicModel {
+    MusicService::new(test_db.get_db())
+        .create(CreateMusicRequest {
+            title: title.to_string(),
+            artist: "Artist".to_string(),
+            album: "Album".to_string(),
+            release_date: "2025-01-01".parse().unwrap(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+}
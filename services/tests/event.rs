@@ -0,0 +1,61 @@
+use swaptun_models::PlaylistOrigin;
+use swaptun_services::{EventHub, UserEvent};
+
+fn imported(playlist_id: i32) -> UserEvent {
+    UserEvent::PlaylistImported {
+        playlist_id,
+        origin: PlaylistOrigin::Spotify,
+    }
+}
+
+#[tokio::test]
+async fn test_events_reach_every_stream_of_the_user() {
+    let hub = EventHub::new();
+    let mut first = hub.subscribe(1);
+    let mut second = hub.subscribe(1);
+
+    hub.publish(1, imported(10));
+
+    assert_eq!(first.recv().await.unwrap(), imported(10));
+    assert_eq!(second.recv().await.unwrap(), imported(10));
+}
+
+#[tokio::test]
+async fn test_events_are_not_sent_to_other_users() {
+    let hub = EventHub::new();
+    let mut receiver = hub.subscribe(1);
+
+    hub.publish(2, imported(10));
+    hub.publish(1, imported(11));
+
+    assert_eq!(receiver.recv().await.unwrap(), imported(11));
+    assert!(receiver.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_events_without_stream_are_dropped() {
+    let hub = EventHub::new();
+    hub.publish(1, imported(10));
+
+    let receiver = hub.subscribe(1);
+    drop(receiver);
+    hub.publish(1, imported(11));
+
+    // A new stream only gets the events published after it opened
+    let mut receiver = hub.subscribe(1);
+    hub.publish(1, imported(12));
+    assert_eq!(receiver.recv().await.unwrap(), imported(12));
+}
+
+#[tokio::test]
+async fn test_channel_is_removed_with_the_last_stream() {
+    let hub = EventHub::new();
+    let first = hub.subscribe(1);
+    let second = hub.subscribe(1);
+
+    hub.unsubscribe(1, first);
+    assert!(hub.has_streams(1));
+
+    hub.unsubscribe(1, second);
+    assert!(!hub.has_streams(1));
+}
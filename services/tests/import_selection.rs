@@ -0,0 +1,236 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use swaptun_models::{JobStatus, MusicModel, PlaylistOrigin, UserModel};
+use swaptun_services::error::AppError;
+use swaptun_services::{
+    AddTokenRequest, ImportSelection, ImportSelectionService, JobPayload, JobProgress, JobService,
+    JobWorker, MusicProvider, PlaylistExport, ProviderRegistry, RemotePlaylist, TestDatabase,
+    TrackMatchEntry, UpdateImportSelectionRequest,
+};
+
+/// Provider with a followed editorial playlist next to one of the user's own
+#[derive(Default)]
+struct LibraryProvider {
+    imported: Mutex<Vec<String>>,
+    panics: bool,
+}
+
+fn remote_playlist(id: &str, owned: bool) -> RemotePlaylist {
+    RemotePlaylist {
+        id: id.to_string(),
+        name: id.to_string(),
+        track_count: Some(10),
+        owned: Some(owned),
+        image_url: None,
+        selected: false,
+    }
+}
+
+#[async_trait]
+impl MusicProvider for LibraryProvider {
+    fn origin(&self) -> PlaylistOrigin {
+        PlaylistOrigin::Spotify
+    }
+
+    async fn connect(&self, _user: &UserModel, _request: AddTokenRequest) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn connected_users(&self) -> Result<Vec<i32>, AppError> {
+        Ok(vec![])
+    }
+
+    async fn list_playlists(&self, _user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError> {
+        Ok(vec![
+            remote_playlist("mine", true),
+            remote_playlist("editorial", false),
+        ])
+    }
+
+    async fn import_playlists(
+        &self,
+        _user: &UserModel,
+        selection: &ImportSelection,
+        _progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        if self.panics {
+            panic!("Broken library");
+        }
+        let mut imported = self.imported.lock().unwrap();
+        for id in ["mine", "editorial"] {
+            if selection.includes(id) {
+                imported.push(id.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    async fn search_track(
+        &self,
+        _user: &UserModel,
+        music: &MusicModel,
+    ) -> Result<TrackMatchEntry, AppError> {
+        Ok(TrackMatchEntry::not_found(music))
+    }
+
+    async fn preview_playlist(
+        &self,
+        _user: &UserModel,
+        _playlist_id: i32,
+        _progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError> {
+        Ok(vec![])
+    }
+
+    async fn create_playlist_from_matches(
+        &self,
+        _user: &UserModel,
+        _playlist_id: i32,
+        report: Vec<TrackMatchEntry>,
+        _progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        Ok(PlaylistExport {
+            playlist_id: String::new(),
+            report,
+        })
+    }
+
+    async fn update_playlist_from_matches(
+        &self,
+        _user: &UserModel,
+        platform_playlist_id: &str,
+        report: Vec<TrackMatchEntry>,
+        _progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        Ok(PlaylistExport {
+            playlist_id: platform_playlist_id.to_string(),
+            report,
+        })
+    }
+
+    async fn disconnect(&self, _user: &UserModel) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+fn selected_ids(playlists: &[RemotePlaylist]) -> Vec<&str> {
+    playlists
+        .iter()
+        .filter(|playlist| playlist.selected)
+        .map(|playlist| playlist.id.as_str())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_remote_playlists_show_the_selection() {
+    let test_db = TestDatabase::new().await;
+    let user = test_db.get_user();
+    let service = ImportSelectionService::new(test_db.get_db());
+    let provider = LibraryProvider::default();
+
+    // Everything is imported until the user picks playlists
+    let response = service
+        .get_remote_playlists(&provider, &user)
+        .await
+        .unwrap();
+    assert!(!response.has_selection);
+    assert_eq!(selected_ids(&response.playlists), vec!["mine", "editorial"]);
+
+    let request = UpdateImportSelectionRequest {
+        playlist_ids: vec!["mine".to_string(), "mine".to_string()],
+    };
+    service
+        .save(user.id, PlaylistOrigin::Spotify, request)
+        .await
+        .unwrap();
+    let response = service
+        .get_remote_playlists(&provider, &user)
+        .await
+        .unwrap();
+    assert!(response.has_selection);
+    assert_eq!(selected_ids(&response.playlists), vec!["mine"]);
+
+    // The selection is kept per platform
+    let deezer = service.get(user.id, PlaylistOrigin::Deezer).await.unwrap();
+    assert!(deezer.is_all());
+
+    service
+        .clear(user.id, PlaylistOrigin::Spotify)
+        .await
+        .unwrap();
+    let response = service
+        .get_remote_playlists(&provider, &user)
+        .await
+        .unwrap();
+    assert!(!response.has_selection);
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_queued_imports_only_import_the_selection() {
+    let test_db = TestDatabase::new().await;
+    let user = test_db.get_user();
+    let provider = Arc::new(LibraryProvider::default());
+    let mut registry = ProviderRegistry::new(test_db.get_db());
+    registry.register(provider.clone());
+    let worker = JobWorker::with_registry(test_db.get_db(), registry);
+
+    ImportSelectionService::new(test_db.get_db())
+        .save(
+            user.id,
+            PlaylistOrigin::Spotify,
+            UpdateImportSelectionRequest {
+                playlist_ids: vec!["mine".to_string()],
+            },
+        )
+        .await
+        .unwrap();
+    JobService::new(test_db.get_db())
+        .enqueue(
+            user.id,
+            JobPayload::ImportPlaylists {
+                origin: PlaylistOrigin::Spotify,
+            },
+        )
+        .await
+        .unwrap();
+
+    assert!(worker.run_next().await.unwrap());
+    assert_eq!(*provider.imported.lock().unwrap(), vec!["mine"]);
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_panicking_import_fails_its_job() {
+    let test_db = TestDatabase::new().await;
+    let user = test_db.get_user();
+    let provider = Arc::new(LibraryProvider {
+        panics: true,
+        ..Default::default()
+    });
+    let mut registry = ProviderRegistry::new(test_db.get_db());
+    registry.register(provider);
+    let worker = JobWorker::with_registry(test_db.get_db(), registry);
+    let job_service = JobService::new(test_db.get_db());
+
+    let job = job_service
+        .enqueue(
+            user.id,
+            JobPayload::ImportPlaylists {
+                origin: PlaylistOrigin::Spotify,
+            },
+        )
+        .await
+        .unwrap();
+
+    // The worker survives and the job is released instead of staying running
+    assert!(worker.run_next().await.unwrap());
+    let job = job_service.get_job(job.id).await.unwrap();
+    assert_ne!(job.status, JobStatus::Running);
+    assert!(job.error.is_some());
+
+    test_db.drop().await;
+}
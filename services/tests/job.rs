@@ -0,0 +1,266 @@
+use swaptun_models::{JobKind, JobStatus, PlaylistOrigin};
+use swaptun_services::error::AppError;
+use swaptun_services::{
+    GetJobsParams, JobPayload, JobPlaylistError, JobProgress, JobService, TestDatabase,
+};
+
+fn import_payload() -> JobPayload {
+    JobPayload::ImportPlaylists {
+        origin: PlaylistOrigin::Spotify,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_enqueued_job_is_claimed_once() {
+    let test_db = TestDatabase::new().await;
+    let job_service = JobService::new(test_db.get_db());
+    let user = test_db.get_user();
+
+    let job = job_service
+        .enqueue(user.id, import_payload())
+        .await
+        .unwrap();
+    assert_eq!(job.status, JobStatus::Queued);
+    assert_eq!(job.kind, JobKind::ImportPlaylists);
+    assert_eq!(job.attempts, 0);
+
+    let (first, second) = tokio::join!(job_service.claim_next(), job_service.claim_next());
+    let claimed: Vec<_> = [first.unwrap(), second.unwrap()]
+        .into_iter()
+        .flatten()
+        .collect();
+    assert_eq!(claimed.len(), 1);
+    assert_eq!(claimed[0].id, job.id);
+    assert_eq!(claimed[0].status, JobStatus::Running);
+    assert_eq!(claimed[0].attempts, 1);
+    assert!(claimed[0].started_on.is_some());
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_failed_job_is_retried_later() {
+    let test_db = TestDatabase::new().await;
+    let job_service = JobService::new(test_db.get_db());
+    let user = test_db.get_user();
+
+    job_service
+        .enqueue(user.id, import_payload())
+        .await
+        .unwrap();
+    let job = job_service.claim_next().await.unwrap().unwrap();
+
+    let job = job_service
+        .fail(job, &AppError::InternalServerError)
+        .await
+        .unwrap();
+    assert_eq!(job.status, JobStatus::Queued);
+    assert_eq!(job.error.as_deref(), Some("Internal server error"));
+    assert!(job.run_at > chrono::Utc::now());
+
+    // Not due before the backoff delay
+    assert!(job_service.claim_next().await.unwrap().is_none());
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_failed_export_is_not_retried() {
+    let test_db = TestDatabase::new().await;
+    let job_service = JobService::new(test_db.get_db());
+    let user = test_db.get_user();
+
+    job_service
+        .enqueue(
+            user.id,
+            JobPayload::ExportPlaylist {
+                playlist_id: 1,
+                destination: PlaylistOrigin::Deezer,
+                matches: None,
+                create_new: false,
+            },
+        )
+        .await
+        .unwrap();
+    let job = job_service.claim_next().await.unwrap().unwrap();
+    assert_eq!(job.max_attempts, 1);
+
+    // The playlist may already exist on the destination, another attempt would duplicate it
+    let job = job_service
+        .fail(job, &AppError::InternalServerError)
+        .await
+        .unwrap();
+    assert_eq!(job.status, JobStatus::Failed);
+    assert!(job_service.claim_next().await.unwrap().is_none());
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_job_fails_without_retry_on_permanent_error() {
+    let test_db = TestDatabase::new().await;
+    let job_service = JobService::new(test_db.get_db());
+    let user = test_db.get_user();
+
+    job_service
+        .enqueue(user.id, import_payload())
+        .await
+        .unwrap();
+    let job = job_service.claim_next().await.unwrap().unwrap();
+
+    let job = job_service
+        .fail(job, &AppError::Unauthorized("No Spotify token".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(job.status, JobStatus::Failed);
+    assert_eq!(job.error.as_deref(), Some("Unauthorized: No Spotify token"));
+    assert!(job.finished_on.is_some());
+
+    let job = job_service.get_job(job.id).await.unwrap();
+    assert_eq!(job.status, JobStatus::Failed);
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_succeeded_job_keeps_its_result() {
+    let test_db = TestDatabase::new().await;
+    let job_service = JobService::new(test_db.get_db());
+    let user = test_db.get_user();
+
+    job_service
+        .enqueue(user.id, import_payload())
+        .await
+        .unwrap();
+    let job = job_service.claim_next().await.unwrap().unwrap();
+
+    let job = job_service
+        .succeed(job, Some(serde_json::json!({ "playlist_id": "abc" })))
+        .await
+        .unwrap();
+    assert_eq!(job.status, JobStatus::Succeeded);
+    assert_eq!(job.result.unwrap()["playlist_id"], "abc");
+    assert!(job_service.claim_next().await.unwrap().is_none());
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_progress_is_saved_on_the_job() {
+    let test_db = TestDatabase::new().await;
+    let job_service = JobService::new(test_db.get_db());
+    let user = test_db.get_user();
+
+    job_service
+        .enqueue(user.id, import_payload())
+        .await
+        .unwrap();
+    let job = job_service.claim_next().await.unwrap().unwrap();
+
+    let progress = job_service.progress(&job);
+    progress.start_playlist("Chill", 3).await;
+    progress.track_done().await;
+    progress.track_done().await;
+    progress
+        .playlist_failed("Broken", &AppError::NotFound("Playlist gone".to_string()))
+        .await;
+
+    let job = job_service.get_job(job.id).await.unwrap();
+    assert_eq!(job.total_tracks, 3);
+    assert_eq!(job.processed_tracks, 2);
+    assert_eq!(job.current_playlist.as_deref(), Some("Chill"));
+    let errors: Vec<JobPlaylistError> = serde_json::from_value(job.playlist_errors).unwrap();
+    assert_eq!(
+        errors,
+        vec![JobPlaylistError {
+            playlist: "Broken".to_string(),
+            error: "Not found: Playlist gone".to_string(),
+        }]
+    );
+
+    // Reports outside of a job are ignored
+    JobProgress::none().start_playlist("Preview", 10).await;
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_processed_tracks_are_saved_in_batches() {
+    let test_db = TestDatabase::new().await;
+    let job_service = JobService::new(test_db.get_db());
+    let user = test_db.get_user();
+
+    job_service
+        .enqueue(user.id, import_payload())
+        .await
+        .unwrap();
+    let job = job_service.claim_next().await.unwrap().unwrap();
+
+    let progress = job_service.progress(&job);
+    progress.start_playlist("Chill", 100).await;
+    progress.track_done().await;
+    progress.track_done().await;
+
+    // A couple of tracks right after the start of the playlist are not written yet
+    let saved = job_service.get_job(job.id).await.unwrap();
+    assert_eq!(saved.total_tracks, 100);
+    assert_eq!(saved.processed_tracks, 0);
+
+    progress.tracks_done(30).await;
+    let saved = job_service.get_job(job.id).await.unwrap();
+    assert_eq!(saved.processed_tracks, 32);
+
+    progress.track_done().await;
+    progress.finish_playlist().await;
+    let saved = job_service.get_job(job.id).await.unwrap();
+    assert_eq!(saved.processed_tracks, 33);
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_user_jobs_are_listed_most_recent_first() {
+    let test_db = TestDatabase::new().await;
+    let job_service = JobService::new(test_db.get_db());
+    let user = test_db.get_user();
+
+    let import = job_service
+        .enqueue(user.id, import_payload())
+        .await
+        .unwrap();
+    let export = job_service
+        .enqueue(
+            user.id,
+            JobPayload::ExportPlaylist {
+                playlist_id: 1,
+                destination: PlaylistOrigin::Deezer,
+                matches: None,
+                create_new: false,
+            },
+        )
+        .await
+        .unwrap();
+
+    let jobs = job_service
+        .get_user_jobs(user.id, GetJobsParams::default())
+        .await
+        .unwrap();
+    let ids: Vec<i32> = jobs.iter().map(|job| job.id).collect();
+    assert_eq!(ids, vec![export.id, import.id]);
+    assert_eq!(jobs[0].kind, JobKind::ExportPlaylist);
+
+    let page = job_service
+        .get_user_jobs(
+            user.id,
+            GetJobsParams {
+                limit: Some(1),
+                offset: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+    let ids: Vec<i32> = page.iter().map(|job| job.id).collect();
+    assert_eq!(ids, vec![import.id]);
+
+    test_db.drop().await;
+}
@@ -1,11 +1,18 @@
 mod apple;
 mod deezer;
+mod event;
 mod forgot_password_test;
+mod import_selection;
+mod job;
 mod mail_service;
 mod music;
 mod musicbrainz;
+mod playlist_export;
+mod playlist_mirror;
+mod playlist_snapshot;
 mod playlist_test;
 mod spotify;
+mod sync_scheduler;
 mod user_service;
 mod user_exclude_self_tests;
 mod yt_music;
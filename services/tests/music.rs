@@ -1,5 +1,8 @@
 use swaptun_services::TestDatabase;
-use swaptun_services::{music::dto::CreateMusicRequest, MusicService};
+use swaptun_services::{
+    music::dto::{CreateMusicRequest, UpdateMusicRequest},
+    ArtistCredit, ArtistService, MusicService,
+};
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_create_music_success() {
     let test_db = TestDatabase::new().await;
@@ -11,19 +14,13 @@ async fn test_create_music_success() {
         release_date: "2025-01-01".parse().unwrap(),
         genre: Some("Pop".to_string()),
         description: Some("New Description".to_string()),
+        ..Default::default()
     };
     let result = music_service.create(crate_music_request).await;
     println!("Result: {:?}", result);
     assert!(result.is_ok());
 
-    let music = music_service
-        .find_by_id(
-            "New Music".to_string(),
-            "New Artist".to_string(),
-            "New Album".to_string(),
-        )
-        .await
-        .unwrap();
+    let music = music_service.find_by_id(result.unwrap().id).await.unwrap();
     println!("Music: {:?}", music);
     assert!(music.is_some());
     assert_eq!(music.unwrap().title, "New Music".to_string());
@@ -42,17 +39,12 @@ async fn test_delete_music_success() {
         release_date: "2025-01-01".parse().unwrap(),
         genre: Some("Pop".to_string()),
         description: Some("New Description".to_string()),
+        ..Default::default()
     };
     let created_music = music_service.create(crate_music_request).await.unwrap();
 
     // Supprime la musique
-    let result = music_service
-        .delete(
-            created_music.title.clone(),
-            created_music.artist.clone(),
-            created_music.album.clone(),
-        )
-        .await;
+    let result = music_service.delete(created_music.id).await;
     println!("Result: {:?}", result);
     assert!(result.is_ok());
     test_db.drop().await;
@@ -70,6 +62,7 @@ async fn test_create_music_duplicate() {
         release_date: "2025-01-01".parse().unwrap(),
         genre: Some("Pop".to_string()),
         description: Some("New Description".to_string()),
+        ..Default::default()
     };
 
     // Crée une première musique
@@ -84,6 +77,7 @@ async fn test_create_music_duplicate() {
         release_date: "2025-01-01".parse().unwrap(),
         genre: Some("Pop".to_string()),
         description: Some("New Description".to_string()),
+        ..Default::default()
     };
 
     // Essaye de créer la même musique
@@ -96,3 +90,149 @@ async fn test_create_music_duplicate() {
     assert_eq!(all_music.len(), 1);
     test_db.drop().await;
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_create_music_fills_missing_isrc() {
+    let test_db = TestDatabase::new().await;
+    let music_service = MusicService::new(test_db.get_db());
+
+    let crate_music_request = CreateMusicRequest {
+        title: "New Music".to_string(),
+        artist: "New Artist".to_string(),
+        album: "New Album".to_string(),
+        release_date: "2025-01-01".parse().unwrap(),
+        genre: Some("Pop".to_string()),
+        ..Default::default()
+    };
+    music_service.create(crate_music_request).await.unwrap();
+
+    let crate_music_request2 = CreateMusicRequest {
+        title: "New Music".to_string(),
+        artist: "New Artist".to_string(),
+        album: "New Album".to_string(),
+        release_date: "2025-01-01".parse().unwrap(),
+        genre: Some("Pop".to_string()),
+        isrc: Some("GBUM71029604".to_string()),
+        ..Default::default()
+    };
+    let music = music_service.create(crate_music_request2).await.unwrap();
+
+    assert_eq!(music.isrc, Some("GBUM71029604".to_string()));
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_create_music_keeps_existing_metadata() {
+    let test_db = TestDatabase::new().await;
+    let music_service = MusicService::new(test_db.get_db());
+
+    let crate_music_request = CreateMusicRequest {
+        title: "New Music".to_string(),
+        artist: "New Artist".to_string(),
+        album: "New Album".to_string(),
+        release_date: "2025-01-01".parse().unwrap(),
+        duration_ms: Some(218_000),
+        ..Default::default()
+    };
+    music_service.create(crate_music_request).await.unwrap();
+
+    let crate_music_request2 = CreateMusicRequest {
+        title: "New Music".to_string(),
+        artist: "New Artist".to_string(),
+        album: "New Album".to_string(),
+        release_date: "2025-01-01".parse().unwrap(),
+        duration_ms: Some(217_000),
+        explicit: Some(true),
+        artwork_url: Some("https://example.com/cover.jpg".to_string()),
+        disc_number: Some(1),
+        track_number: Some(4),
+        ..Default::default()
+    };
+    let music = music_service.create(crate_music_request2).await.unwrap();
+
+    assert_eq!(music.duration_ms, Some(218_000));
+    assert_eq!(music.explicit, Some(true));
+    assert_eq!(music.track_number, Some(4));
+    assert!(music.preview_url.is_none());
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_update_music_title_keeps_id() {
+    let test_db = TestDatabase::new().await;
+    let music_service = MusicService::new(test_db.get_db());
+
+    let crate_music_request = CreateMusicRequest {
+        title: "New Msuic".to_string(),
+        artist: "New Artist".to_string(),
+        album: "New Album".to_string(),
+        release_date: "2025-01-01".parse().unwrap(),
+        ..Default::default()
+    };
+    let created_music = music_service.create(crate_music_request).await.unwrap();
+
+    let update_music_request = UpdateMusicRequest {
+        id: created_music.id,
+        name: None,
+        description: None,
+        title: Some("New Music".to_string()),
+        artist: None,
+        album: None,
+        release_date: None,
+        genre: None,
+    };
+    let updated_music = music_service.update(update_music_request).await.unwrap();
+
+    assert_eq!(updated_music.id, created_music.id);
+    assert_eq!(updated_music.title, "New Music".to_string());
+    assert_eq!(updated_music.artist, "New Artist".to_string());
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_create_music_records_every_artist() {
+    let test_db = TestDatabase::new().await;
+    let music_service = MusicService::new(test_db.get_db());
+    let artist_service = ArtistService::new(test_db.get_db());
+
+    let crate_music_request = CreateMusicRequest {
+        title: "Tout va bien".to_string(),
+        artist: "SDM".to_string(),
+        album: "Liens du 100".to_string(),
+        release_date: "2024-03-22".parse().unwrap(),
+        artists: ArtistCredit::from_names(["SDM", "Werenoi"]),
+        ..Default::default()
+    };
+    let music = music_service.create(crate_music_request).await.unwrap();
+
+    let artists = artist_service.find_by_music(&music).await.unwrap();
+    let names: Vec<&str> = artists.iter().map(|artist| artist.name.as_str()).collect();
+    assert_eq!(names, vec!["SDM", "Werenoi"]);
+    assert!(music.album_id.is_some());
+
+    let sdm = artist_service.get_artist(artists[0].id).await.unwrap();
+    assert_eq!(sdm.albums.len(), 1);
+    assert_eq!(sdm.musics.len(), 1);
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_concurrent_imports_share_artist_and_album() {
+    let test_db = TestDatabase::new().await;
+    let artist_service = ArtistService::new(test_db.get_db());
+
+    // Both lookups miss, the second insert must not fail on the unique name
+    let (first, second) = tokio::join!(
+        artist_service.find_or_create("Daft Punk"),
+        artist_service.find_or_create("Daft Punk")
+    );
+    let (first, second) = (first.unwrap(), second.unwrap());
+    assert_eq!(first.id, second.id);
+
+    let (first_album, second_album) = tokio::join!(
+        artist_service.find_or_create_album("Discovery", Some(first.id)),
+        artist_service.find_or_create_album("Discovery", Some(first.id))
+    );
+    assert_eq!(first_album.unwrap().id, second_album.unwrap().id);
+    test_db.drop().await;
+}
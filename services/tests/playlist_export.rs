@@ -0,0 +1,338 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use swaptun_models::{MusicModel, PlaylistOrigin, UserModel};
+use swaptun_services::error::AppError;
+use swaptun_services::{
+    AddTokenRequest, ImportSelection, JobProgress, MusicProvider, MusicService, PlaylistExport,
+    PlaylistExportService, RemotePlaylist, TestDatabase, TrackMatchEntry,
+};
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{create_music, create_playlist};
+
+/// Provider keeping track of the playlists it was asked to create or update
+#[derive(Default)]
+struct FakeProvider {
+    created: Mutex<Vec<i32>>,
+    updated: Mutex<Vec<String>>,
+    deleted_on_platform: bool,
+}
+
+#[async_trait]
+impl MusicProvider for FakeProvider {
+    fn origin(&self) -> PlaylistOrigin {
+        PlaylistOrigin::Deezer
+    }
+
+    async fn connect(&self, _user: &UserModel, _request: AddTokenRequest) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn connected_users(&self) -> Result<Vec<i32>, AppError> {
+        Ok(vec![])
+    }
+
+    async fn list_playlists(&self, _user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError> {
+        Ok(vec![])
+    }
+
+    async fn import_playlists(
+        &self,
+        _user: &UserModel,
+        _selection: &ImportSelection,
+        _progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn search_track(
+        &self,
+        _user: &UserModel,
+        music: &MusicModel,
+    ) -> Result<TrackMatchEntry, AppError> {
+        Ok(TrackMatchEntry::not_found(music))
+    }
+
+    async fn preview_playlist(
+        &self,
+        _user: &UserModel,
+        _playlist_id: i32,
+        _progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError> {
+        Ok(vec![])
+    }
+
+    async fn create_playlist_from_matches(
+        &self,
+        _user: &UserModel,
+        playlist_id: i32,
+        report: Vec<TrackMatchEntry>,
+        _progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        let mut created = self.created.lock().unwrap();
+        created.push(playlist_id);
+        Ok(PlaylistExport {
+            playlist_id: format!("remote_{}", created.len()),
+            report,
+        })
+    }
+
+    async fn update_playlist_from_matches(
+        &self,
+        _user: &UserModel,
+        platform_playlist_id: &str,
+        report: Vec<TrackMatchEntry>,
+        _progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        if self.deleted_on_platform {
+            return Err(AppError::NotFound("Playlist deleted".to_string()));
+        }
+        self.updated
+            .lock()
+            .unwrap()
+            .push(platform_playlist_id.to_string());
+        Ok(PlaylistExport {
+            playlist_id: platform_playlist_id.to_string(),
+            report,
+        })
+    }
+
+    async fn disconnect(&self, _user: &UserModel) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_second_send_updates_the_first_playlist() {
+    let test_db = TestDatabase::new().await;
+    let export_service = PlaylistExportService::new(test_db.get_db());
+    let user = test_db.get_user();
+    let playlist_id = create_playlist(&test_db, "Road trip", PlaylistOrigin::Spotify)
+        .await
+        .id;
+    let provider = FakeProvider::default();
+    let progress = JobProgress::none();
+
+    let first = export_service
+        .export(
+            &provider,
+            &user,
+            playlist_id,
+            vec![],
+            false,
+            false,
+            &progress,
+        )
+        .await
+        .unwrap();
+    assert_eq!(first.playlist_id, "remote_1");
+    assert!(!first.updated);
+
+    let second = export_service
+        .export(
+            &provider,
+            &user,
+            playlist_id,
+            vec![],
+            false,
+            false,
+            &progress,
+        )
+        .await
+        .unwrap();
+    assert_eq!(second.playlist_id, "remote_1");
+    assert!(second.updated);
+    assert_eq!(*provider.created.lock().unwrap(), vec![playlist_id]);
+    assert_eq!(*provider.updated.lock().unwrap(), vec!["remote_1"]);
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_create_new_replaces_the_recorded_playlist() {
+    let test_db = TestDatabase::new().await;
+    let export_service = PlaylistExportService::new(test_db.get_db());
+    let user = test_db.get_user();
+    let playlist_id = create_playlist(&test_db, "Road trip", PlaylistOrigin::Spotify)
+        .await
+        .id;
+    let provider = FakeProvider::default();
+    let progress = JobProgress::none();
+
+    export_service
+        .export(
+            &provider,
+            &user,
+            playlist_id,
+            vec![],
+            false,
+            false,
+            &progress,
+        )
+        .await
+        .unwrap();
+    let response = export_service
+        .export(
+            &provider,
+            &user,
+            playlist_id,
+            vec![],
+            true,
+            false,
+            &progress,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.playlist_id, "remote_2");
+    assert!(!response.updated);
+
+    let export = export_service
+        .get_export(playlist_id, user.id, PlaylistOrigin::Deezer)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(export.platform_playlist_id, "remote_2");
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_playlist_deleted_on_the_platform_is_created_again() {
+    let test_db = TestDatabase::new().await;
+    let export_service = PlaylistExportService::new(test_db.get_db());
+    let user = test_db.get_user();
+    let playlist_id = create_playlist(&test_db, "Road trip", PlaylistOrigin::Spotify)
+        .await
+        .id;
+    let provider = FakeProvider {
+        deleted_on_platform: true,
+        ..Default::default()
+    };
+    let progress = JobProgress::none();
+
+    export_service
+        .export(
+            &provider,
+            &user,
+            playlist_id,
+            vec![],
+            false,
+            false,
+            &progress,
+        )
+        .await
+        .unwrap();
+    let response = export_service
+        .export(
+            &provider,
+            &user,
+            playlist_id,
+            vec![],
+            false,
+            false,
+            &progress,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.playlist_id, "remote_2");
+    assert!(!response.updated);
+    assert!(provider.updated.lock().unwrap().is_empty());
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_only_matcher_reports_are_cached() {
+    let test_db = TestDatabase::new().await;
+    let export_service = PlaylistExportService::new(test_db.get_db());
+    let music_service = MusicService::new(test_db.get_db());
+    let user = test_db.get_user();
+    let playlist_id = create_playlist(&test_db, "Road trip", PlaylistOrigin::Spotify)
+        .await
+        .id;
+    let provider = FakeProvider::default();
+    let progress = JobProgress::none();
+    let music = create_music(&test_db, "Intro").await;
+    let report = vec![TrackMatchEntry::known(&music, "dz_intro")];
+
+    export_service
+        .export(
+            &provider,
+            &user,
+            playlist_id,
+            report.clone(),
+            false,
+            false,
+            &progress,
+        )
+        .await
+        .unwrap();
+    let cached = music_service
+        .find_external_id(&music, PlaylistOrigin::Deezer)
+        .await
+        .unwrap();
+    assert_eq!(cached, None);
+
+    export_service
+        .export(
+            &provider,
+            &user,
+            playlist_id,
+            report,
+            false,
+            true,
+            &progress,
+        )
+        .await
+        .unwrap();
+    let cached = music_service
+        .find_external_id(&music, PlaylistOrigin::Deezer)
+        .await
+        .unwrap();
+    assert_eq!(cached.as_deref(), Some("dz_intro"));
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fuzzy_matches_are_not_cached() {
+    let test_db = TestDatabase::new().await;
+    let export_service = PlaylistExportService::new(test_db.get_db());
+    let music_service = MusicService::new(test_db.get_db());
+    let user = test_db.get_user();
+    let playlist_id = create_playlist(&test_db, "Road trip", PlaylistOrigin::Spotify)
+        .await
+        .id;
+    let provider = FakeProvider::default();
+    let progress = JobProgress::none();
+    let music = create_music(&test_db, "Intro").await;
+    // A match just above the threshold would otherwise win over an ISRC lookup for good
+    let report = vec![TrackMatchEntry {
+        confidence: Some(0.8),
+        ..TrackMatchEntry::known(&music, "dz_intro_live")
+    }];
+
+    export_service
+        .export(
+            &provider,
+            &user,
+            playlist_id,
+            report,
+            false,
+            true,
+            &progress,
+        )
+        .await
+        .unwrap();
+    let cached = music_service
+        .find_external_id(&music, PlaylistOrigin::Deezer)
+        .await
+        .unwrap();
+    assert_eq!(cached, None);
+
+    test_db.drop().await;
+}
@@ -0,0 +1,114 @@
+use swaptun_models::{JobKind, PlaylistOrigin};
+use swaptun_services::{
+    CreatePlaylistMirrorRequest, JobService, PlaylistMirrorService, PlaylistService, TestDatabase,
+};
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{create_music, create_playlist};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_mirror_is_listed_with_the_linked_playlist() {
+    let test_db = TestDatabase::new().await;
+    let mirror_service = PlaylistMirrorService::new(test_db.get_db());
+    let user = test_db.get_user();
+    let playlist = create_playlist(&test_db, "Commute", PlaylistOrigin::Spotify).await;
+
+    let mirror = mirror_service
+        .create(
+            &user,
+            CreatePlaylistMirrorRequest {
+                playlist_id: playlist.id,
+                destination: PlaylistOrigin::AppleMusic,
+                platform_playlist_id: Some("p.commute".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(mirror.platform_playlist_id.as_deref(), Some("p.commute"));
+
+    let mirrors = mirror_service.get_user_mirrors(user.id).await.unwrap();
+    assert_eq!(mirrors.len(), 1);
+    assert_eq!(mirrors[0].playlist.id, playlist.id);
+    assert_eq!(mirrors[0].destination, PlaylistOrigin::AppleMusic);
+
+    // The first push is queued right away
+    let jobs = JobService::new(test_db.get_db())
+        .get_user_jobs(user.id)
+        .await
+        .unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].kind, JobKind::ExportPlaylist);
+
+    mirror_service.delete(user.id, mirror.id).await.unwrap();
+    assert!(mirror_service
+        .get_user_mirrors(user.id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_mirror_to_the_source_platform_is_refused() {
+    let test_db = TestDatabase::new().await;
+    let mirror_service = PlaylistMirrorService::new(test_db.get_db());
+    let playlist = create_playlist(&test_db, "Commute", PlaylistOrigin::Spotify).await;
+
+    let result = mirror_service
+        .create(
+            &test_db.get_user(),
+            CreatePlaylistMirrorRequest {
+                playlist_id: playlist.id,
+                destination: PlaylistOrigin::Spotify,
+                platform_playlist_id: None,
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_only_changed_imports_are_pushed() {
+    let test_db = TestDatabase::new().await;
+    let playlist_service = PlaylistService::new(test_db.get_db());
+    let job_service = JobService::new(test_db.get_db());
+    let user = test_db.get_user();
+    let playlist = create_playlist(&test_db, "Commute", PlaylistOrigin::Spotify).await;
+
+    PlaylistMirrorService::new(test_db.get_db())
+        .create(
+            &user,
+            CreatePlaylistMirrorRequest {
+                playlist_id: playlist.id,
+                destination: PlaylistOrigin::Deezer,
+                platform_playlist_id: None,
+            },
+        )
+        .await
+        .unwrap();
+    let first_push = job_service.claim_next().await.unwrap().unwrap();
+    job_service.succeed(first_push, None).await.unwrap();
+
+    let music_id = create_music(&test_db, "Intro").await.id;
+    playlist_service
+        .sync_musics(&playlist, &[music_id])
+        .await
+        .unwrap();
+    assert_eq!(job_service.queued_exports().await.unwrap().len(), 1);
+
+    // Still queued, the waiting push will read the playlist as it is now
+    playlist_service.sync_musics(&playlist, &[]).await.unwrap();
+    assert_eq!(job_service.queued_exports().await.unwrap().len(), 1);
+
+    let push = job_service.claim_next().await.unwrap().unwrap();
+    job_service.succeed(push, None).await.unwrap();
+    playlist_service.sync_musics(&playlist, &[]).await.unwrap();
+    assert!(job_service.queued_exports().await.unwrap().is_empty());
+
+    test_db.drop().await;
+}
@@ -0,0 +1,133 @@
+use swaptun_models::{PlaylistOrigin, SnapshotReason};
+use swaptun_services::{PlaylistService, SnapshotDiffParams, TestDatabase};
+
+#[path = "common/mod.rs"]
+mod common;
+
+use common::{create_music, create_playlist};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_unchanged_import_records_no_snapshot() {
+    let test_db = TestDatabase::new().await;
+    let playlist_service = PlaylistService::new(test_db.get_db());
+    let user = test_db.get_user();
+    let playlist = create_playlist(&test_db, "Chill", PlaylistOrigin::Deezer).await;
+    let intro = create_music(&test_db, "Intro").await.id;
+
+    playlist_service
+        .sync_musics(&playlist, &[intro])
+        .await
+        .unwrap();
+    playlist_service
+        .sync_musics(&playlist, &[intro])
+        .await
+        .unwrap();
+
+    let snapshots = playlist_service
+        .get_snapshots(playlist.id, user.id)
+        .await
+        .unwrap();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].reason, SnapshotReason::Import);
+    assert_eq!(snapshots[0].music_ids, vec![intro]);
+
+    // The history is only shown to the owner
+    assert!(playlist_service
+        .get_snapshots(playlist.id, user.id + 1)
+        .await
+        .is_err());
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tracks_removed_by_an_import_can_be_restored() {
+    let test_db = TestDatabase::new().await;
+    let playlist_service = PlaylistService::new(test_db.get_db());
+    let user = test_db.get_user();
+    let playlist = create_playlist(&test_db, "Chill", PlaylistOrigin::Deezer).await;
+    let intro = create_music(&test_db, "Intro").await.id;
+    let outro = create_music(&test_db, "Outro").await.id;
+
+    playlist_service
+        .sync_musics(&playlist, &[intro, outro])
+        .await
+        .unwrap();
+    playlist_service
+        .sync_musics(&playlist, &[intro])
+        .await
+        .unwrap();
+
+    let snapshots = playlist_service
+        .get_snapshots(playlist.id, user.id)
+        .await
+        .unwrap();
+    let (newer, older) = (snapshots[0].id, snapshots[1].id);
+    let diff = playlist_service
+        .diff_snapshots(
+            playlist.id,
+            user.id,
+            SnapshotDiffParams {
+                from: older,
+                to: newer,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(diff.added.is_empty());
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].id, outro);
+
+    playlist_service
+        .restore_snapshot(playlist.id, older, user.id)
+        .await
+        .unwrap();
+    let musics = playlist_service
+        .get_playlist_musics(playlist.id)
+        .await
+        .unwrap()
+        .musics;
+    let ids: Vec<i32> = musics.iter().map(|music| music.id).collect();
+    assert_eq!(ids, vec![intro, outro]);
+
+    let snapshots = playlist_service
+        .get_snapshots(playlist.id, user.id)
+        .await
+        .unwrap();
+    assert_eq!(snapshots.len(), 3);
+    assert_eq!(snapshots[0].reason, SnapshotReason::Restore);
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_edits_record_snapshots() {
+    let test_db = TestDatabase::new().await;
+    let playlist_service = PlaylistService::new(test_db.get_db());
+    let user = test_db.get_user();
+    let playlist = create_playlist(&test_db, "Chill", PlaylistOrigin::Deezer).await;
+    let music = create_music(&test_db, "Intro").await;
+    let intro = music.id;
+
+    playlist_service
+        .add_music(&playlist, music.clone())
+        .await
+        .unwrap();
+    playlist_service
+        .remove_music(&playlist, &music, 0)
+        .await
+        .unwrap();
+
+    let snapshots = playlist_service
+        .get_snapshots(playlist.id, user.id)
+        .await
+        .unwrap();
+    assert_eq!(snapshots.len(), 2);
+    assert!(snapshots
+        .iter()
+        .all(|snapshot| snapshot.reason == SnapshotReason::Edit));
+    assert!(snapshots[0].music_ids.is_empty());
+    assert_eq!(snapshots[1].music_ids, vec![intro]);
+
+    test_db.drop().await;
+}
@@ -1,8 +1,12 @@
 use swaptun_services::TestDatabase;
 
 use swaptun_models::PlaylistOrigin;
+use swaptun_services::error::AppError;
 use swaptun_services::CreateUserRequest;
-use swaptun_services::{CreatePlaylistRequest, GetPlaylistsParams, PlaylistService, UserService};
+use swaptun_services::{
+    CreateMusicRequest, CreatePlaylistRequest, GetPlaylistsParams, MusicService, PlaylistService,
+    TrackMatchEntry, UserService,
+};
 
 #[tokio::test]
 async fn test_get_playlists_by_origin() {
@@ -200,6 +204,266 @@ async fn test_user_and_shared_playlists_separation() {
     assert_ne!(user_playlists.vec[0].id, shared_playlists.vec[0].id);
 }
 
+#[tokio::test]
+async fn test_move_music_reorders_playlist() {
+    let test_db = TestDatabase::new().await;
+    let db = test_db.get_db();
+    let playlist_service = PlaylistService::new(db.clone().into());
+    let music_service = MusicService::new(db.clone().into());
+    let user = test_db.get_user();
+
+    let playlist = playlist_service
+        .create(
+            CreatePlaylistRequest {
+                name: "Focus".to_string(),
+                description: None,
+                origin: PlaylistOrigin::Spotify,
+                origin_id: "focus".into(),
+                image_url: None,
+            },
+            user.id,
+        )
+        .await
+        .unwrap();
+
+    let mut music_ids = Vec::new();
+    for title in ["First", "Second", "Third"] {
+        let music = music_service
+            .create(CreateMusicRequest {
+                title: title.to_string(),
+                artist: "Artist".to_string(),
+                album: "Album".to_string(),
+                release_date: "2025-01-01".parse().unwrap(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        music_ids.push(music.id);
+        playlist_service.add_music(&playlist, music).await.unwrap();
+    }
+    let (first, second, third) = (music_ids[0], music_ids[1], music_ids[2]);
+
+    playlist_service
+        .move_music(&playlist, third, 0, 0)
+        .await
+        .unwrap();
+    let entries = playlist_service
+        .music_playlist_repository
+        .find_by_playlist_id(playlist.id)
+        .await
+        .unwrap();
+    let ids: Vec<i32> = entries.iter().map(|entry| entry.music_id).collect();
+    assert_eq!(ids, vec![third, first, second]);
+    let positions: Vec<i32> = entries.iter().map(|entry| entry.position).collect();
+    assert_eq!(positions, vec![0, 1, 2]);
+
+    // A position past the end moves the music last
+    playlist_service
+        .move_music(&playlist, third, 0, 10)
+        .await
+        .unwrap();
+    let musics = playlist_service
+        .get_playlist_musics(playlist.id)
+        .await
+        .unwrap()
+        .musics;
+    let ids: Vec<i32> = musics.iter().map(|music| music.id).collect();
+    assert_eq!(ids, vec![first, second, third]);
+
+    test_db.drop().await;
+}
+
+#[tokio::test]
+async fn test_sync_musics_keeps_order_and_duplicates() {
+    let test_db = TestDatabase::new().await;
+    let db = test_db.get_db();
+    let playlist_service = PlaylistService::new(db.clone().into());
+    let music_service = MusicService::new(db.clone().into());
+    let user = test_db.get_user();
+
+    let playlist = playlist_service
+        .create(
+            CreatePlaylistRequest {
+                name: "Workout".to_string(),
+                description: None,
+                origin: PlaylistOrigin::Spotify,
+                origin_id: "workout".into(),
+                image_url: None,
+            },
+            user.id,
+        )
+        .await
+        .unwrap();
+
+    let mut music_ids = Vec::new();
+    for title in ["Intro", "Middle"] {
+        let music = music_service
+            .create(CreateMusicRequest {
+                title: title.to_string(),
+                artist: "Artist".to_string(),
+                album: "Album".to_string(),
+                release_date: "2025-01-01".parse().unwrap(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        music_ids.push(music.id);
+    }
+    let (intro, middle) = (music_ids[0], music_ids[1]);
+
+    // The same track at the start and the end is kept twice
+    playlist_service
+        .sync_musics(&playlist, &[intro, middle, intro])
+        .await
+        .unwrap();
+    let musics = playlist_service
+        .get_playlist_musics(playlist.id)
+        .await
+        .unwrap()
+        .musics;
+    let ids: Vec<i32> = musics.iter().map(|music| music.id).collect();
+    assert_eq!(ids, vec![intro, middle, intro]);
+
+    // Moving the second copy only moves that entry
+    playlist_service
+        .move_music(&playlist, intro, 1, 0)
+        .await
+        .unwrap();
+    playlist_service
+        .move_music(&playlist, middle, 0, 0)
+        .await
+        .unwrap();
+    let musics = playlist_service
+        .get_playlist_musics(playlist.id)
+        .await
+        .unwrap()
+        .musics;
+    let ids: Vec<i32> = musics.iter().map(|music| music.id).collect();
+    assert_eq!(ids, vec![middle, intro, intro]);
+
+    // Removing a copy keeps the other one
+    let intro_music = music_service.find_by_id(intro).await.unwrap().unwrap();
+    playlist_service
+        .remove_music(&playlist, &intro_music, 1)
+        .await
+        .unwrap();
+    let musics = playlist_service
+        .get_playlist_musics(playlist.id)
+        .await
+        .unwrap()
+        .musics;
+    let ids: Vec<i32> = musics.iter().map(|music| music.id).collect();
+    assert_eq!(ids, vec![middle, intro]);
+
+    // Syncing again puts the tracks back in order
+    playlist_service
+        .sync_musics(&playlist, &[intro, middle])
+        .await
+        .unwrap();
+    let musics = playlist_service
+        .get_playlist_musics(playlist.id)
+        .await
+        .unwrap()
+        .musics;
+    let ids: Vec<i32> = musics.iter().map(|music| music.id).collect();
+    assert_eq!(ids, vec![intro, middle]);
+
+    test_db.drop().await;
+}
+
+#[tokio::test]
+async fn test_liked_songs_playlist_per_origin() {
+    let test_db = TestDatabase::new().await;
+    let db = test_db.get_db();
+    let playlist_service = PlaylistService::new(db.clone().into());
+    let user = test_db.get_user();
+
+    let spotify = playlist_service
+        .create_or_get(
+            CreatePlaylistRequest::liked_songs(PlaylistOrigin::Spotify),
+            &user,
+        )
+        .await
+        .unwrap();
+    let apple = playlist_service
+        .create_or_get(
+            CreatePlaylistRequest::liked_songs(PlaylistOrigin::AppleMusic),
+            &user,
+        )
+        .await
+        .unwrap();
+    assert_ne!(spotify.id, apple.id);
+    assert_eq!(apple.origin, PlaylistOrigin::AppleMusic);
+
+    // Importing again reuses the playlist of the platform
+    let spotify_again = playlist_service
+        .create_or_get(
+            CreatePlaylistRequest::liked_songs(PlaylistOrigin::Spotify),
+            &user,
+        )
+        .await
+        .unwrap();
+    assert_eq!(spotify_again.id, spotify.id);
+
+    test_db.drop().await;
+}
+
+#[tokio::test]
+async fn test_check_matches_rejects_musics_outside_playlist() {
+    let test_db = TestDatabase::new().await;
+    let db = test_db.get_db();
+    let playlist_service = PlaylistService::new(db.clone().into());
+    let music_service = MusicService::new(db.clone().into());
+    let user = test_db.get_user();
+
+    let playlist = playlist_service
+        .create(
+            CreatePlaylistRequest {
+                name: "Road Trip".to_string(),
+                description: None,
+                origin: PlaylistOrigin::Spotify,
+                origin_id: "road_trip".into(),
+                image_url: None,
+            },
+            user.id,
+        )
+        .await
+        .unwrap();
+
+    let mut musics = Vec::new();
+    for title in ["Inside", "Outside"] {
+        let music = music_service
+            .create(CreateMusicRequest {
+                title: title.to_string(),
+                artist: "Artist".to_string(),
+                album: "Album".to_string(),
+                release_date: "2025-01-01".parse().unwrap(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        musics.push(music);
+    }
+    playlist_service
+        .sync_musics(&playlist, &[musics[0].id])
+        .await
+        .unwrap();
+
+    let inside = TrackMatchEntry::overridden(&musics[0], "track-1");
+    playlist_service
+        .check_matches(playlist.id, &[inside.clone()])
+        .await
+        .unwrap();
+
+    let outside = TrackMatchEntry::overridden(&musics[1], "track-2");
+    let result = playlist_service
+        .check_matches(playlist.id, &[inside, outside])
+        .await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+
+    test_db.drop().await;
+}
+
 async fn create_test_playlists(playlist_service: &PlaylistService, user_id: i32) {
     // Create Spotify playlists
     let spotify_playlist1 = CreatePlaylistRequest {
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use swaptun_models::{JobKind, MusicModel, PlaylistOrigin, UserModel};
+use swaptun_services::error::AppError;
+use swaptun_services::{
+    AddTokenRequest, GetJobsParams, ImportSelection, JobProgress, JobService, MusicProvider,
+    PlaylistExport, ProviderRegistry, RemotePlaylist, SyncScheduler, TestDatabase, TrackMatchEntry,
+};
+
+/// Provider whose account is connected by a fixed list of users
+struct ConnectedProvider {
+    origin: PlaylistOrigin,
+    user_ids: Vec<i32>,
+}
+
+#[async_trait]
+impl MusicProvider for ConnectedProvider {
+    fn origin(&self) -> PlaylistOrigin {
+        self.origin.clone()
+    }
+
+    async fn connect(&self, _user: &UserModel, _request: AddTokenRequest) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn connected_users(&self) -> Result<Vec<i32>, AppError> {
+        Ok(self.user_ids.clone())
+    }
+
+    async fn list_playlists(&self, _user: &UserModel) -> Result<Vec<RemotePlaylist>, AppError> {
+        Ok(vec![])
+    }
+
+    async fn import_playlists(
+        &self,
+        _user: &UserModel,
+        _selection: &ImportSelection,
+        _progress: &JobProgress,
+    ) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn search_track(
+        &self,
+        _user: &UserModel,
+        music: &MusicModel,
+    ) -> Result<TrackMatchEntry, AppError> {
+        Ok(TrackMatchEntry::not_found(music))
+    }
+
+    async fn preview_playlist(
+        &self,
+        _user: &UserModel,
+        _playlist_id: i32,
+        _progress: &JobProgress,
+    ) -> Result<Vec<TrackMatchEntry>, AppError> {
+        Ok(vec![])
+    }
+
+    async fn create_playlist_from_matches(
+        &self,
+        _user: &UserModel,
+        _playlist_id: i32,
+        report: Vec<TrackMatchEntry>,
+        _progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        Ok(PlaylistExport {
+            playlist_id: String::new(),
+            report,
+        })
+    }
+
+    async fn update_playlist_from_matches(
+        &self,
+        _user: &UserModel,
+        platform_playlist_id: &str,
+        report: Vec<TrackMatchEntry>,
+        _progress: &JobProgress,
+    ) -> Result<PlaylistExport, AppError> {
+        Ok(PlaylistExport {
+            playlist_id: platform_playlist_id.to_string(),
+            report,
+        })
+    }
+
+    async fn disconnect(&self, _user: &UserModel) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+fn scheduler(test_db: &TestDatabase, user_id: i32) -> SyncScheduler {
+    let mut registry = ProviderRegistry::new(test_db.get_db());
+    for origin in [PlaylistOrigin::Deezer, PlaylistOrigin::Spotify] {
+        registry.register(Arc::new(ConnectedProvider {
+            origin,
+            user_ids: vec![user_id],
+        }));
+    }
+    SyncScheduler::with_registry(test_db.get_db(), registry)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_imports_are_spread_over_the_interval() {
+    let test_db = TestDatabase::new().await;
+    let user = test_db.get_user();
+    let scheduler = scheduler(&test_db, user.id);
+
+    let scheduled = scheduler
+        .schedule_imports(Duration::from_secs(3600))
+        .await
+        .unwrap();
+    assert_eq!(scheduled, 2);
+
+    let jobs = JobService::new(test_db.get_db())
+        .get_user_jobs(user.id, GetJobsParams::default())
+        .await
+        .unwrap();
+    assert_eq!(jobs.len(), 2);
+    assert!(jobs.iter().all(|job| job.kind == JobKind::ImportPlaylists));
+    let gap = (jobs[0].run_at - jobs[1].run_at).num_seconds().abs();
+    assert!((1795..=1805).contains(&gap), "unexpected gap {}", gap);
+
+    test_db.drop().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_pending_imports_are_not_scheduled_twice() {
+    let test_db = TestDatabase::new().await;
+    let user = test_db.get_user();
+    let scheduler = scheduler(&test_db, user.id);
+
+    scheduler
+        .schedule_imports(Duration::from_secs(3600))
+        .await
+        .unwrap();
+    let scheduled = scheduler
+        .schedule_imports(Duration::from_secs(3600))
+        .await
+        .unwrap();
+    assert_eq!(scheduled, 0);
+
+    let jobs = JobService::new(test_db.get_db())
+        .get_user_jobs(user.id, GetJobsParams::default())
+        .await
+        .unwrap();
+    assert_eq!(jobs.len(), 2);
+
+    test_db.drop().await;
+}
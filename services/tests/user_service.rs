@@ -491,6 +491,7 @@ async fn test_reset_password_success() {
         user_id: user.id,
         username: user.username.clone(),
         role: user.role.clone(),
+        scope: None,
     };
 
     // Test reset password with valid token
@@ -527,6 +528,7 @@ async fn test_reset_password_expired_token() {
         user_id: user.id,
         username: user.username.clone(),
         role: user.role.clone(),
+        scope: None,
     };
 
     // Test reset password with expired token